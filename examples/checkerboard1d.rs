@@ -1,28 +1,29 @@
 extern crate noisy;
 
+use noisy::utils::to_unit;
 use noisy::gen::{ NoiseGen, Checkerboard };
 
 // Width of the output in columns.
-static WIDTH: usize = 80;
+static WIDTH: i64 = 80;
 
 // A five color gradient used for the output.
-static GRADIENT: [&'static str; 2] = [" ", "█"];
+static GRADIENT: [&str; 2] = [" ", "█"];
 
 fn main() {
     // Create a new simplex instance.
     let checkerboard = Checkerboard::new();
 
     // Iterate over the columnss.
-    for x in range(-WIDTH/2, WIDTH/2) {
+    for x in -WIDTH/2..WIDTH/2 {
         // Generate a noise value using the x coordinate.
         let mut val = checkerboard.noise1d(x as f64 * 0.5);
 
         // Since the result is within [-1, 1], scale and offset the result to [0, 1].
-        val = (val + 1.0) * 0.5;
+        val = to_unit(val);
 
         // Print the columns in the row.
         print!("{}", GRADIENT[val as usize]);
     }
     // Start a new row.
-    println!("");
+    println!();
 }
@@ -1,5 +1,6 @@
 extern crate noisy;
 
+use noisy::utils::to_unit;
 use noisy::gen::{ NoiseGen, Checkerboard };
 
 // Width of the output in columns.
@@ -8,7 +9,7 @@ static WIDTH: usize = 80;
 static HEIGHT: usize = 80;
 
 // A five color gradient used for the output.
-static GRADIENT: [&'static str; 2] = [" ", "█"];
+static GRADIENT: [&str; 2] = [" ", "█"];
 
 fn main() {
     // Create a new simplex instance.
@@ -16,9 +17,9 @@ fn main() {
 
     // Iterate over the rows.
     // HEIGHT is divided by two for a better aspect ratio.
-    for y in range(0, HEIGHT / 2) {
+    for y in 0..HEIGHT / 2 {
         // Iterate over the columns in the rows.
-        for x in range(0, WIDTH) {
+        for x in 0..WIDTH {
             // Generate a noise value using the x and y coordinates.
             let mut val = checkerboard.noise2d(
                 x as f64 * 0.5,
@@ -27,12 +28,12 @@ fn main() {
 
             // Since the result is within [-1, 1],
             // scale and offset the result to [0, 1].
-            val = (val + 1.0) * 0.5;
+            val = to_unit(val);
 
             // Print the columns in the row.
             print!("{}", GRADIENT[val as usize]);
         }
         // Start a new row.
-        println!("");
+        println!();
     }
 }
@@ -0,0 +1,41 @@
+extern crate noisy;
+
+use noisy::utils::to_unit;
+use noisy::gen::{ NoiseGen, DomainWarp2, Simplex };
+
+// Width of the output in columns.
+static WIDTH: usize = 80;
+// Height of the output in rows.
+static HEIGHT: usize = 80;
+
+// A five color gradient used for the output.
+static GRADIENT: [&str; 5] = [" ", "░", "▒", "▓", "█"];
+
+fn main() {
+    // Create a simplex instance warped twice by two independently seeded stages.
+    let warped = DomainWarp2::new(Simplex::new(), 1, 4.0, 2, 4.0);
+
+    // Iterate over the rows.
+    // HEIGHT is divided by two for a better aspect ratio.
+    for y in 0..HEIGHT / 2 {
+        // Iterate over the columns in the rows.
+        for x in 0..WIDTH {
+            // Generate a noise value using the x and y coordinates.
+            let mut val = warped.noise2d(
+                123.0 + x as f64 * 0.02,
+                132.0 + y as f64 * 0.02
+            );
+
+            // Since the result is within [-1, 1], scale and offset the result to [0, 1].
+            val = to_unit(val);
+
+            // Apply the result to the 5 color gradient.
+            val *= 5.0;
+
+            // Print the columns in the row.
+            print!("{}", GRADIENT[val as usize]);
+        }
+        // Start a new row.
+        println!();
+    }
+}
@@ -1,7 +1,8 @@
-use std::rand::{ weak_rng, Rng, XorShiftRng };
+use rand::{ Rng, SeedableRng };
+use rand_xorshift::XorShiftRng;
 use test::Bencher;
 
-use gen::{ NoiseGen, Checkerboard };
+use crate::gen::{ NoiseGen, Checkerboard };
 
 #[bench]
 fn bench_checkerboard_new(b: &mut Bencher) {
@@ -12,7 +13,7 @@ fn bench_checkerboard_new(b: &mut Bencher) {
 
 #[bench]
 fn bench_checkerboard_noise1d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let checkerboard = Checkerboard::new();
     b.iter(|| {
         checkerboard.noise1d(rng.gen());
@@ -21,7 +22,7 @@ fn bench_checkerboard_noise1d(b: &mut Bencher) {
 
 #[bench]
 fn bench_checkerboard_noise2d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let checkerboard = Checkerboard::new();
     b.iter(|| {
         checkerboard.noise2d(
@@ -33,7 +34,7 @@ fn bench_checkerboard_noise2d(b: &mut Bencher) {
 
 #[bench]
 fn bench_checkerboard_noise3d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let checkerboard = Checkerboard::new();
     b.iter(|| {
         checkerboard.noise3d(
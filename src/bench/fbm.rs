@@ -0,0 +1,30 @@
+use rand::{ Rng, SeedableRng };
+use rand_xorshift::XorShiftRng;
+use test::Bencher;
+
+use crate::gen::{ NoiseGen, Fbm, Simplex };
+
+#[bench]
+fn bench_fbm_simplex_noise2d(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let fbm = Fbm::new(Simplex::new(), 4);
+    b.iter(|| {
+        fbm.noise2d(
+            rng.gen(),
+            rng.gen()
+        );
+    })
+}
+
+#[bench]
+fn bench_fbm_simplex_noise3d(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let fbm = Fbm::new(Simplex::new(), 4);
+    b.iter(|| {
+        fbm.noise3d(
+            rng.gen(),
+            rng.gen(),
+            rng.gen()
+        );
+    })
+}
@@ -1,3 +1,7 @@
 mod simplex;
 mod perlin;
 mod checkerboard;
+mod fbm;
+mod worley;
+mod value;
+mod noise_map;
@@ -0,0 +1,21 @@
+use test::Bencher;
+
+use crate::gen::{ Fbm, Simplex };
+use crate::map::NoiseMap;
+
+#[bench]
+fn bench_noise_map_generate2d(b: &mut Bencher) {
+    let fbm = Fbm::new(Simplex::new(), 4);
+    b.iter(|| {
+        NoiseMap::generate2d(&fbm, 256, 256, (0.0, 0.0), (0.02, 0.02))
+    })
+}
+
+#[cfg(feature = "rayon")]
+#[bench]
+fn bench_noise_map_generate2d_parallel(b: &mut Bencher) {
+    let fbm = Fbm::new(Simplex::new(), 4);
+    b.iter(|| {
+        NoiseMap::generate2d_parallel(&fbm, 256, 256, (0.0, 0.0), (0.02, 0.02))
+    })
+}
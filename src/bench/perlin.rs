@@ -1,7 +1,8 @@
-use std::rand::{ weak_rng, Rng, XorShiftRng };
+use rand::{ Rng, SeedableRng };
+use rand_xorshift::XorShiftRng;
 use test::Bencher;
 
-use gen::{ NoiseGen, Perlin };
+use crate::gen::{ NoiseGen, Perlin };
 
 #[bench]
 fn bench_perlin_new(b: &mut Bencher) {
@@ -12,7 +13,7 @@ fn bench_perlin_new(b: &mut Bencher) {
 
 #[bench]
 fn bench_perlin_from_rng(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     b.iter(|| {
         Perlin::from_rng(&mut rng);
     })
@@ -20,7 +21,7 @@ fn bench_perlin_from_rng(b: &mut Bencher) {
 
 #[bench]
 fn bench_perlin_noise1d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let perlin = Perlin::from_rng(&mut rng);
     b.iter(|| {
         perlin.noise1d(rng.gen());
@@ -29,7 +30,7 @@ fn bench_perlin_noise1d(b: &mut Bencher) {
 
 #[bench]
 fn bench_perlin_noise2d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let perlin = Perlin::from_rng(&mut rng);
     b.iter(|| {
         perlin.noise2d(
@@ -41,7 +42,7 @@ fn bench_perlin_noise2d(b: &mut Bencher) {
 
 #[bench]
 fn bench_perlin_noise3d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let perlin = Perlin::from_rng(&mut rng);
     b.iter(|| {
         perlin.noise3d(
@@ -51,3 +52,86 @@ fn bench_perlin_noise3d(b: &mut Bencher) {
         );
     })
 }
+
+#[bench]
+fn bench_perlin_fill_grid2d_f32(b: &mut Bencher) {
+    let perlin = Perlin::new();
+    let mut buf: Vec<f32> = vec![0.0; 64 * 64];
+    b.iter(|| {
+        perlin.fill_grid2d_f32(buf.as_mut_slice(), 64, 64, (0.0, 0.0), (0.02, 0.02));
+    })
+}
+
+#[cfg(feature = "simd")]
+#[bench]
+fn bench_perlin_fill_grid2d_f32_simd(b: &mut Bencher) {
+    let perlin = Perlin::new();
+    let mut buf: Vec<f32> = vec![0.0; 64 * 64];
+    b.iter(|| {
+        perlin.fill_grid2d_f32_simd(buf.as_mut_slice(), 64, 64, (0.0, 0.0), (0.02, 0.02));
+    })
+}
+
+#[bench]
+fn bench_perlin_fill2d(b: &mut Bencher) {
+    let perlin = Perlin::new();
+    let mut buf: Vec<f64> = vec![0.0; 64 * 64];
+    b.iter(|| {
+        perlin.fill2d(buf.as_mut_slice(), 64, (0.0, 0.0), (0.02, 0.02));
+    })
+}
+
+#[bench]
+fn bench_perlin_fill2d_naive(b: &mut Bencher) {
+    let perlin = Perlin::new();
+    let mut buf: Vec<f64> = vec![0.0; 64 * 64];
+    b.iter(|| {
+        for y in 0..64 {
+            for x in 0..64 {
+                buf[y * 64 + x] = perlin.noise2d(x as f64 * 0.02, y as f64 * 0.02);
+            }
+        }
+    })
+}
+
+#[bench]
+fn bench_perlin_fill3d(b: &mut Bencher) {
+    let perlin = Perlin::new();
+    let mut buf: Vec<f64> = vec![0.0; 64 * 64 * 64];
+    b.iter(|| {
+        perlin.fill3d(buf.as_mut_slice(), (64, 64, 64), [0.0; 3], [0.02; 3]);
+    })
+}
+
+#[bench]
+fn bench_perlin_fill3d_naive(b: &mut Bencher) {
+    let perlin = Perlin::new();
+    let mut buf: Vec<f64> = vec![0.0; 64 * 64 * 64];
+    b.iter(|| {
+        for z in 0..64 {
+            for y in 0..64 {
+                for x in 0..64 {
+                    buf[(z * 64 + y) * 64 + x] = perlin.noise3d(x as f64 * 0.02, y as f64 * 0.02, z as f64 * 0.02);
+                }
+            }
+        }
+    })
+}
+
+/// Run with and without `--features unsafe_perm` to compare the checked and
+/// unchecked permutation lookups.
+#[bench]
+fn bench_perlin_noise3d_block(b: &mut Bencher) {
+    let perlin = Perlin::new();
+    b.iter(|| {
+        let mut sum: f64 = 0.0;
+        for z in 0..16 {
+            for y in 0..16 {
+                for x in 0..16 {
+                    sum += perlin.noise3d(x as f64 * 0.1, y as f64 * 0.1, z as f64 * 0.1);
+                }
+            }
+        }
+        sum
+    })
+}
@@ -1,7 +1,8 @@
-use std::rand::{ weak_rng, Rng, XorShiftRng };
+use rand::{ Rng, SeedableRng };
+use rand_xorshift::XorShiftRng;
 use test::Bencher;
 
-use gen::{ NoiseGen, Simplex };
+use crate::gen::{ NoiseGen, Simplex };
 
 #[bench]
 fn bench_simplex_new(b: &mut Bencher) {
@@ -12,7 +13,7 @@ fn bench_simplex_new(b: &mut Bencher) {
 
 #[bench]
 fn bench_simplex_from_rng(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     b.iter(|| {
         Simplex::from_rng(&mut rng);
     })
@@ -20,7 +21,7 @@ fn bench_simplex_from_rng(b: &mut Bencher) {
 
 #[bench]
 fn bench_simplex_noise1d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let simplex = Simplex::from_rng(&mut rng);
     b.iter(|| {
         simplex.noise1d(rng.gen());
@@ -29,7 +30,7 @@ fn bench_simplex_noise1d(b: &mut Bencher) {
 
 #[bench]
 fn bench_simplex_noise2d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let simplex = Simplex::from_rng(&mut rng);
     b.iter(|| {
         simplex.noise2d(
@@ -41,7 +42,7 @@ fn bench_simplex_noise2d(b: &mut Bencher) {
 
 #[bench]
 fn bench_simplex_noise3d(b: &mut Bencher) {
-    let mut rng: XorShiftRng = weak_rng();
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
     let simplex = Simplex::from_rng(&mut rng);
     b.iter(|| {
         simplex.noise3d(
@@ -51,3 +52,123 @@ fn bench_simplex_noise3d(b: &mut Bencher) {
         );
     })
 }
+
+#[bench]
+fn bench_simplex_fill_grid2d_f32(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    let mut buf: Vec<f32> = vec![0.0; 64 * 64];
+    b.iter(|| {
+        simplex.fill_grid2d_f32(buf.as_mut_slice(), 64, 64, (0.0, 0.0), (0.02, 0.02));
+    })
+}
+
+#[cfg(feature = "simd")]
+#[bench]
+fn bench_simplex_fill_grid2d_f32_simd(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    let mut buf: Vec<f32> = vec![0.0; 64 * 64];
+    b.iter(|| {
+        simplex.fill_grid2d_f32_simd(buf.as_mut_slice(), 64, 64, (0.0, 0.0), (0.02, 0.02));
+    })
+}
+
+#[bench]
+fn bench_simplex_noise3d_ray(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    b.iter(|| {
+        let mut acc: f64 = 0.0;
+        for n in 0..256 {
+            let t = n as f64 * 0.05;
+            acc += simplex.noise3d(1.0 + t * 0.48, 2.0 + t * 0.6, 3.0 + t * 0.64);
+        }
+        acc
+    })
+}
+
+#[bench]
+fn bench_simplex_ray_sampler(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    b.iter(|| {
+        simplex.ray_sampler((1.0, 2.0, 3.0), (0.48, 0.6, 0.64), 0.05)
+            .take(256)
+            .fold(0.0, |acc, val| acc + val)
+    })
+}
+
+#[bench]
+fn bench_simplex_noise4d(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let simplex = Simplex::new();
+    b.iter(|| {
+        simplex.noise4d(
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen()
+        );
+    })
+}
+
+#[bench]
+fn bench_simplex_fill2d(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    let mut buf: Vec<f64> = vec![0.0; 64 * 64];
+    b.iter(|| {
+        simplex.fill2d(buf.as_mut_slice(), 64, (0.0, 0.0), (0.02, 0.02));
+    })
+}
+
+#[bench]
+fn bench_simplex_fill2d_naive(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    let mut buf: Vec<f64> = vec![0.0; 64 * 64];
+    b.iter(|| {
+        for y in 0..64 {
+            for x in 0..64 {
+                buf[y * 64 + x] = simplex.noise2d(x as f64 * 0.02, y as f64 * 0.02);
+            }
+        }
+    })
+}
+
+#[bench]
+fn bench_simplex_fill3d(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    let mut buf: Vec<f64> = vec![0.0; 64 * 64 * 64];
+    b.iter(|| {
+        simplex.fill3d(buf.as_mut_slice(), (64, 64, 64), [0.0; 3], [0.02; 3]);
+    })
+}
+
+#[bench]
+fn bench_simplex_fill3d_naive(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    let mut buf: Vec<f64> = vec![0.0; 64 * 64 * 64];
+    b.iter(|| {
+        for z in 0..64 {
+            for y in 0..64 {
+                for x in 0..64 {
+                    buf[(z * 64 + y) * 64 + x] = simplex.noise3d(x as f64 * 0.02, y as f64 * 0.02, z as f64 * 0.02);
+                }
+            }
+        }
+    })
+}
+
+/// Run with and without `--features unsafe_perm` to compare the checked and
+/// unchecked permutation lookups.
+#[bench]
+fn bench_simplex_noise3d_block(b: &mut Bencher) {
+    let simplex = Simplex::new();
+    b.iter(|| {
+        let mut sum: f64 = 0.0;
+        for z in 0..16 {
+            for y in 0..16 {
+                for x in 0..16 {
+                    sum += simplex.noise3d(x as f64 * 0.1, y as f64 * 0.1, z as f64 * 0.1);
+                }
+            }
+        }
+        sum
+    })
+}
@@ -0,0 +1,54 @@
+use rand::{ Rng, SeedableRng };
+use rand_xorshift::XorShiftRng;
+use test::Bencher;
+
+use crate::gen::{ NoiseGen, Value };
+
+#[bench]
+fn bench_value_new(b: &mut Bencher) {
+    b.iter(|| {
+        Value::new();
+    })
+}
+
+#[bench]
+fn bench_value_from_rng(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    b.iter(|| {
+        Value::from_rng(&mut rng);
+    })
+}
+
+#[bench]
+fn bench_value_noise1d(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let value = Value::from_rng(&mut rng);
+    b.iter(|| {
+        value.noise1d(rng.gen());
+    })
+}
+
+#[bench]
+fn bench_value_noise2d(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let value = Value::from_rng(&mut rng);
+    b.iter(|| {
+        value.noise2d(
+            rng.gen(),
+            rng.gen()
+        );
+    })
+}
+
+#[bench]
+fn bench_value_noise3d(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let value = Value::from_rng(&mut rng);
+    b.iter(|| {
+        value.noise3d(
+            rng.gen(),
+            rng.gen(),
+            rng.gen()
+        );
+    })
+}
@@ -0,0 +1,50 @@
+use rand::{ Rng, SeedableRng };
+use rand_xorshift::XorShiftRng;
+use test::Bencher;
+
+use crate::gen::{ NoiseGen, Worley, DistanceKind };
+
+#[bench]
+fn bench_worley_new(b: &mut Bencher) {
+    b.iter(|| {
+        Worley::new();
+    })
+}
+
+#[bench]
+fn bench_worley_noise2d(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let worley = Worley::new();
+    b.iter(|| {
+        worley.noise2d(
+            rng.gen(),
+            rng.gen()
+        );
+    })
+}
+
+#[bench]
+fn bench_worley_noise3d(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let worley = Worley::new();
+    b.iter(|| {
+        worley.noise3d(
+            rng.gen(),
+            rng.gen(),
+            rng.gen()
+        );
+    })
+}
+
+#[bench]
+fn bench_worley_noise3d_euclidean_squared(b: &mut Bencher) {
+    let mut rng: XorShiftRng = XorShiftRng::from_entropy();
+    let worley = Worley::new().with_distance(DistanceKind::EuclideanSquared);
+    b.iter(|| {
+        worley.noise3d(
+            rng.gen(),
+            rng.gen(),
+            rng.gen()
+        );
+    })
+}
@@ -0,0 +1,81 @@
+//! Billow noise: fractional Brownian motion over the folded output of a
+//! generator.
+
+use crate::gen::{ NoiseGen, Fbm };
+use crate::gen::modifier::Abs;
+
+/// A generator summing octaves of `2 * |source| - 1`.
+///
+/// Each octave folds the source around zero before it is weighed and
+/// summed, which gives the puffy, rounded look used for clouds and rolling
+/// hills. The octaves are weighed and normalized as in `Fbm`, so the output
+/// stays within [-1, 1]. This is the same as `Fbm<Abs<G>>`, packaged with
+/// the octave controls of `Fbm`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Billow<G> {
+    fbm: Fbm<Abs<G>>
+}
+
+impl<G: NoiseGen> Billow<G> {
+    /// Stacks `octaves` folded octaves of `source`, with a lacunarity of 2.0
+    /// and a persistence of 0.5.
+    ///
+    /// Panics if `octaves` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Billow, Simplex};
+    ///
+    /// let clouds = Billow::new(Simplex::new(), 5);
+    /// let val = clouds.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, octaves: u32) -> Billow<G> {
+        Billow { fbm: Fbm::new(Abs::new(source), octaves) }
+    }
+
+    /// Sets the frequency multiplier between successive octaves.
+    pub fn with_lacunarity(self, lacunarity: f64) -> Billow<G> {
+        Billow { fbm: self.fbm.with_lacunarity(lacunarity) }
+    }
+
+    /// Sets a separate frequency multiplier between successive octaves for
+    /// the x, y and z axes. The w axis of `noise4d` follows z.
+    pub fn with_axis_lacunarity(self, lacunarity: (f64, f64, f64)) -> Billow<G> {
+        Billow { fbm: self.fbm.with_axis_lacunarity(lacunarity) }
+    }
+
+    /// Sets the amplitude multiplier between successive octaves.
+    ///
+    /// Panics if `persistence` is not finite and positive.
+    pub fn with_persistence(self, persistence: f64) -> Billow<G> {
+        Billow { fbm: self.fbm.with_persistence(persistence) }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        self.fbm.source().source()
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Billow<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.fbm.noise1d(xin)
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.fbm.noise2d(xin, yin)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.fbm.noise3d(xin, yin, zin)
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.fbm.noise4d(xin, yin, zin, win)
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.fbm.bounds()
+    }
+}
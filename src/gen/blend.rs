@@ -0,0 +1,83 @@
+//! Blends two generators using a third one as the weight.
+
+use crate::utils::lerp;
+use crate::gen::NoiseGen;
+
+/// A generator interpolating linearly between two generators, weighted by
+/// the output of a control generator.
+///
+/// A control value of -1 gives the output of `first`, a value of 1 gives the
+/// output of `second`, and anything in between mixes the two. Unlike
+/// `Select`, which switches sources at thresholds, the transition is spread
+/// over the whole range of the control, which makes for the smoothest
+/// changes between two kinds of terrain.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Blend<A, B, C> {
+    first: A,
+    second: B,
+    control: C
+}
+
+impl<A: NoiseGen, B: NoiseGen, C: NoiseGen> Blend<A, B, C> {
+    /// Wraps `first` and `second`, blending them by the output of `control`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Blend, Perlin, RidgedMulti, Simplex};
+    ///
+    /// let terrain = Blend::new(Perlin::new(), RidgedMulti::new(Simplex::new(), 4), Simplex::new());
+    /// let val = terrain.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(first: A, second: B, control: C) -> Blend<A, B, C> {
+        Blend { first: first, second: second, control: control }
+    }
+
+    /// Returns the generator used where the control is -1.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the generator used where the control is 1.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Returns the control generator.
+    pub fn control(&self) -> &C {
+        &self.control
+    }
+}
+
+impl<A: NoiseGen, B: NoiseGen, C: NoiseGen> NoiseGen for Blend<A, B, C> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        let weight: f64 = (self.control.noise1d(xin) + 1.0) * 0.5;
+
+        lerp(weight, self.first.noise1d(xin), self.second.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let weight: f64 = (self.control.noise2d(xin, yin) + 1.0) * 0.5;
+
+        lerp(weight, self.first.noise2d(xin, yin), self.second.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let weight: f64 = (self.control.noise3d(xin, yin, zin) + 1.0) * 0.5;
+
+        lerp(weight, self.first.noise3d(xin, yin, zin), self.second.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        let weight: f64 = (self.control.noise4d(xin, yin, zin, win) + 1.0) * 0.5;
+
+        lerp(weight, self.first.noise4d(xin, yin, zin, win), self.second.noise4d(xin, yin, zin, win))
+    }
+
+    /// The output is either source or a blend of both.
+    fn bounds(&self) -> (f64, f64) {
+        let (first, second) = (self.first.bounds(), self.second.bounds());
+
+        (first.0.min(second.0), first.1.max(second.1))
+    }
+}
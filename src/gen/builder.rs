@@ -0,0 +1,75 @@
+//! Fluent construction of generator pipelines.
+
+use core::fmt;
+use alloc::boxed::Box;
+
+use crate::gen::{ NoiseGen, Checked, Fbm };
+use crate::gen::modifier::{ ScaleBias, Clamp, Abs };
+
+/// Builds a generator pipeline one stage at a time.
+///
+/// Each method wraps the pipeline built so far in another stage, so the
+/// stages are applied in the order they are written. The pipeline is boxed
+/// along the way, which trades a virtual call per stage for not having to
+/// spell out types such as `Clamp<ScaleBias<Fbm<Simplex>>>`.
+pub struct NoiseBuilder {
+    gen: Box<dyn NoiseGen>
+}
+
+impl NoiseBuilder {
+    /// Starts a pipeline from `source`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, NoiseBuilder, Simplex};
+    ///
+    /// let terrain = NoiseBuilder::new(Simplex::new())
+    ///     .fbm(4)
+    ///     .scale_bias(0.5, 0.5)
+    ///     .clamp(0.0, 1.0)
+    ///     .build();
+    /// let val = terrain.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new<G: NoiseGen + 'static>(source: G) -> NoiseBuilder {
+        NoiseBuilder { gen: Box::new(source) }
+    }
+
+    /// Stacks `octaves` octaves of the pipeline, as in `Fbm::new`.
+    pub fn fbm(self, octaves: u32) -> NoiseBuilder {
+        NoiseBuilder::new(Fbm::new(self.gen, octaves))
+    }
+
+    /// Multiplies the output by `scale` and adds `bias`, as in `ScaleBias`.
+    pub fn scale_bias(self, scale: f64, bias: f64) -> NoiseBuilder {
+        NoiseBuilder::new(ScaleBias::new(self.gen, scale, bias))
+    }
+
+    /// Clamps the output to [lower, upper], as in `Clamp`.
+    pub fn clamp(self, lower: f64, upper: f64) -> NoiseBuilder {
+        NoiseBuilder::new(Clamp::new(self.gen, lower, upper))
+    }
+
+    /// Folds the output with `Abs`.
+    pub fn abs(self) -> NoiseBuilder {
+        NoiseBuilder::new(Abs::new(self.gen))
+    }
+
+    /// Returns the finished pipeline.
+    ///
+    /// The pipeline is wrapped in `Checked`, so debug builds panic as soon as
+    /// it produces NaN or a runaway value.
+    pub fn build(self) -> Box<dyn NoiseGen> {
+        Box::new(Checked::new(self.gen))
+    }
+}
+
+/// The boxed stages are opaque, so only the bounds of the pipeline are
+/// printed.
+impl fmt::Debug for NoiseBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NoiseBuilder")
+            .field("bounds", &self.gen.bounds())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,90 @@
+//! Memoizes the most recent sample of a generator.
+
+use core::cell::Cell;
+
+use crate::gen::NoiseGen;
+
+/// A generator adapter that remembers the last coordinate it was sampled at
+/// and the value it returned.
+///
+/// Sampling the same coordinate again, with the same number of dimensions,
+/// returns the remembered value without touching the source. This pays off
+/// when a pipeline branches and samples an expensive generator at the same
+/// point several times, e.g. a control generator feeding both a `Select` and
+/// a modifier: wrap it once in a `Cache` and share it through an `Rc`.
+///
+/// Only a single entry is kept, so any other coordinate evicts it; sampling a
+/// grid point by point gets no benefit. The entry is stored in a `Cell`, so a
+/// cache is meant to be used from a single thread.
+#[derive(Debug)]
+pub struct Cache<G> {
+    source: G,
+    last: Cell<Option<(u8, [f64; 4], f64)>>
+}
+
+impl<G: NoiseGen> Cache<G> {
+    /// Wraps `source` in an empty cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    /// use noisy::gen::{NoiseGen, Cache, Perlin, Select, Simplex};
+    /// use noisy::gen::modifier::Abs;
+    ///
+    /// let control = Rc::new(Cache::new(Simplex::new()));
+    /// let select = Select::new(Perlin::new(), Abs::new(control.clone()), control, 0.0, 1.0);
+    /// let val = select.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G) -> Cache<G> {
+        Cache { source: source, last: Cell::new(None) }
+    }
+
+    /// Forgets the remembered sample.
+    pub fn clear(&self) {
+        self.last.set(None);
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Returns the remembered value for `coord` in `dims` dimensions, or
+    /// samples it and remembers it.
+    #[inline]
+    fn lookup<F: Fn() -> f64>(&self, dims: u8, coord: [f64; 4], sample: F) -> f64 {
+        if let Some((last_dims, last_coord, val)) = self.last.get() {
+            if last_dims == dims && last_coord == coord {
+                return val;
+            }
+        }
+
+        let val: f64 = sample();
+        self.last.set(Some((dims, coord, val)));
+
+        val
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Cache<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.lookup(1, [xin, 0.0, 0.0, 0.0], || self.source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.lookup(2, [xin, yin, 0.0, 0.0], || self.source.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.lookup(3, [xin, yin, zin, 0.0], || self.source.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.lookup(4, [xin, yin, zin, win], || self.source.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
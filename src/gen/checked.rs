@@ -0,0 +1,74 @@
+//! Catches NaN and runaway values coming out of a generator in debug builds.
+
+use crate::gen::NoiseGen;
+
+/// The largest magnitude `Checked` lets through. Far outside the range of
+/// any sane pipeline, so only genuinely broken values trip it.
+const SANITY_LIMIT: f64 = 1e6;
+
+/// A generator adapter asserting, in debug builds, that every value its
+/// source returns is finite and no larger than 1e6 in magnitude.
+///
+/// A bug deep in a pipeline, such as a division by a zero amplitude, would
+/// otherwise let NaN spread silently through a whole heightmap. The
+/// assertion panics with the method and coordinate that produced the value
+/// instead. The check is a `debug_assert!`, so release builds pass the
+/// value straight through at no cost.
+///
+/// `NoiseBuilder::build` wraps every pipeline in a `Checked`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Checked<G> {
+    source: G
+}
+
+impl<G: NoiseGen> Checked<G> {
+    /// Wraps `source` in the debug assertion.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Checked, Fbm, Simplex};
+    ///
+    /// let terrain = Checked::new(Fbm::new(Simplex::new(), 6));
+    /// let val = terrain.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G) -> Checked<G> {
+        Checked { source: source }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+}
+
+/// Asserts in debug builds that `val` is sane, naming the method and the
+/// coordinate that produced it.
+#[inline]
+fn check(val: f64, method: &str, coord: &[f64]) -> f64 {
+    debug_assert!(val.is_finite() && val.abs() <= SANITY_LIMIT,
+                  "{} at {:?} returned {}", method, coord, val);
+    val
+}
+
+impl<G: NoiseGen> NoiseGen for Checked<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        check(self.source.noise1d(xin), "noise1d", &[xin])
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        check(self.source.noise2d(xin, yin), "noise2d", &[xin, yin])
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        check(self.source.noise3d(xin, yin, zin), "noise3d", &[xin, yin, zin])
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        check(self.source.noise4d(xin, yin, zin, win), "noise4d", &[xin, yin, zin, win])
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
@@ -1,14 +1,24 @@
 //! Outputs a [check pattern](http://en.wikipedia.org/wiki/Check_(pattern))
 
-use utils::if_else;
-use gen::NoiseGen;
+use crate::utils::{ if_else, fade, FadeKind };
+use crate::gen::{ NoiseGen, Seedable };
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
 
-
-use std::num::Float;
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
 
 /// A check pattern generator.
-#[derive(Copy)]
-pub struct Checkerboard;
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Checkerboard {
+    cos: f64,
+    sin: f64,
+    size: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    soften: f64
+}
 
 impl Checkerboard {
     /// Initializes a new simplex instance with a random seed using XorShiftRng.
@@ -21,7 +31,118 @@ impl Checkerboard {
     /// let checkerboard = Checkerboard::new();
     /// ```
     pub fn new() -> Checkerboard {
-        Checkerboard
+        Checkerboard { cos: 1.0, sin: 0.0, size: 1.0, soften: 0.0 }
+    }
+
+    /// Rotates the pattern counterclockwise by `theta` radians around the
+    /// origin of the (x, y) plane.
+    ///
+    /// The rotation is applied to the input coordinate before the cell parity
+    /// is computed, so a rotation of 45 degrees produces a diagonal pattern.
+    /// It affects `noise2d` and `noise3d`; `noise1d` has no plane to rotate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::f64::consts::FRAC_PI_4;
+    /// use noisy::gen::Checkerboard;
+    ///
+    /// let checkerboard = Checkerboard::new().with_rotation(FRAC_PI_4);
+    /// ```
+    pub fn with_rotation(self, theta: f64) -> Checkerboard {
+        Checkerboard { cos: theta.cos(), sin: theta.sin(), ..self }
+    }
+
+    /// Sets the edge length of a cell, which defaults to 1.
+    ///
+    /// Input coordinates are divided by `size` before the cell parity is
+    /// computed, so a size of 2 doubles the period of the pattern on every
+    /// axis.
+    ///
+    /// Panics if `size` is not positive.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Checkerboard;
+    ///
+    /// let checkerboard = Checkerboard::new().with_size(2.0);
+    /// ```
+    pub fn with_size(self, size: f64) -> Checkerboard {
+        assert!(size > 0.0, "checkerboard size must be positive, got {}", size);
+
+        Checkerboard { size: size, ..self }
+    }
+
+    /// Softens the cell edges over a band `soften` cells wide, which
+    /// defaults to 0.
+    ///
+    /// Within half the band of a cell border, the output eases from one
+    /// cell's value to the other's along the quintic fade curve, passing
+    /// through 0 on the border itself. This anti-aliases the pattern when
+    /// it is sampled coarsely. A width of 0 gives the hard pattern and a
+    /// width of 1 leaves no flat area at all.
+    ///
+    /// Panics if `soften` is not within [0, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Checkerboard;
+    ///
+    /// let checkerboard = Checkerboard::new().with_soften(0.2);
+    /// ```
+    pub fn with_soften(self, soften: f64) -> Checkerboard {
+        assert!((0.0..=1.0).contains(&soften), "checkerboard soften must be within [0, 1]");
+
+        Checkerboard { soften: soften, ..self }
+    }
+
+    /// Returns 1 in even cells and -1 in odd cells along one axis, eased
+    /// across the softened band around each border.
+    ///
+    /// The checker value is the product of these waves over every axis,
+    /// which is the XOR of the cell parities for the hard pattern.
+    #[inline]
+    fn wave(&self, t: f64) -> f64 {
+        let cell: f64 = t.floor();
+        let parity: f64 = if_else(cell as i64 & 1 == 1, -1.0, 1.0);
+        let half: f64 = self.soften * 0.5;
+        let frac: f64 = t - cell;
+
+        // The fade can round a hair outside [0, 1] at the edges of the band,
+        // which would push the output out of [-1, 1].
+        let ease = |t: f64| fade(FadeKind::Quintic, t).clamp(0.0, 1.0);
+
+        if frac > 1.0 - half {
+            // Leaving this cell for the next one.
+            parity * (1.0 - 2.0 * ease((frac - 1.0 + half) / self.soften))
+        } else if frac < half {
+            // Arriving from the previous cell.
+            -parity * (1.0 - 2.0 * ease((frac + half) / self.soften))
+        } else {
+            parity
+        }
+    }
+
+    /// Rotates an (x, y) coordinate into the pattern's frame and scales it
+    /// to cell units.
+    #[inline]
+    fn rotate(&self, xin: f64, yin: f64) -> (f64, f64) {
+        ((xin * self.cos - yin * self.sin) / self.size,
+         (xin * self.sin + yin * self.cos) / self.size)
+    }
+}
+
+impl Seedable for Checkerboard {
+    /// The pattern is not random, so the seed is ignored.
+    fn set_seed(&mut self, seed: u64) {
+        let _ = seed;
+    }
+
+    /// Always 0.
+    fn seed(&self) -> u64 {
+        0
     }
 }
 
@@ -37,9 +158,7 @@ impl NoiseGen for Checkerboard {
     /// let val = checkerboard.noise1d(1.0);
     /// ```
     fn noise1d(&self, xin: f64) -> f64 {
-        let ix: i64 = xin.floor() as i64;
-
-        if_else(ix & 1 == 1, -1.0, 1.0)
+        self.wave(xin / self.size)
     }
 
     /// Given a (x, y) coordinate, return a value in the interval [-1, 1].
@@ -53,10 +172,9 @@ impl NoiseGen for Checkerboard {
     /// let val = checkerboard.noise2d(1.0, 2.0);
     /// ```
     fn noise2d(&self, xin: f64, yin: f64) -> f64 {
-        let ix: i64 = xin.floor() as i64;
-        let iy: i64 = yin.floor() as i64;
+        let (xin, yin) = self.rotate(xin, yin);
 
-        if_else(ix & 1 ^ iy & 1 == 1, -1.0, 1.0)
+        self.wave(xin) * self.wave(yin)
     }
 
     /// Given a (x, y, z) coordinate, return a value in the interval [-1, 1].
@@ -70,10 +188,8 @@ impl NoiseGen for Checkerboard {
     /// let val = checkerboard.noise3d(1.0, 2.0, 3.0);
     /// ```
     fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
-        let ix: i64 = xin.floor() as i64;
-        let iy: i64 = yin.floor() as i64;
-        let iz: i64 = zin.floor() as i64;
+        let (xin, yin) = self.rotate(xin, yin);
 
-        if_else(ix & 1 ^ iy & 1 ^ iz & 1 == 1, -1.0, 1.0)
+        self.wave(xin) * self.wave(yin) * self.wave(zin / self.size)
     }
 }
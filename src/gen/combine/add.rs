@@ -0,0 +1,62 @@
+//! Adds the outputs of two generators.
+
+use crate::gen::NoiseGen;
+
+/// A generator returning the average of two generators.
+///
+/// Summing two fields in [-1, 1] gives a value in [-2, 2], so the sum is halved to keep the output within [-1, 1]. Wrap the result in a `ScaleBias` to restore the full sum.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Add<A, B> {
+    first: A,
+    second: B
+}
+
+impl<A: NoiseGen, B: NoiseGen> Add<A, B> {
+    /// Wraps `first` and `second`, averaging their outputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Perlin, Simplex};
+    /// use noisy::gen::combine::Add;
+    ///
+    /// let combined = Add::new(Simplex::new(), Perlin::new());
+    /// ```
+    pub fn new(first: A, second: B) -> Add<A, B> {
+        Add { first: first, second: second }
+    }
+
+    /// Returns the first wrapped generator.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the second wrapped generator.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: NoiseGen, B: NoiseGen> NoiseGen for Add<A, B> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        0.5 * (self.first.noise1d(xin) + self.second.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        0.5 * (self.first.noise2d(xin, yin) + self.second.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        0.5 * (self.first.noise3d(xin, yin, zin) + self.second.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        0.5 * (self.first.noise4d(xin, yin, zin, win) + self.second.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        let (first, second) = (self.first.bounds(), self.second.bounds());
+
+        (0.5 * (first.0 + second.0), 0.5 * (first.1 + second.1))
+    }
+}
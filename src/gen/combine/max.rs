@@ -0,0 +1,62 @@
+//! Takes the larger output of two generators.
+
+use crate::gen::NoiseGen;
+
+/// A generator returning the larger output of two generators.
+///
+/// Useful for raising one field over another, e.g. plateaus above terrain.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Max<A, B> {
+    first: A,
+    second: B
+}
+
+impl<A: NoiseGen, B: NoiseGen> Max<A, B> {
+    /// Wraps `first` and `second`, keeping the larger of their outputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Perlin, Simplex};
+    /// use noisy::gen::combine::Max;
+    ///
+    /// let combined = Max::new(Simplex::new(), Perlin::new());
+    /// ```
+    pub fn new(first: A, second: B) -> Max<A, B> {
+        Max { first: first, second: second }
+    }
+
+    /// Returns the first wrapped generator.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the second wrapped generator.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: NoiseGen, B: NoiseGen> NoiseGen for Max<A, B> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.first.noise1d(xin).max(self.second.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.first.noise2d(xin, yin).max(self.second.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.first.noise3d(xin, yin, zin).max(self.second.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.first.noise4d(xin, yin, zin, win).max(self.second.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        let (first, second) = (self.first.bounds(), self.second.bounds());
+
+        (first.0.max(second.0), first.1.max(second.1))
+    }
+}
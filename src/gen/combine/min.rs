@@ -0,0 +1,62 @@
+//! Takes the smaller output of two generators.
+
+use crate::gen::NoiseGen;
+
+/// A generator returning the smaller output of two generators.
+///
+/// Useful for carving one field out of another, e.g. valleys into terrain.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Min<A, B> {
+    first: A,
+    second: B
+}
+
+impl<A: NoiseGen, B: NoiseGen> Min<A, B> {
+    /// Wraps `first` and `second`, keeping the smaller of their outputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Perlin, Simplex};
+    /// use noisy::gen::combine::Min;
+    ///
+    /// let combined = Min::new(Simplex::new(), Perlin::new());
+    /// ```
+    pub fn new(first: A, second: B) -> Min<A, B> {
+        Min { first: first, second: second }
+    }
+
+    /// Returns the first wrapped generator.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the second wrapped generator.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: NoiseGen, B: NoiseGen> NoiseGen for Min<A, B> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.first.noise1d(xin).min(self.second.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.first.noise2d(xin, yin).min(self.second.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.first.noise3d(xin, yin, zin).min(self.second.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.first.noise4d(xin, yin, zin, win).min(self.second.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        let (first, second) = (self.first.bounds(), self.second.bounds());
+
+        (first.0.min(second.0), first.1.min(second.1))
+    }
+}
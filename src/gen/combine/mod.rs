@@ -0,0 +1,13 @@
+//! Generators that combine the output of two generators.
+
+pub use crate::gen::combine::add::Add;
+pub use crate::gen::combine::multiply::Multiply;
+pub use crate::gen::combine::min::Min;
+pub use crate::gen::combine::max::Max;
+pub use crate::gen::combine::power::Power;
+
+mod add;
+mod multiply;
+mod min;
+mod max;
+mod power;
@@ -0,0 +1,65 @@
+//! Multiplies the outputs of two generators.
+
+use crate::gen::NoiseGen;
+
+/// A generator returning the product of two generators.
+///
+/// The product of two values in [-1, 1] stays within [-1, 1], which makes `Multiply` a cheap way to mask one field with another.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Multiply<A, B> {
+    first: A,
+    second: B
+}
+
+impl<A: NoiseGen, B: NoiseGen> Multiply<A, B> {
+    /// Wraps `first` and `second`, multiplying their outputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Perlin, Simplex};
+    /// use noisy::gen::combine::Multiply;
+    ///
+    /// let combined = Multiply::new(Simplex::new(), Perlin::new());
+    /// ```
+    pub fn new(first: A, second: B) -> Multiply<A, B> {
+        Multiply { first: first, second: second }
+    }
+
+    /// Returns the first wrapped generator.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the second wrapped generator.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: NoiseGen, B: NoiseGen> NoiseGen for Multiply<A, B> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.first.noise1d(xin) * self.second.noise1d(xin)
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.first.noise2d(xin, yin) * self.second.noise2d(xin, yin)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.first.noise3d(xin, yin, zin) * self.second.noise3d(xin, yin, zin)
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.first.noise4d(xin, yin, zin, win) * self.second.noise4d(xin, yin, zin, win)
+    }
+
+    /// The extremes of a product lie at products of the factors' extremes.
+    fn bounds(&self) -> (f64, f64) {
+        let (first, second) = (self.first.bounds(), self.second.bounds());
+        let corners: [f64; 4] = [first.0 * second.0, first.0 * second.1, first.1 * second.0, first.1 * second.1];
+
+        (corners.iter().fold(f64::INFINITY, |acc: f64, v| acc.min(*v)),
+         corners.iter().fold(f64::NEG_INFINITY, |acc: f64, v| acc.max(*v)))
+    }
+}
@@ -0,0 +1,72 @@
+//! Raises the output of one generator to the power of another.
+
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// A generator raising the magnitude of `base` to the magnitude of
+/// `exponent`, keeping the sign of `base`.
+///
+/// Exponents above 1 push the output towards 0 and sharpen its peaks,
+/// exponents below 1 push it towards -1 and 1. The result is clamped to
+/// [-1, 1].
+///
+/// Where `base` is 0, the output is 0 for any non-zero exponent. An exponent
+/// of 0 maps every base to 1 with the sign of the base, so a base of 0 gives
+/// 1 and a base of -0.0 gives -1.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Power<A, B> {
+    base: A,
+    exponent: B
+}
+
+impl<A: NoiseGen, B: NoiseGen> Power<A, B> {
+    /// Wraps `base`, raising it to the power given by `exponent`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Constant, Simplex};
+    /// use noisy::gen::combine::Power;
+    ///
+    /// let sharpened = Power::new(Simplex::new(), Constant::new(2.0));
+    /// let val = sharpened.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(base: A, exponent: B) -> Power<A, B> {
+        Power { base: base, exponent: exponent }
+    }
+
+    /// Returns the base generator.
+    pub fn base(&self) -> &A {
+        &self.base
+    }
+
+    /// Returns the exponent generator.
+    pub fn exponent(&self) -> &B {
+        &self.exponent
+    }
+}
+
+#[inline]
+fn power(base: f64, exponent: f64) -> f64 {
+    (base.signum() * base.abs().powf(exponent.abs())).clamp(-1.0, 1.0)
+}
+
+impl<A: NoiseGen, B: NoiseGen> NoiseGen for Power<A, B> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        power(self.base.noise1d(xin), self.exponent.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        power(self.base.noise2d(xin, yin), self.exponent.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        power(self.base.noise3d(xin, yin, zin), self.exponent.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        power(self.base.noise4d(xin, yin, zin, win), self.exponent.noise4d(xin, yin, zin, win))
+    }
+}
@@ -0,0 +1,51 @@
+//! A generator that ignores its input.
+
+use crate::gen::NoiseGen;
+
+/// A generator returning the same value at every coordinate.
+///
+/// Mostly useful as an input to combinators, e.g. to offset or mask another
+/// generator, and for tests that need a predictable source.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Constant {
+    /// The value returned for every coordinate.
+    pub value: f64
+}
+
+impl Constant {
+    /// Initializes a new Constant instance returning `value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Constant};
+    ///
+    /// let half = Constant::new(0.5);
+    /// assert_eq!(half.noise2d(1.0, 2.0), 0.5);
+    /// ```
+    pub fn new(value: f64) -> Constant {
+        Constant { value: value }
+    }
+}
+
+impl NoiseGen for Constant {
+    fn noise1d(&self, _xin: f64) -> f64 {
+        self.value
+    }
+
+    fn noise2d(&self, _xin: f64, _yin: f64) -> f64 {
+        self.value
+    }
+
+    fn noise3d(&self, _xin: f64, _yin: f64, _zin: f64) -> f64 {
+        self.value
+    }
+
+    fn noise4d(&self, _xin: f64, _yin: f64, _zin: f64, _win: f64) -> f64 {
+        self.value
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        (self.value, self.value)
+    }
+}
@@ -0,0 +1,105 @@
+//! Displaces each input axis of a generator by its own generator.
+
+use crate::gen::NoiseGen;
+
+/// A generator that moves each axis of the input coordinate by the output of
+/// a separate displacement generator before sampling its source.
+///
+/// Sampling `(x, y, z)` samples the source at
+/// `(x + x_source(x, y, z), y + y_source(x, y, z), z + z_source(x, y, z))`.
+/// Lower dimensions only use the displacement generators of their axes, so
+/// `noise2d` never samples the z source. There is no w source: `noise4d`
+/// displaces x, y and z by the 4D output of their generators and passes w
+/// through unchanged. Wrap the displacement generators in
+/// a `ScaleBias` to control how far the coordinate moves.
+///
+/// This is the general form of domain distortion; `Turbulence` is the
+/// special case of a single, offset-sampled warp generator.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Displace<G, X, Y, Z> {
+    source: G,
+    x_source: X,
+    y_source: Y,
+    z_source: Z
+}
+
+impl<G: NoiseGen, X: NoiseGen, Y: NoiseGen, Z: NoiseGen> Displace<G, X, Y, Z> {
+    /// Wraps `source`, displacing each axis by the matching generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Displace, Perlin, Simplex};
+    /// use noisy::gen::modifier::ScaleBias;
+    ///
+    /// let displace = Displace::new(
+    ///     Perlin::new(),
+    ///     ScaleBias::new(Simplex::new(), 0.5, 0.0),
+    ///     ScaleBias::new(Simplex::new(), 0.5, 0.0),
+    ///     ScaleBias::new(Simplex::new(), 0.5, 0.0)
+    /// );
+    /// let val = displace.noise3d(1.0, 2.0, 3.0);
+    /// ```
+    pub fn new(source: G, x_source: X, y_source: Y, z_source: Z) -> Displace<G, X, Y, Z> {
+        Displace {
+            source: source,
+            x_source: x_source,
+            y_source: y_source,
+            z_source: z_source
+        }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Returns the generator displacing the x axis.
+    pub fn x_source(&self) -> &X {
+        &self.x_source
+    }
+
+    /// Returns the generator displacing the y axis.
+    pub fn y_source(&self) -> &Y {
+        &self.y_source
+    }
+
+    /// Returns the generator displacing the z axis.
+    pub fn z_source(&self) -> &Z {
+        &self.z_source
+    }
+}
+
+impl<G: NoiseGen, X: NoiseGen, Y: NoiseGen, Z: NoiseGen> NoiseGen for Displace<G, X, Y, Z> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.source.noise1d(xin + self.x_source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.source.noise2d(
+            xin + self.x_source.noise2d(xin, yin),
+            yin + self.y_source.noise2d(xin, yin)
+        )
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.source.noise3d(
+            xin + self.x_source.noise3d(xin, yin, zin),
+            yin + self.y_source.noise3d(xin, yin, zin),
+            zin + self.z_source.noise3d(xin, yin, zin)
+        )
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.source.noise4d(
+            xin + self.x_source.noise4d(xin, yin, zin, win),
+            yin + self.y_source.noise4d(xin, yin, zin, win),
+            zin + self.z_source.noise4d(xin, yin, zin, win),
+            win
+        )
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
@@ -0,0 +1,120 @@
+//! Two-stage domain warping, as popularized by Inigo Quilez.
+
+use crate::gen::{ NoiseGen, Simplex };
+
+/// A generator that warps the input coordinate twice before sampling its
+/// source.
+///
+/// For a point `p`, the first stage samples its own simplex generator to get
+/// an offset `q`, the second stage samples another one at `p + s1 * q` to get
+/// an offset `r`, and the source is finally sampled at `p + s2 * r`. Each
+/// stage samples its axes at fixed, distinct offsets so the displacement
+/// components are decorrelated.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DomainWarp2<N> {
+    source: N,
+    first: Simplex,
+    first_strength: f64,
+    second: Simplex,
+    second_strength: f64
+}
+
+impl<N: NoiseGen> DomainWarp2<N> {
+    /// Wraps `source` in a two-stage warp whose displacement generators are
+    /// seeded from `first_seed` and `second_seed`.
+    ///
+    /// The stages are built with `Simplex::from_seed`, so a given pair of
+    /// seeds warps identically on every platform.
+    ///
+    /// A strength of 0.0 disables the corresponding stage.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, DomainWarp2, Simplex};
+    ///
+    /// let warped = DomainWarp2::new(Simplex::new(), 1, 4.0, 2, 4.0);
+    /// let val = warped.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: N, first_seed: u64, first_strength: f64, second_seed: u64, second_strength: f64) -> DomainWarp2<N> {
+        DomainWarp2 {
+            source: source,
+            first: Simplex::from_seed(first_seed),
+            first_strength: first_strength,
+            second: Simplex::from_seed(second_seed),
+            second_strength: second_strength
+        }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &N {
+        &self.source
+    }
+}
+
+impl<N: NoiseGen> NoiseGen for DomainWarp2<N> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        let q: f64 = self.first.noise1d(xin);
+        let r: f64 = self.second.noise1d(xin + self.first_strength * q + 1.7);
+
+        self.source.noise1d(xin + self.second_strength * r)
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let qx: f64 = self.first.noise2d(xin, yin);
+        let qy: f64 = self.first.noise2d(xin + 5.2, yin + 1.3);
+
+        let px: f64 = xin + self.first_strength * qx;
+        let py: f64 = yin + self.first_strength * qy;
+        let rx: f64 = self.second.noise2d(px + 1.7, py + 9.2);
+        let ry: f64 = self.second.noise2d(px + 8.3, py + 2.8);
+
+        self.source.noise2d(xin + self.second_strength * rx, yin + self.second_strength * ry)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let qx: f64 = self.first.noise3d(xin, yin, zin);
+        let qy: f64 = self.first.noise3d(xin + 5.2, yin + 1.3, zin + 3.7);
+        let qz: f64 = self.first.noise3d(xin + 2.9, yin + 7.1, zin + 4.6);
+
+        let px: f64 = xin + self.first_strength * qx;
+        let py: f64 = yin + self.first_strength * qy;
+        let pz: f64 = zin + self.first_strength * qz;
+        let rx: f64 = self.second.noise3d(px + 1.7, py + 9.2, pz + 6.1);
+        let ry: f64 = self.second.noise3d(px + 8.3, py + 2.8, pz + 0.4);
+        let rz: f64 = self.second.noise3d(px + 4.4, py + 5.9, pz + 3.3);
+
+        self.source.noise3d(
+            xin + self.second_strength * rx,
+            yin + self.second_strength * ry,
+            zin + self.second_strength * rz
+        )
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        let qx: f64 = self.first.noise4d(xin, yin, zin, win);
+        let qy: f64 = self.first.noise4d(xin + 5.2, yin + 1.3, zin + 3.7, win + 8.8);
+        let qz: f64 = self.first.noise4d(xin + 2.9, yin + 7.1, zin + 4.6, win + 0.9);
+        let qw: f64 = self.first.noise4d(xin + 6.4, yin + 3.5, zin + 9.7, win + 2.2);
+
+        let px: f64 = xin + self.first_strength * qx;
+        let py: f64 = yin + self.first_strength * qy;
+        let pz: f64 = zin + self.first_strength * qz;
+        let pw: f64 = win + self.first_strength * qw;
+        let rx: f64 = self.second.noise4d(px + 1.7, py + 9.2, pz + 6.1, pw + 3.9);
+        let ry: f64 = self.second.noise4d(px + 8.3, py + 2.8, pz + 0.4, pw + 7.6);
+        let rz: f64 = self.second.noise4d(px + 4.4, py + 5.9, pz + 3.3, pw + 1.5);
+        let rw: f64 = self.second.noise4d(px + 0.6, py + 4.7, pz + 8.2, pw + 5.1);
+
+        self.source.noise4d(
+            xin + self.second_strength * rx,
+            yin + self.second_strength * ry,
+            zin + self.second_strength * rz,
+            win + self.second_strength * rw
+        )
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
@@ -0,0 +1,181 @@
+//! Fractional Brownian motion: several octaves of a generator stacked at
+//! increasing frequencies and decreasing amplitudes.
+
+use alloc::vec::Vec;
+
+use crate::gen::NoiseGen;
+
+/// A generator summing octaves of its source.
+///
+/// Octave `i` samples the source at `lacunarity^i` times the input coordinate
+/// and weighs it by `persistence^i`. The sum is divided by the total weight,
+/// so the output stays within [-1, 1]. The lacunarity can differ per axis.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Fbm<G> {
+    source: G,
+    octaves: u32,
+    lacunarity: (f64, f64, f64),
+    persistence: f64,
+    layers: Option<Vec<(f64, f64)>>
+}
+
+impl<G: NoiseGen> Fbm<G> {
+    /// Stacks `octaves` octaves of `source`, with a lacunarity of 2.0 and a
+    /// persistence of 0.5.
+    ///
+    /// Panics if `octaves` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Fbm, Simplex};
+    ///
+    /// let fbm = Fbm::new(Simplex::new(), 4);
+    /// let val = fbm.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, octaves: u32) -> Fbm<G> {
+        assert!(octaves > 0, "fbm needs at least one octave");
+
+        Fbm {
+            source: source,
+            octaves: octaves,
+            lacunarity: (2.0, 2.0, 2.0),
+            persistence: 0.5,
+            layers: None
+        }
+    }
+
+    /// Stacks one layer of `source` per `(frequency, amplitude)` pair, instead
+    /// of octaves following a geometric progression.
+    ///
+    /// Each layer samples the source at `frequency` times the input
+    /// coordinate and weighs it by `amplitude`. The sum is divided by the total
+    /// amplitude, so the output stays within [-1, 1]. Lacunarity and
+    /// persistence have no effect on a layered generator.
+    ///
+    /// Panics if `layers` is empty, an amplitude is negative or all of them
+    /// are 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Fbm, Simplex};
+    ///
+    /// // Broad hills with a little detail and a faint high-frequency grain.
+    /// let fbm = Fbm::with_layers(Simplex::new(), vec![(1.0, 1.0), (3.0, 0.2), (17.0, 0.05)]);
+    /// let val = fbm.noise2d(1.0, 2.0);
+    /// ```
+    pub fn with_layers(source: G, layers: Vec<(f64, f64)>) -> Fbm<G> {
+        assert!(!layers.is_empty(), "fbm needs at least one layer");
+        assert!(layers.iter().all(|&(_, amplitude)| amplitude >= 0.0), "fbm layer amplitudes must not be negative");
+        assert!(layers.iter().any(|&(_, amplitude)| amplitude > 0.0), "fbm needs a layer with a positive amplitude");
+
+        Fbm { octaves: layers.len() as u32, layers: Some(layers), ..Fbm::new(source, 1) }
+    }
+
+    /// Sets the frequency multiplier between successive octaves.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Fbm, Simplex};
+    ///
+    /// let fbm = Fbm::new(Simplex::new(), 4).with_lacunarity(2.5);
+    /// ```
+    pub fn with_lacunarity(self, lacunarity: f64) -> Fbm<G> {
+        Fbm { lacunarity: (lacunarity, lacunarity, lacunarity), ..self }
+    }
+
+    /// Sets a separate frequency multiplier between successive octaves for
+    /// the x, y and z axes, so the octaves scale anisotropically. The w axis
+    /// of `noise4d` follows z.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Fbm, Simplex};
+    ///
+    /// // Detail builds up quickly along y, giving thin strata.
+    /// let fbm = Fbm::new(Simplex::new(), 4).with_axis_lacunarity((2.0, 3.0, 2.0));
+    /// ```
+    pub fn with_axis_lacunarity(self, lacunarity: (f64, f64, f64)) -> Fbm<G> {
+        Fbm { lacunarity: lacunarity, ..self }
+    }
+
+    /// Sets the amplitude multiplier between successive octaves.
+    ///
+    /// Panics if `persistence` is not finite and positive, as the total
+    /// amplitude the octaves are normalized by could otherwise be 0 or
+    /// the output leave the bounds of the source.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Fbm, Simplex};
+    ///
+    /// let fbm = Fbm::new(Simplex::new(), 4).with_persistence(0.6);
+    /// ```
+    pub fn with_persistence(self, persistence: f64) -> Fbm<G> {
+        assert!(persistence.is_finite() && persistence > 0.0,
+                "fbm persistence must be finite and positive, got {}", persistence);
+
+        Fbm { persistence: persistence, ..self }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Sums the octaves or layers of `sample(frequency)` and normalizes by
+    /// the total amplitude. The frequency holds one factor per axis.
+    #[inline]
+    fn accumulate<F: Fn((f64, f64, f64)) -> f64>(&self, sample: F) -> f64 {
+        if let Some(ref layers) = self.layers {
+            let (sum, total) = layers.iter().fold((0.0, 0.0), |(sum, total), &(frequency, amplitude)| {
+                (sum + amplitude * sample((frequency, frequency, frequency)), total + amplitude)
+            });
+
+            return sum / total;
+        }
+
+        let mut sum: f64 = 0.0;
+        let mut total: f64 = 0.0;
+        let mut frequency: (f64, f64, f64) = (1.0, 1.0, 1.0);
+        let mut amplitude: f64 = 1.0;
+
+        for _ in 0..self.octaves {
+            sum += amplitude * sample(frequency);
+            total += amplitude;
+            frequency.0 *= self.lacunarity.0;
+            frequency.1 *= self.lacunarity.1;
+            frequency.2 *= self.lacunarity.2;
+            amplitude *= self.persistence;
+        }
+
+        sum / total
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Fbm<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise1d(xin * f.0))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise2d(xin * f.0, yin * f.1))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise3d(xin * f.0, yin * f.1, zin * f.2))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.accumulate(|f| self.source.noise4d(xin * f.0, yin * f.1, zin * f.2, win * f.2))
+    }
+
+    /// The octaves are averaged, so the source bounds carry over.
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
@@ -0,0 +1,87 @@
+//! A linear ramp along a direction.
+
+use crate::gen::NoiseGen;
+
+/// A generator ramping linearly from -1 at one point to 1 at another.
+///
+/// A coordinate is projected onto the line through the two endpoints; the
+/// output is -1 at or before `start`, 1 at or past `end` and interpolated in
+/// between. Every plane perpendicular to the line has the same value, which
+/// makes it a handy control source for `Blend` or `Select`, e.g. for sky
+/// gradients or latitude bands.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Gradient {
+    start: [f64; 3],
+    end: [f64; 3],
+    // The axis from start to end, divided by its squared length.
+    axis: [f64; 3]
+}
+
+impl Gradient {
+    /// Initializes a new Gradient from `start` (-1) to `end` (1).
+    ///
+    /// Panics if both endpoints are the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Gradient};
+    ///
+    /// // Latitude bands: -1 at the south pole, 1 at the north pole.
+    /// let latitude = Gradient::new([0.0, -1.0, 0.0], [0.0, 1.0, 0.0]);
+    /// assert_eq!(latitude.noise3d(0.3, 0.0, 0.7), 0.0);
+    /// ```
+    pub fn new(start: [f64; 3], end: [f64; 3]) -> Gradient {
+        let axis: [f64; 3] = [end[0] - start[0], end[1] - start[1], end[2] - start[2]];
+        let length: f64 = axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2];
+        assert!(length > 0.0, "gradient endpoints must differ");
+
+        Gradient {
+            start: start,
+            end: end,
+            axis: [axis[0] / length, axis[1] / length, axis[2] / length]
+        }
+    }
+
+    /// Returns the point where the output is -1.
+    pub fn start(&self) -> [f64; 3] {
+        self.start
+    }
+
+    /// Returns the point where the output is 1.
+    pub fn end(&self) -> [f64; 3] {
+        self.end
+    }
+
+    /// Projects a coordinate onto the axis and maps it to [-1, 1].
+    #[inline]
+    fn ramp(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let t: f64 = (xin - self.start[0]) * self.axis[0]
+                   + (yin - self.start[1]) * self.axis[1]
+                   + (zin - self.start[2]) * self.axis[2];
+
+        (2.0 * t - 1.0).clamp(-1.0, 1.0)
+    }
+}
+
+impl NoiseGen for Gradient {
+    /// Samples the ramp at (x, 0, 0).
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.ramp(xin, 0.0, 0.0)
+    }
+
+    /// Samples the ramp at (x, y, 0).
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.ramp(xin, yin, 0.0)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.ramp(xin, yin, zin)
+    }
+
+    /// The w coordinate is ignored.
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        let _ = win;
+        self.ramp(xin, yin, zin)
+    }
+}
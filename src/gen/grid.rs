@@ -0,0 +1,74 @@
+//! A row-major grid of sampled noise values.
+
+use core::ops::Index;
+use core::slice::{ Iter, Chunks };
+use alloc::vec::Vec;
+
+/// A `width` by `height` grid of noise values stored in row-major order.
+#[derive(Clone, PartialEq)]
+pub struct Grid2d {
+    /// The samples, row after row.
+    pub data: Vec<f64>,
+    /// The number of columns.
+    pub width: usize,
+    /// The number of rows.
+    pub height: usize
+}
+
+impl Grid2d {
+    /// Wraps row-major samples into a grid.
+    ///
+    /// Panics if `data` does not hold exactly `width * height` values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Grid2d;
+    ///
+    /// let grid = Grid2d::new(vec![0.0, 0.5, 1.0, -1.0], 2, 2);
+    /// assert_eq!(grid[(1, 1)], -1.0);
+    /// ```
+    pub fn new(data: Vec<f64>, width: usize, height: usize) -> Grid2d {
+        assert!(data.len() == width * height,
+                "grid data holds {} values, expected {}x{}", data.len(), width, height);
+
+        Grid2d { data: data, width: width, height: height }
+    }
+
+    /// Returns the value at column `x` and row `y`, or `None` if the
+    /// coordinate lies outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<f64> {
+        if x < self.width && y < self.height {
+            Some(self.data[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over every value in row-major order.
+    pub fn iter(&self) -> Iter<'_, f64> {
+        self.data.iter()
+    }
+
+    /// Iterates over the rows of the grid, each as a slice of `width` values.
+    ///
+    /// A grid with no columns holds no values, so it yields no rows.
+    pub fn rows(&self) -> Chunks<'_, f64> {
+        // `chunks` panics on a chunk size of 0, and the data is empty then
+        // anyway.
+        self.data.chunks(self.width.max(1))
+    }
+}
+
+impl Index<(usize, usize)> for Grid2d {
+    type Output = f64;
+
+    /// Returns the value at `(x, y)`, panicking if it lies outside the grid.
+    fn index(&self, index: (usize, usize)) -> &f64 {
+        let (x, y) = index;
+        assert!(x < self.width && y < self.height,
+                "grid index ({}, {}) out of bounds for {}x{}", x, y, self.width, self.height);
+
+        &self.data[y * self.width + x]
+    }
+}
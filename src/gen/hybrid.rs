@@ -0,0 +1,136 @@
+//! Hybrid multifractal noise, as described by F. Kenton Musgrave.
+
+use crate::gen::NoiseGen;
+
+/// A generator summing octaves of its source, each weighted by the running
+/// result of the octaves before it.
+///
+/// Every octave shifts the source by `offset`, so low values of the coarse
+/// octaves damp the finer ones: valleys stay smooth while detail builds up
+/// on the peaks, which gives the foothill-to-peak transitions fBm lacks.
+/// Octave `i` samples the source at `lacunarity^i` times the frequency and is
+/// weighted by `persistence^i`, and the weight carried between octaves is
+/// clamped to [0, 1]. The sum is normalized so the output stays within
+/// [-1, 1].
+#[derive(Clone, PartialEq, Debug)]
+pub struct HybridMulti<G> {
+    source: G,
+    octaves: u32,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+    offset: f64
+}
+
+impl<G: NoiseGen> HybridMulti<G> {
+    /// Stacks `octaves` octaves of `source`, with a frequency of 1.0, a
+    /// lacunarity of 2.0, a persistence of 0.84 and an offset of 0.7.
+    ///
+    /// These are Musgrave's defaults: the persistence is `2^-H` for his
+    /// fractal increment `H` of 0.25. Lower persistences shrink the weight
+    /// carried between octaves quickly enough to hide the finer ones.
+    ///
+    /// Panics if `octaves` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, HybridMulti, Simplex};
+    ///
+    /// let hills = HybridMulti::new(Simplex::new(), 6);
+    /// let val = hills.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, octaves: u32) -> HybridMulti<G> {
+        assert!(octaves > 0, "hybrid multifractal needs at least one octave");
+
+        HybridMulti {
+            source: source,
+            octaves: octaves,
+            frequency: 1.0,
+            lacunarity: 2.0,
+            persistence: 0.84,
+            offset: 0.7
+        }
+    }
+
+    /// Sets the frequency of the first octave.
+    pub fn with_frequency(self, frequency: f64) -> HybridMulti<G> {
+        HybridMulti { frequency: frequency, ..self }
+    }
+
+    /// Sets the frequency multiplier between successive octaves.
+    pub fn with_lacunarity(self, lacunarity: f64) -> HybridMulti<G> {
+        HybridMulti { lacunarity: lacunarity, ..self }
+    }
+
+    /// Sets the amplitude multiplier between successive octaves.
+    pub fn with_persistence(self, persistence: f64) -> HybridMulti<G> {
+        HybridMulti { persistence: persistence, ..self }
+    }
+
+    /// Sets the offset added to every octave of the source.
+    ///
+    /// Larger offsets let more detail through in the valleys.
+    ///
+    /// Panics if `offset` is not above -1.0, where every octave would be
+    /// negative or zero.
+    pub fn with_offset(self, offset: f64) -> HybridMulti<G> {
+        assert!(offset > -1.0, "hybrid multifractal offset must be above -1");
+
+        HybridMulti { offset: offset, ..self }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Sums the weighted octaves of `sample(frequency)` and maps the range
+    /// the sum can reach onto [-1, 1].
+    #[inline]
+    fn accumulate<F: Fn(f64) -> f64>(&self, sample: F) -> f64 {
+        let mut frequency: f64 = self.frequency;
+        let mut amplitude: f64 = 1.0;
+
+        // The first octave is added as is and seeds the weight.
+        let mut sum: f64 = (sample(frequency) + self.offset) * amplitude;
+        let mut weight: f64 = sum;
+        let mut rest: f64 = 0.0;
+
+        for _ in 1..self.octaves {
+            frequency *= self.lacunarity;
+            amplitude *= self.persistence;
+            weight = weight.clamp(0.0, 1.0);
+
+            let signal: f64 = (sample(frequency) + self.offset) * amplitude;
+            sum += weight * signal;
+            weight *= signal;
+            rest += amplitude;
+        }
+
+        // Every octave after the first is scaled by a weight in [0, 1], so
+        // it contributes nothing at worst when its signal cannot go negative.
+        let lower: f64 = self.offset - 1.0 + (self.offset - 1.0).min(0.0) * rest;
+        let upper: f64 = (self.offset + 1.0) * (1.0 + rest);
+
+        (2.0 * (sum - lower) / (upper - lower) - 1.0).clamp(-1.0, 1.0)
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for HybridMulti<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise1d(xin * f))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise2d(xin * f, yin * f))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise3d(xin * f, yin * f, zin * f))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.accumulate(|f| self.source.noise4d(xin * f, yin * f, zin * f, win * f))
+    }
+}
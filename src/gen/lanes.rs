@@ -0,0 +1,59 @@
+//! The row driver shared by the lane-parallel grid fills of `Simplex` and
+//! `Perlin`.
+
+/// The number of samples a lane kernel evaluates at once.
+pub const LANES: usize = 4;
+
+/// The 2D gradients of `utils::grad::grad2`, indexed by the low 3 bits of the
+/// hash. Looking them up instead of branching on the hash bits keeps lanes
+/// with different hashes from diverging.
+const GRAD2: [[f64; 2]; 8] = [
+    [1.0, 2.0], [-1.0, 2.0], [1.0, -2.0], [-1.0, -2.0],
+    [2.0, 1.0], [2.0, -1.0], [-2.0, 1.0], [-2.0, -1.0]
+];
+
+/// Same as `utils::grad::grad2`, without branches.
+#[inline(always)]
+pub fn grad2(hash: u8, x: f64, y: f64) -> f64 {
+    let g: [f64; 2] = GRAD2[(hash & 7) as usize];
+
+    g[0] * x + g[1] * y
+}
+
+/// Fills `buf` with a `width` by `height` grid of 2D noise in row-major
+/// order, narrowed to `f32`.
+///
+/// Each row is cut into groups of `LANES` samples, which `kernel` evaluates
+/// together from their x coordinates and the shared y coordinate of the row.
+/// The samples left over at the end of a row go through `scalar`, which must
+/// compute the same field one point at a time.
+pub fn fill_grid2d_f32<K, S>(buf: &mut [f32], width: usize, height: usize, origin: (f64, f64), step: (f64, f64),
+                             kernel: K, scalar: S)
+    where K: Fn([f64; LANES], f64) -> [f64; LANES], S: Fn(f64, f64) -> f64 {
+    assert!(buf.len() >= width * height, "buffer too small for a {}x{} grid", width, height);
+
+    for y in 0..height {
+        let yin: f64 = origin.1 + y as f64 * step.1;
+        let row = &mut buf[y * width..(y + 1) * width];
+
+        let mut groups = row.chunks_exact_mut(LANES);
+        for (group, out) in (&mut groups).enumerate() {
+            let x: usize = group * LANES;
+            let mut xs: [f64; LANES] = [0.0; LANES];
+            for (lane, xin) in xs.iter_mut().enumerate() {
+                *xin = origin.0 + (x + lane) as f64 * step.0;
+            }
+
+            let vals: [f64; LANES] = kernel(xs, yin);
+            for (dst, val) in out.iter_mut().zip(vals.iter()) {
+                *dst = *val as f32;
+            }
+        }
+
+        // Scalar tail for widths that are not a multiple of the lane count.
+        let tail: usize = width - width % LANES;
+        for (x, dst) in groups.into_remainder().iter_mut().enumerate() {
+            *dst = scalar(origin.0 + (tail + x) as f64 * step.0, yin) as f32;
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Records the range of the values sampled from a generator.
+
+use core::cell::Cell;
+use crate::gen::NoiseGen;
+
+/// A generator adapter that records the running minimum and maximum of every
+/// value that passes through it.
+///
+/// Wrap a source in a probe, render as usual, then read `range()` to
+/// calibrate a normalization from the values that were actually produced.
+///
+/// The extrema are tracked with `Cell`s, so a probe is meant to be used from
+/// a single thread: it cannot be shared between threads.
+#[derive(Debug)]
+pub struct MinMaxProbe<N> {
+    source: N,
+    min: Cell<f64>,
+    max: Cell<f64>
+}
+
+impl<N: NoiseGen> MinMaxProbe<N> {
+    /// Wraps `source` in a probe that has not recorded anything yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, MinMaxProbe, Simplex};
+    ///
+    /// let probe = MinMaxProbe::new(Simplex::new());
+    /// probe.noise2d(1.0, 2.0);
+    /// let (min, max) = probe.range().unwrap();
+    /// ```
+    pub fn new(source: N) -> MinMaxProbe<N> {
+        MinMaxProbe {
+            source: source,
+            min: Cell::new(f64::INFINITY),
+            max: Cell::new(f64::NEG_INFINITY)
+        }
+    }
+
+    /// Returns the `(min, max)` of the values sampled so far, or `None` if
+    /// nothing has been sampled since the probe was created or reset.
+    pub fn range(&self) -> Option<(f64, f64)> {
+        if self.min.get() > self.max.get() {
+            None
+        } else {
+            Some((self.min.get(), self.max.get()))
+        }
+    }
+
+    /// Forgets the recorded range.
+    pub fn reset(&self) {
+        self.min.set(f64::INFINITY);
+        self.max.set(f64::NEG_INFINITY);
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &N {
+        &self.source
+    }
+
+    /// Records a sampled value and passes it through.
+    #[inline]
+    fn record(&self, val: f64) -> f64 {
+        self.min.set(self.min.get().min(val));
+        self.max.set(self.max.get().max(val));
+
+        val
+    }
+}
+
+impl<N: NoiseGen> NoiseGen for MinMaxProbe<N> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.record(self.source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.record(self.source.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.record(self.source.noise3d(xin, yin, zin))
+    }
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.record(self.source.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
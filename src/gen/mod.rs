@@ -1,12 +1,85 @@
 //! Procedural noise generators.
 
-pub use gen::simplex::Simplex;
-pub use gen::perlin::Perlin;
-pub use gen::checkerboard::Checkerboard;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+pub use crate::gen::simplex::{ Simplex, RaySampler };
+pub use crate::gen::perlin::Perlin;
+pub use crate::gen::open_simplex::OpenSimplex;
+pub use crate::gen::checkerboard::Checkerboard;
+pub use crate::gen::worley::{ Worley, DistanceKind, FeatureKind };
+pub use crate::gen::value::Value;
+pub use crate::gen::grid::Grid2d;
+pub use crate::gen::min_max_probe::MinMaxProbe;
+pub use crate::gen::unit_range::UnitRange;
+pub use crate::gen::domain_warp::DomainWarp2;
+pub use crate::gen::fbm::Fbm;
+pub use crate::gen::billow::Billow;
+pub use crate::gen::tileable::Tileable2d;
+pub use crate::gen::constant::Constant;
+pub use crate::gen::gradient::Gradient;
+pub use crate::gen::select::Select;
+pub use crate::gen::turbulence::Turbulence;
+pub use crate::gen::ridged::RidgedMulti;
+pub use crate::gen::hybrid::HybridMulti;
+pub use crate::gen::displace::Displace;
+pub use crate::gen::blend::Blend;
+pub use crate::gen::cache::Cache;
+pub use crate::gen::builder::NoiseBuilder;
+pub use crate::gen::checked::Checked;
+
+pub mod modifier;
+pub mod combine;
+pub mod shapes;
 
 mod simplex;
 mod perlin;
+mod open_simplex;
 mod checkerboard;
+mod worley;
+mod value;
+mod grid;
+mod min_max_probe;
+mod unit_range;
+mod domain_warp;
+mod fbm;
+mod billow;
+mod tileable;
+mod constant;
+mod gradient;
+mod select;
+mod turbulence;
+mod ridged;
+mod hybrid;
+mod displace;
+mod blend;
+mod cache;
+mod builder;
+mod checked;
+#[cfg(feature = "simd")]
+mod lanes;
+
+/// A generator whose randomness is derived from an integer seed.
+///
+/// Lets generic code reseed any generator, e.g. to regenerate a world from a
+/// saved seed without knowing which generators it is built from.
+pub trait Seedable {
+  /// Rebuilds the generator's random state from `seed`. Reseeding two
+  /// generators of the same kind and settings with the same seed makes them
+  /// equal.
+  fn set_seed(&mut self, seed: u64);
+
+  /// Returns the seed the generator was last built or reseeded from.
+  ///
+  /// Generators built from an arbitrary RNG or a saved permutation table
+  /// don't know their seed and return 0.
+  fn seed(&self) -> u64;
+}
 
 /// A procedural noise generator.
 pub trait NoiseGen {
@@ -18,4 +91,388 @@ pub trait NoiseGen {
 
   /// For a given (x, y, z) coordinate, return a value between -1 and 1.
   fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64;
+
+  /// For a given (x, y, z, w) coordinate, return a value between -1 and 1.
+  ///
+  /// The fourth axis is typically used as time, to animate 3D noise without
+  /// the seams of reusing a spatial axis. Generators without a 4D variant
+  /// panic, naming the generator.
+  fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+    let _ = (xin, yin, zin, win);
+    panic!("{} does not implement noise4d", core::any::type_name::<Self>())
+  }
+
+  /// Returns the interval `(lower, upper)` the output is guaranteed to lie
+  /// in.
+  ///
+  /// Generators default to [-1, 1]. Modifiers and combiners derive their
+  /// bounds from their sources, so the range of a whole pipeline is known
+  /// without sampling it, e.g. to normalize its output. The bounds may be
+  /// wider than the values actually produced.
+  fn bounds(&self) -> (f64, f64) {
+    (-1.0, 1.0)
+  }
+
+  /// Single-precision variant of `noise1d`, for filling f32 buffers.
+  ///
+  /// The coordinate is widened to f64 and the result narrowed back to f32.
+  fn noise1d_f32(&self, xin: f32) -> f32 {
+    self.noise1d(xin as f64) as f32
+  }
+
+  /// Single-precision variant of `noise2d`, for filling f32 buffers.
+  fn noise2d_f32(&self, xin: f32, yin: f32) -> f32 {
+    self.noise2d(xin as f64, yin as f64) as f32
+  }
+
+  /// Single-precision variant of `noise3d`, for filling f32 buffers.
+  fn noise3d_f32(&self, xin: f32, yin: f32, zin: f32) -> f32 {
+    self.noise3d(xin as f64, yin as f64, zin as f64) as f32
+  }
+
+  /// Single-precision variant of `noise4d`, for filling f32 buffers.
+  fn noise4d_f32(&self, xin: f32, yin: f32, zin: f32, win: f32) -> f32 {
+    self.noise4d(xin as f64, yin as f64, zin as f64, win as f64) as f32
+  }
+
+  /// Samples 2D noise at a point held as an `[x, y]` array, as returned by
+  /// most vector math libraries.
+  fn noise_point2(&self, p: [f64; 2]) -> f64 {
+    self.noise2d(p[0], p[1])
+  }
+
+  /// Samples 3D noise at a point held as an `[x, y, z]` array.
+  fn noise_point3(&self, p: [f64; 3]) -> f64 {
+    self.noise3d(p[0], p[1], p[2])
+  }
+
+  /// Samples a 2D field animated over time `t`, as the slice of `noise3d`
+  /// at z = `t`.
+  ///
+  /// Holding `t` fixed gives a stable 2D field that changes smoothly as `t`
+  /// moves. Scale `t` to set how fast the field evolves, independently of
+  /// the spatial frequency.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use noisy::gen::{NoiseGen, Simplex};
+  ///
+  /// let simplex = Simplex::new();
+  /// let seconds: f64 = 2.5;
+  /// let val = simplex.noise2d_at_time(1.0, 2.0, seconds * 0.1);
+  /// ```
+  fn noise2d_at_time(&self, xin: f64, yin: f64, t: f64) -> f64 {
+    self.noise3d(xin, yin, t)
+  }
+
+  /// Displaces (x, y) by this generator's own noise, scaled by `strength`.
+  ///
+  /// The x and y displacements are sampled at offset positions so they are
+  /// decorrelated. The returned coordinate can be reused to sample several
+  /// generators at the same warped position.
+  fn warp2d(&self, xin: f64, yin: f64, strength: f64) -> (f64, f64) {
+    (xin + strength * self.noise2d(xin, yin),
+     yin + strength * self.noise2d(xin + 5.2, yin + 1.3))
+  }
+
+  /// Samples 2D noise at the coordinate returned by `warp2d`.
+  fn noise2d_warped(&self, xin: f64, yin: f64, strength: f64) -> f64 {
+    let (x, y) = self.warp2d(xin, yin, strength);
+
+    self.noise2d(x, y)
+  }
+
+  /// Given a (x, y) coordinate, return a single-octave ridged value in the
+  /// interval [-1, 1].
+  ///
+  /// This is `1 - 2 * |noise2d(x, y)|`: the zero crossings of the noise
+  /// become sharp ridges at 1, and its extrema become valleys at -1.
+  fn ridge2d(&self, xin: f64, yin: f64) -> f64 {
+    (1.0 - 2.0 * self.noise2d(xin, yin).abs()).clamp(-1.0, 1.0)
+  }
+
+  /// Returns the partial derivatives `(d/dx, d/dy)` of `noise2d` at (x, y).
+  ///
+  /// The default estimates them by central differences over a step of
+  /// 1e-5. Generators with analytic derivatives override it.
+  fn gradient2d(&self, xin: f64, yin: f64) -> (f64, f64) {
+    let h: f64 = 1e-5;
+    let dx: f64 = (self.noise2d(xin + h, yin) - self.noise2d(xin - h, yin)) / (2.0 * h);
+    let dy: f64 = (self.noise2d(xin, yin + h) - self.noise2d(xin, yin - h)) / (2.0 * h);
+
+    (dx, dy)
+  }
+
+  /// Returns the unit surface normal at (x, y) of the heightfield
+  /// `z = strength * noise2d(x, y)`, e.g. for lighting a terrain.
+  ///
+  /// The normal is built from `gradient2d` and always points towards +z.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use noisy::gen::{NoiseGen, Simplex};
+  ///
+  /// let simplex = Simplex::new();
+  /// let [nx, ny, nz] = simplex.normal2d(1.0, 2.0, 0.5);
+  /// ```
+  fn normal2d(&self, xin: f64, yin: f64, strength: f64) -> [f64; 3] {
+    let (dx, dy) = self.gradient2d(xin, yin);
+    let (nx, ny): (f64, f64) = (-strength * dx, -strength * dy);
+    let len: f64 = (nx * nx + ny * ny + 1.0).sqrt();
+
+    [nx / len, ny / len, 1.0 / len]
+  }
+
+  /// Returns whether the noise value at (x, y) lies strictly above `threshold`.
+  fn above(&self, xin: f64, yin: f64, threshold: f64) -> bool {
+    self.noise2d(xin, yin) > threshold
+  }
+
+  /// Samples 2D noise at `samples` evenly spaced points on the segment from
+  /// `start` to `end`, both included, e.g. to plot a cross-section.
+  ///
+  /// A single sample is taken at `start`.
+  fn sample_line2d(&self, start: (f64, f64), end: (f64, f64), samples: usize) -> Vec<f64> {
+    let last: f64 = if samples > 1 { (samples - 1) as f64 } else { 1.0 };
+
+    (0..samples).map(|i| {
+      let t: f64 = i as f64 / last;
+      self.noise2d(start.0 + (end.0 - start.0) * t, start.1 + (end.1 - start.1) * t)
+    }).collect()
+  }
+
+  /// Samples a `width` by `height` grid of 2D noise values in row-major order.
+  ///
+  /// The sample at column `x` and row `y` is taken at
+  /// `(origin.0 + x * step.0, origin.1 + y * step.1)`.
+  fn fill_grid2d(&self, width: usize, height: usize, origin: (f64, f64), step: (f64, f64)) -> Vec<f64> {
+    let mut grid: Vec<f64> = Vec::with_capacity(width * height);
+    for y in 0..height {
+      let yin: f64 = origin.1 + y as f64 * step.1;
+      for x in 0..width {
+        grid.push(self.noise2d(origin.0 + x as f64 * step.0, yin));
+      }
+    }
+
+    grid
+  }
+
+  /// Samples a quick, low-resolution preview of `fill_grid2d`.
+  ///
+  /// Only every `stride`-th column of every `stride`-th row is sampled; the
+  /// points in between copy the sample at the top-left of their
+  /// `stride` by `stride` block. The grid has the same layout as
+  /// `fill_grid2d` and a stride of 1 produces exactly the same values.
+  /// Panics if `stride` is 0.
+  fn fill_grid2d_strided(&self, width: usize, height: usize, origin: (f64, f64), step: (f64, f64), stride: usize) -> Vec<f64> {
+    assert!(stride > 0, "stride must be at least 1");
+
+    let mut grid: Vec<f64> = Vec::with_capacity(width * height);
+    for y in 0..height {
+      if y % stride != 0 {
+        // Repeat the row sampled at the top of this block.
+        let start: usize = (y - y % stride) * width;
+        for x in 0..width {
+          let val: f64 = grid[start + x];
+          grid.push(val);
+        }
+        continue;
+      }
+
+      let yin: f64 = origin.1 + y as f64 * step.1;
+      let mut val: f64 = 0.0;
+      for x in 0..width {
+        if x % stride == 0 {
+          val = self.noise2d(origin.0 + x as f64 * step.0, yin);
+        }
+        grid.push(val);
+      }
+    }
+
+    grid
+  }
+
+  /// Fills `buf` with a `width` by `height` grid of 2D noise values in
+  /// row-major order, narrowed to `f32` for texture uploads.
+  ///
+  /// The grid is laid out the same way as in `fill_grid2d`. Panics if `buf`
+  /// holds fewer than `width * height` values.
+  fn fill_grid2d_f32(&self, buf: &mut [f32], width: usize, height: usize, origin: (f64, f64), step: (f64, f64)) {
+    assert!(buf.len() >= width * height, "buffer too small for a {}x{} grid", width, height);
+
+    for y in 0..height {
+      let yin: f64 = origin.1 + y as f64 * step.1;
+      let row = &mut buf[y * width..(y + 1) * width];
+      for (x, val) in row.iter_mut().enumerate() {
+        *val = self.noise2d(origin.0 + x as f64 * step.0, yin) as f32;
+      }
+    }
+  }
+
+  /// Samples a `width` by `height` grid of 2D noise values.
+  ///
+  /// This is `fill_grid2d` wrapped in a `Grid2d`, which can be indexed by
+  /// `(column, row)` instead of computing `row * width + column` by hand.
+  fn field2d(&self, width: usize, height: usize, origin: (f64, f64), step: (f64, f64)) -> Grid2d {
+    Grid2d::new(self.fill_grid2d(width, height, origin, step), width, height)
+  }
+
+  /// Returns the (column, row) indices of the grid points whose noise value
+  /// lies strictly above `threshold`, in row-major order.
+  ///
+  /// The grid is laid out the same way as in `fill_grid2d`, which makes this
+  /// handy for scattering objects such as trees or rocks at noise peaks.
+  fn peaks_above(&self, width: usize, height: usize, origin: (f64, f64), step: (f64, f64), threshold: f64) -> Vec<(usize, usize)> {
+    let mut peaks: Vec<(usize, usize)> = Vec::new();
+    for y in 0..height {
+      let yin: f64 = origin.1 + y as f64 * step.1;
+      for x in 0..width {
+        if self.above(origin.0 + x as f64 * step.0, yin, threshold) {
+          peaks.push((x, y));
+        }
+      }
+    }
+
+    peaks
+  }
+
+  /// Iterates over a `width` by `height` grid of 2D noise values in
+  /// row-major order, yielding `(column, row, value)`.
+  ///
+  /// The grid is laid out the same way as in `fill_grid2d`, but the values
+  /// are computed lazily and the generator is only borrowed, so the samples
+  /// can be filtered or mapped without an intermediate buffer.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use noisy::gen::{NoiseGen, Simplex};
+  ///
+  /// let simplex = Simplex::new();
+  /// let trees: Vec<(usize, usize)> = simplex.samples2d((0.0, 0.0), (0.1, 0.1), 64, 64)
+  ///     .filter(|&(_, _, val)| val > 0.6)
+  ///     .map(|(x, y, _)| (x, y))
+  ///     .collect();
+  /// ```
+  fn samples2d(&self, origin: (f64, f64), step: (f64, f64), width: usize, height: usize) -> impl Iterator<Item = (usize, usize, f64)>
+    where Self: Sized {
+    (0..height).flat_map(move |y| {
+      let yin: f64 = origin.1 + y as f64 * step.1;
+      (0..width).map(move |x| (x, y, self.noise2d(origin.0 + x as f64 * step.0, yin)))
+    })
+  }
+}
+
+/// Samples the boxed generator, so pipelines can hold generators whose type
+/// is only known at runtime.
+impl<G: NoiseGen + ?Sized> NoiseGen for Box<G> {
+  fn noise1d(&self, xin: f64) -> f64 {
+    (**self).noise1d(xin)
+  }
+
+  fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+    (**self).noise2d(xin, yin)
+  }
+
+  fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+    (**self).noise3d(xin, yin, zin)
+  }
+
+  fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+    (**self).noise4d(xin, yin, zin, win)
+  }
+
+  fn bounds(&self) -> (f64, f64) {
+    (**self).bounds()
+  }
+
+  fn gradient2d(&self, xin: f64, yin: f64) -> (f64, f64) {
+    (**self).gradient2d(xin, yin)
+  }
+}
+
+/// Samples the borrowed generator, so combinators can take generators owned
+/// elsewhere without moving or cloning them.
+impl<G: NoiseGen + ?Sized> NoiseGen for &G {
+  fn noise1d(&self, xin: f64) -> f64 {
+    (**self).noise1d(xin)
+  }
+
+  fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+    (**self).noise2d(xin, yin)
+  }
+
+  fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+    (**self).noise3d(xin, yin, zin)
+  }
+
+  fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+    (**self).noise4d(xin, yin, zin, win)
+  }
+
+  fn bounds(&self) -> (f64, f64) {
+    (**self).bounds()
+  }
+
+  fn gradient2d(&self, xin: f64, yin: f64) -> (f64, f64) {
+    (**self).gradient2d(xin, yin)
+  }
+}
+
+/// Samples the shared generator, so several consumers can use one
+/// permutation table without cloning it.
+impl<G: NoiseGen + ?Sized> NoiseGen for Rc<G> {
+  fn noise1d(&self, xin: f64) -> f64 {
+    (**self).noise1d(xin)
+  }
+
+  fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+    (**self).noise2d(xin, yin)
+  }
+
+  fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+    (**self).noise3d(xin, yin, zin)
+  }
+
+  fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+    (**self).noise4d(xin, yin, zin, win)
+  }
+
+  fn bounds(&self) -> (f64, f64) {
+    (**self).bounds()
+  }
+
+  fn gradient2d(&self, xin: f64, yin: f64) -> (f64, f64) {
+    (**self).gradient2d(xin, yin)
+  }
+}
+
+/// Samples the shared generator, so several consumers (possibly on
+/// different threads) can use one permutation table without cloning it.
+impl<G: NoiseGen + ?Sized> NoiseGen for Arc<G> {
+  fn noise1d(&self, xin: f64) -> f64 {
+    (**self).noise1d(xin)
+  }
+
+  fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+    (**self).noise2d(xin, yin)
+  }
+
+  fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+    (**self).noise3d(xin, yin, zin)
+  }
+
+  fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+    (**self).noise4d(xin, yin, zin, win)
+  }
+
+  fn bounds(&self) -> (f64, f64) {
+    (**self).bounds()
+  }
+
+  fn gradient2d(&self, xin: f64, yin: f64) -> (f64, f64) {
+    (**self).gradient2d(xin, yin)
+  }
 }
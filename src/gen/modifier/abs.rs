@@ -0,0 +1,76 @@
+//! Folds the output of a generator around zero.
+
+use crate::gen::NoiseGen;
+
+/// A generator returning `2 * |source| - 1`.
+///
+/// Folding the negative half of the output onto the positive half turns the
+/// zero crossings of the source into sharp creases and its extrema into
+/// rounded bumps, while keeping the output within [-1, 1].
+///
+/// Wrapping `Abs` in an `Fbm` produces billow noise, the puffy look used for
+/// clouds and rolling hills, which `Billow` packages as a single type:
+///
+/// ```rust
+/// use noisy::gen::{NoiseGen, Fbm, Simplex};
+/// use noisy::gen::modifier::Abs;
+///
+/// let billow = Fbm::new(Abs::new(Simplex::new()), 4);
+/// let val = billow.noise2d(1.0, 2.0);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Abs<G> {
+    source: G
+}
+
+impl<G: NoiseGen> Abs<G> {
+    /// Wraps `source`, folding its output around zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    /// use noisy::gen::modifier::Abs;
+    ///
+    /// let abs = Abs::new(Simplex::new());
+    /// ```
+    pub fn new(source: G) -> Abs<G> {
+        Abs { source: source }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Abs<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        2.0 * self.source.noise1d(xin).abs() - 1.0
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        2.0 * self.source.noise2d(xin, yin).abs() - 1.0
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        2.0 * self.source.noise3d(xin, yin, zin).abs() - 1.0
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        2.0 * self.source.noise4d(xin, yin, zin, win).abs() - 1.0
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        let (lower, upper) = self.source.bounds();
+        let (lower, upper) = if lower >= 0.0 {
+            (lower, upper)
+        } else if upper <= 0.0 {
+            (-upper, -lower)
+        } else {
+            (0.0, upper.max(-lower))
+        };
+
+        (2.0 * lower - 1.0, 2.0 * upper - 1.0)
+    }
+}
@@ -0,0 +1,78 @@
+//! Restricts the output of a generator to a range.
+
+use crate::gen::NoiseGen;
+
+/// A generator clamping the output of its source to `[lower, upper]`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Clamp<G> {
+    source: G,
+    lower: f64,
+    upper: f64
+}
+
+impl<G: NoiseGen> Clamp<G> {
+    /// Wraps `source`, clamping its output to `[lower, upper]`.
+    ///
+    /// Panics if `lower` is greater than `upper`. When both bounds are equal
+    /// the output is constant.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    /// use noisy::gen::modifier::Clamp;
+    ///
+    /// let clamp = Clamp::new(Simplex::new(), -0.5, 0.5);
+    /// let val = clamp.noise2d(1.0, 2.0);
+    /// assert!(val >= -0.5 && val <= 0.5);
+    /// ```
+    pub fn new(source: G, lower: f64, upper: f64) -> Clamp<G> {
+        assert!(lower <= upper, "clamp lower bound {} is greater than upper bound {}", lower, upper);
+
+        Clamp { source: source, lower: lower, upper: upper }
+    }
+
+    /// Returns the lower bound.
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns the upper bound.
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    #[inline]
+    fn clamp(&self, val: f64) -> f64 {
+        val.max(self.lower).min(self.upper)
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Clamp<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.clamp(self.source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.clamp(self.source.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.clamp(self.source.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.clamp(self.source.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        let (lower, upper) = self.source.bounds();
+
+        (self.clamp(lower), self.clamp(upper))
+    }
+}
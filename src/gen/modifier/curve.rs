@@ -0,0 +1,188 @@
+//! Remaps the output of a generator through a transfer curve.
+
+use alloc::vec::Vec;
+
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// A generator mapping the output of its source through a curve defined by
+/// (input, output) control points.
+///
+/// Between two control points the curve follows a Catmull-Rom spline through
+/// the surrounding points, so it passes through every control point exactly.
+/// Values below the first or above the last control point map to the output
+/// of that endpoint.
+///
+/// Placing pairs of control points close together with the same output
+/// flattens the curve between them, which carves terraces into terrain.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Curve<G> {
+    source: G,
+    points: Vec<(f64, f64)>
+}
+
+impl<G: NoiseGen> Curve<G> {
+    /// Wraps `source`, mapping its output through `points`. The points are
+    /// sorted by their input.
+    ///
+    /// Panics if fewer than 4 control points are given or an input is NaN.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    /// use noisy::gen::modifier::Curve;
+    ///
+    /// // Flatten the lowlands and sharpen the peaks.
+    /// let curve = Curve::new(Simplex::new(), vec![
+    ///     (-1.0, -1.0), (-0.2, -0.6), (0.4, 0.0), (0.8, 0.5), (1.0, 1.0)
+    /// ]);
+    /// let val = curve.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, points: Vec<(f64, f64)>) -> Curve<G> {
+        assert!(points.len() >= 4, "curve needs at least 4 control points, got {}", points.len());
+
+        let mut points = points;
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("curve control point input is NaN"));
+
+        Curve { source: source, points: points }
+    }
+
+    /// Returns the control points, sorted by input.
+    pub fn points(&self) -> &[(f64, f64)] {
+        self.points.as_slice()
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Maps a value through the curve.
+    fn map(&self, val: f64) -> f64 {
+        let last: usize = self.points.len() - 1;
+
+        // Find the first control point with an input above the value.
+        let mut pos: usize = 0;
+        while pos <= last && val >= self.points[pos].0 {
+            pos += 1;
+        }
+
+        let clamp = |idx: isize| -> usize {
+            if idx < 0 { 0 } else if idx as usize > last { last } else { idx as usize }
+        };
+        let pos: isize = pos as isize;
+        let i0: usize = clamp(pos - 2);
+        let i1: usize = clamp(pos - 1);
+        let i2: usize = clamp(pos);
+        let i3: usize = clamp(pos + 1);
+
+        // Outside of the curve, or on coincident inputs: no segment to follow.
+        if i1 == i2 || self.points[i1].0 == self.points[i2].0 {
+            return self.points[i1].1;
+        }
+
+        let alpha: f64 = (val - self.points[i1].0) / (self.points[i2].0 - self.points[i1].0);
+
+        catmull_rom(
+            self.points[i0].1,
+            self.points[i1].1,
+            self.points[i2].1,
+            self.points[i3].1,
+            alpha
+        )
+    }
+}
+
+/// Returns the lowest and highest value a Catmull-Rom segment between `n1`
+/// and `n2` takes on.
+///
+/// Besides its endpoints, the spline can overshoot them where its
+/// derivative vanishes inside the segment.
+fn catmull_rom_range(n0: f64, n1: f64, n2: f64, n3: f64) -> (f64, f64) {
+    // The derivative is 0.5 * (b + 2c t + 3d t^2).
+    let b: f64 = n2 - n0;
+    let c: f64 = 2.0 * n0 - 5.0 * n1 + 4.0 * n2 - n3;
+    let d: f64 = 3.0 * (n1 - n2) + n3 - n0;
+
+    let mut roots: [f64; 2] = [0.0, 1.0];
+    if d == 0.0 {
+        if c != 0.0 {
+            roots[0] = -b / (2.0 * c);
+        }
+    } else {
+        let disc: f64 = c * c - 3.0 * d * b;
+        if disc >= 0.0 {
+            let sq: f64 = disc.sqrt();
+            roots = [(-c - sq) / (3.0 * d), (-c + sq) / (3.0 * d)];
+        }
+    }
+
+    let mut range: (f64, f64) = (n1.min(n2), n1.max(n2));
+    for &t in roots.iter() {
+        if t > 0.0 && t < 1.0 {
+            let val: f64 = catmull_rom(n0, n1, n2, n3, t);
+            range = (range.0.min(val), range.1.max(val));
+        }
+    }
+
+    range
+}
+
+/// Interpolates between `n1` and `n2` with a Catmull-Rom spline.
+#[inline]
+fn catmull_rom(n0: f64, n1: f64, n2: f64, n3: f64, t: f64) -> f64 {
+    0.5 * (2.0 * n1
+        + (n2 - n0) * t
+        + (2.0 * n0 - 5.0 * n1 + 4.0 * n2 - n3) * t * t
+        + (3.0 * (n1 - n2) + n3 - n0) * t * t * t)
+}
+
+impl<G: NoiseGen> NoiseGen for Curve<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.map(self.source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.map(self.source.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.map(self.source.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.map(self.source.noise4d(xin, yin, zin, win))
+    }
+
+    /// Spans every control point output, widened wherever a segment of the
+    /// spline overshoots its endpoints.
+    fn bounds(&self) -> (f64, f64) {
+        let last: usize = self.points.len() - 1;
+        let out = |idx: usize| self.points[idx].1;
+
+        let mut bounds: (f64, f64) = (f64::INFINITY, f64::NEG_INFINITY);
+        for (_, val) in self.points.iter() {
+            bounds = (bounds.0.min(*val), bounds.1.max(*val));
+        }
+
+        // Follow the same neighbours as `map` does for each segment.
+        for i in 0..last {
+            if self.points[i].0 == self.points[i + 1].0 {
+                continue;
+            }
+
+            let (lower, upper) = catmull_rom_range(
+                out(i.saturating_sub(1)),
+                out(i),
+                out(i + 1),
+                out((i + 2).min(last))
+            );
+            bounds = (bounds.0.min(lower), bounds.1.max(upper));
+        }
+
+        bounds
+    }
+}
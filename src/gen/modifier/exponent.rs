@@ -0,0 +1,76 @@
+//! Reshapes the output distribution of a generator with a power curve.
+
+use crate::utils::to_unit;
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// A generator mapping the output of its source to [0, 1], raising it to
+/// `exponent` and mapping the result back to [-1, 1].
+///
+/// The ends of the range stay fixed while everything in between is bent:
+/// exponents above 1 pull values down towards -1, widening valleys and
+/// narrowing peaks, and exponents between 0 and 1 push them up towards 1,
+/// flattening the highs into plateaus. Fractional exponents are fine since
+/// the power is taken of a value in [0, 1]; an exponent of 0 maps everything
+/// to 1, and negative exponents leave the range.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Exponent<G> {
+    source: G,
+    /// The power the unit-range output is raised to.
+    pub exponent: f64
+}
+
+impl<G: NoiseGen> Exponent<G> {
+    /// Wraps `source`, bending its output with `exponent`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    /// use noisy::gen::modifier::Exponent;
+    ///
+    /// // Broad valleys and sharp peaks.
+    /// let valleys = Exponent::new(Simplex::new(), 2.0);
+    /// let val = valleys.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, exponent: f64) -> Exponent<G> {
+        Exponent { source: source, exponent: exponent }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    #[inline]
+    fn bend(&self, val: f64) -> f64 {
+        to_unit(val).powf(self.exponent) * 2.0 - 1.0
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Exponent<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.bend(self.source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.bend(self.source.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.bend(self.source.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.bend(self.source.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        let (lower, upper) = self.source.bounds();
+        let (lower, upper) = (self.bend(lower), self.bend(upper));
+
+        (lower.min(upper), lower.max(upper))
+    }
+}
@@ -0,0 +1,172 @@
+//! Reshapes the output histogram of a generator towards a normal
+//! distribution.
+
+use core::fmt;
+use alloc::vec::Vec;
+
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// The number of calibration samples taken per dimension.
+const SAMPLES: usize = 4096;
+
+/// A generator remapping the output of its source so its histogram is
+/// approximately Gaussian, while staying within [-1, 1].
+///
+/// On construction, the source is sampled at 4096 fixed points per
+/// dimension, spread over one 256-unit period of the lattice with a
+/// low-discrepancy sequence, and the sorted samples serve as an estimate of
+/// the source's distribution. Each output value is then ranked against the
+/// samples of its dimension and pushed through an approximation of the
+/// inverse normal CDF, scaled so the most extreme rank lands on ±1. The
+/// result is close to a normal distribution with a standard deviation of
+/// about 0.29, for any source.
+///
+/// This is an approximation: the calibration only sees a finite sample, and
+/// sources whose distribution changes across space are matched on average.
+/// The remap never decreases, so the order of values is kept. `noise4d`
+/// output is remapped with the 3D calibration, so sources without a 4D
+/// variant can still be wrapped.
+#[derive(Clone, PartialEq)]
+pub struct Gaussianize<G> {
+    source: G,
+    sorted: [Vec<f64>; 3]
+}
+
+impl<G: NoiseGen> Gaussianize<G> {
+    /// Wraps `source`, calibrating the remap from its 1D, 2D and 3D output.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    /// use noisy::gen::modifier::Gaussianize;
+    ///
+    /// let normal = Gaussianize::new(Simplex::new());
+    /// let val = normal.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G) -> Gaussianize<G> {
+        // An additive recurrence on the inverse powers of the 3D generalized
+        // golden ratio fills the unit cube evenly without random numbers.
+        let alpha: [f64; 3] = [0.8191725133961645, 0.6710436067037893, 0.5497004779019703];
+        let at = |i: usize, axis: usize| ((0.5 + i as f64 * alpha[axis]) % 1.0) * 256.0;
+
+        let mut sorted: [Vec<f64>; 3] = [
+            (0..SAMPLES).map(|i| source.noise1d(at(i, 0))).collect(),
+            (0..SAMPLES).map(|i| source.noise2d(at(i, 0), at(i, 1))).collect(),
+            (0..SAMPLES).map(|i| source.noise3d(at(i, 0), at(i, 1), at(i, 2))).collect()
+        ];
+        for samples in sorted.iter_mut() {
+            samples.sort_by(|a, b| a.partial_cmp(b).expect("gaussianize source returned NaN"));
+        }
+
+        Gaussianize { source: source, sorted: sorted }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Remaps `val` through the calibration of dimension `dim` (0 to 2).
+    fn remap(&self, dim: usize, val: f64) -> f64 {
+        let samples: &[f64] = self.sorted[dim].as_slice();
+        let last: usize = samples.len() - 1;
+
+        // Fractional rank of the value among the samples, with ties ranked
+        // in the middle of their run.
+        let below: usize = samples.partition_point(|&s| s < val);
+        let upto: usize = samples.partition_point(|&s| s <= val);
+        let rank: f64 = if below < upto {
+            (below + upto - 1) as f64 * 0.5
+        } else if below == 0 {
+            0.0
+        } else if below > last {
+            last as f64
+        } else {
+            let (lo, hi) = (samples[below - 1], samples[below]);
+            (below - 1) as f64 + (val - lo) / (hi - lo)
+        };
+
+        // Keep clear of 0 and 1, where the inverse CDF diverges.
+        let n: f64 = samples.len() as f64;
+        let limit: f64 = -probit(0.5 / n);
+
+        (probit((rank + 0.5) / n) / limit).clamp(-1.0, 1.0)
+    }
+}
+
+/// Approximates the inverse of the standard normal CDF at `p` in (0, 1).
+///
+/// Peter Acklam's rational approximation, with a relative error below
+/// 1.15e-9.
+fn probit(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+                         1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00];
+    const B: [f64; 5] = [-5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+                         6.680131188771972e+01, -1.328068155288572e+01];
+    const C: [f64; 6] = [-7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+                         -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00];
+    const D: [f64; 4] = [7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+                         3.754408661907416e+00];
+    const LOW: f64 = 0.02425;
+
+    // The tails share one approximation, mirrored.
+    let tail = |p: f64| -> f64 {
+        let q: f64 = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < LOW {
+        tail(p)
+    } else if p > 1.0 - LOW {
+        -tail(1.0 - p)
+    } else {
+        let q: f64 = p - 0.5;
+        let r: f64 = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Prints the source; the calibration samples are summarized by their
+/// count.
+impl<G: fmt::Debug> fmt::Debug for Gaussianize<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Gaussianize")
+            .field("source", &self.source)
+            .field("samples", &SAMPLES)
+            .finish()
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Gaussianize<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.remap(0, self.source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.remap(1, self.source.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.remap(2, self.source.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.remap(2, self.source.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        // The remap never decreases, so it maps the source bounds onto the
+        // output bounds. They differ per dimension; take the widest.
+        let (lower, upper) = self.source.bounds();
+        let lower: f64 = (0..3).map(|dim| self.remap(dim, lower)).fold(1.0, f64::min);
+        let upper: f64 = (0..3).map(|dim| self.remap(dim, upper)).fold(-1.0, f64::max);
+
+        (lower, upper)
+    }
+}
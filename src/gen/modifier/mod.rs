@@ -0,0 +1,25 @@
+//! Generators that transform the output of another generator.
+
+pub use crate::gen::modifier::scale_bias::ScaleBias;
+pub use crate::gen::modifier::abs::Abs;
+pub use crate::gen::modifier::clamp::Clamp;
+pub use crate::gen::modifier::curve::Curve;
+pub use crate::gen::modifier::rotate_point::RotatePoint;
+pub use crate::gen::modifier::scale_point::ScalePoint;
+pub use crate::gen::modifier::translate_point::TranslatePoint;
+pub use crate::gen::modifier::exponent::Exponent;
+pub use crate::gen::modifier::terrace::Terrace;
+pub use crate::gen::modifier::repeat::Repeat;
+pub use crate::gen::modifier::gaussianize::Gaussianize;
+
+mod scale_bias;
+mod abs;
+mod clamp;
+mod curve;
+mod rotate_point;
+mod scale_point;
+mod translate_point;
+mod exponent;
+mod terrace;
+mod repeat;
+mod gaussianize;
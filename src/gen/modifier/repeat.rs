@@ -0,0 +1,84 @@
+//! Repeats the input domain of a generator.
+
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// A generator wrapping each axis of the input coordinate into
+/// `[0, period)` before sampling its source, so the output repeats with an
+/// integer period on every axis.
+///
+/// This repeats the input domain, it does not wrap the noise seamlessly:
+/// the source is sampled at the wrapped coordinate, so gradient noise such
+/// as `Simplex` or `Perlin` jumps wherever a coordinate wraps back to 0.
+/// It suits patterns that already line up with the integer lattice, such as
+/// `Checkerboard`, `Value` or `Worley` sampled at integer periods. Use
+/// `Tileable2d` for seamless gradient noise.
+///
+/// The w axis of `noise4d` is passed through unchanged.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Repeat<G> {
+    source: G,
+    period: [i64; 3]
+}
+
+impl<G: NoiseGen> Repeat<G> {
+    /// Wraps `source`, with a separate period for the x, y and z axes.
+    ///
+    /// Panics if a period is not positive.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Checkerboard};
+    /// use noisy::gen::modifier::Repeat;
+    ///
+    /// // A 3 by 2 tile of the check pattern, repeated forever.
+    /// let tile = Repeat::new(Checkerboard::new(), [3, 2, 1]);
+    /// assert_eq!(tile.noise2d(0.5, 0.5), tile.noise2d(3.5, -1.5));
+    /// ```
+    pub fn new(source: G, period: [i64; 3]) -> Repeat<G> {
+        assert!(period.iter().all(|&p| p > 0), "repeat periods must be positive, got {:?}", period);
+
+        Repeat { source: source, period: period }
+    }
+
+    /// Returns the period of each axis.
+    pub fn period(&self) -> [i64; 3] {
+        self.period
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Wraps a coordinate on `axis` into `[0, period)`.
+    #[inline]
+    fn wrap(&self, axis: usize, val: f64) -> f64 {
+        val.rem_euclid(self.period[axis] as f64)
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Repeat<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.source.noise1d(self.wrap(0, xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.source.noise2d(self.wrap(0, xin), self.wrap(1, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.source.noise3d(self.wrap(0, xin), self.wrap(1, yin), self.wrap(2, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.source.noise4d(self.wrap(0, xin), self.wrap(1, yin), self.wrap(2, zin), win)
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
@@ -0,0 +1,106 @@
+//! Rotates the input coordinate of a generator.
+
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// A generator rotating the input coordinate around the origin before
+/// sampling its source.
+///
+/// The rotation is given as Euler angles in radians and applied to a point
+/// counterclockwise around the x-axis first, then the y-axis, then the
+/// z-axis. Rotating the domain hides the subtle axis-aligned structure of
+/// lattice noise, and lets stacked layers use different orientations.
+///
+/// `noise2d` rotates the (x, y) plane by the z angle only and `noise4d`
+/// leaves the w axis alone. A single axis has nothing to rotate, so
+/// `noise1d` samples the source unchanged.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RotatePoint<G> {
+    source: G,
+    angles: (f64, f64, f64),
+    matrix: [[f64; 3]; 3],
+    plane: (f64, f64)
+}
+
+impl<G: NoiseGen> RotatePoint<G> {
+    /// Wraps `source`, rotating its input by `x_angle`, `y_angle` and
+    /// `z_angle` radians around the respective axes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Perlin};
+    /// use noisy::gen::modifier::RotatePoint;
+    ///
+    /// let rotated = RotatePoint::new(Perlin::new(), 0.3, 0.5, 0.7);
+    /// let val = rotated.noise3d(1.0, 2.0, 3.0);
+    /// ```
+    pub fn new(source: G, x_angle: f64, y_angle: f64, z_angle: f64) -> RotatePoint<G> {
+        let (sa, ca) = x_angle.sin_cos();
+        let (sb, cb) = y_angle.sin_cos();
+        let (sg, cg) = z_angle.sin_cos();
+
+        // Rz * Ry * Rx
+        let matrix: [[f64; 3]; 3] = [
+            [cg * cb, cg * sb * sa - sg * ca, cg * sb * ca + sg * sa],
+            [sg * cb, sg * sb * sa + cg * ca, sg * sb * ca - cg * sa],
+            [-sb, cb * sa, cb * ca]
+        ];
+
+        RotatePoint {
+            source: source,
+            angles: (x_angle, y_angle, z_angle),
+            matrix: matrix,
+            plane: (sg, cg)
+        }
+    }
+
+    /// Returns the rotation angles around the x, y and z axes, in radians.
+    pub fn angles(&self) -> (f64, f64, f64) {
+        self.angles
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    #[inline]
+    fn rotate(&self, xin: f64, yin: f64, zin: f64) -> (f64, f64, f64) {
+        let m = &self.matrix;
+
+        (m[0][0] * xin + m[0][1] * yin + m[0][2] * zin,
+         m[1][0] * xin + m[1][1] * yin + m[1][2] * zin,
+         m[2][0] * xin + m[2][1] * yin + m[2][2] * zin)
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for RotatePoint<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.source.noise1d(xin)
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let (sin, cos) = self.plane;
+
+        self.source.noise2d(cos * xin - sin * yin, sin * xin + cos * yin)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let (x, y, z) = self.rotate(xin, yin, zin);
+
+        self.source.noise3d(x, y, z)
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        let (x, y, z) = self.rotate(xin, yin, zin);
+
+        self.source.noise4d(x, y, z, win)
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
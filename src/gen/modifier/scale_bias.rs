@@ -0,0 +1,62 @@
+//! Applies an affine transform to the output of a generator.
+
+use crate::gen::NoiseGen;
+
+/// A generator returning `source * scale + bias`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScaleBias<G> {
+    source: G,
+    /// The factor the source output is multiplied by.
+    pub scale: f64,
+    /// The offset added after scaling.
+    pub bias: f64
+}
+
+impl<G: NoiseGen> ScaleBias<G> {
+    /// Wraps `source`, scaling its output by `scale` and then adding `bias`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    /// use noisy::gen::modifier::ScaleBias;
+    ///
+    /// // Remap [-1, 1] to [0, 1].
+    /// let unit = ScaleBias::new(Simplex::new(), 0.5, 0.5);
+    /// let val = unit.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, scale: f64, bias: f64) -> ScaleBias<G> {
+        ScaleBias { source: source, scale: scale, bias: bias }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for ScaleBias<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.source.noise1d(xin) * self.scale + self.bias
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.source.noise2d(xin, yin) * self.scale + self.bias
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.source.noise3d(xin, yin, zin) * self.scale + self.bias
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.source.noise4d(xin, yin, zin, win) * self.scale + self.bias
+    }
+
+    /// A negative scale flips the source bounds.
+    fn bounds(&self) -> (f64, f64) {
+        let (lower, upper) = self.source.bounds();
+        let (lower, upper) = (lower * self.scale + self.bias, upper * self.scale + self.bias);
+
+        (lower.min(upper), lower.max(upper))
+    }
+}
@@ -0,0 +1,63 @@
+//! Scales the input coordinate of a generator.
+
+use crate::gen::NoiseGen;
+
+/// A generator multiplying each axis of the input coordinate by its own
+/// factor before sampling its source.
+///
+/// The w axis of `noise4d` is passed through unchanged.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScalePoint<G> {
+    source: G,
+    /// The factor applied to the x axis.
+    pub x: f64,
+    /// The factor applied to the y axis.
+    pub y: f64,
+    /// The factor applied to the z axis.
+    pub z: f64
+}
+
+impl<G: NoiseGen> ScalePoint<G> {
+    /// Wraps `source`, with a separate factor for each axis.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    /// use noisy::gen::modifier::ScalePoint;
+    ///
+    /// // Samples `source` at twice the frequency on every axis.
+    /// let gen = ScalePoint::new(Simplex::new(), 2.0, 2.0, 2.0);
+    /// let val = gen.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, x: f64, y: f64, z: f64) -> ScalePoint<G> {
+        ScalePoint { source: source, x: x, y: y, z: z }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for ScalePoint<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.source.noise1d(xin * self.x)
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.source.noise2d(xin * self.x, yin * self.y)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.source.noise3d(xin * self.x, yin * self.y, zin * self.z)
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.source.noise4d(xin * self.x, yin * self.y, zin * self.z, win)
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
@@ -0,0 +1,134 @@
+//! Carves flat terraces into the output of a generator.
+
+use alloc::vec::Vec;
+
+use crate::gen::NoiseGen;
+use crate::utils::{ fade, lerp, FadeKind };
+
+/// A generator snapping the output of its source onto a staircase of
+/// control points.
+///
+/// Between two neighbouring control points the output rises from the lower
+/// point towards the upper one along `t²`, where `t` is the position of the
+/// source value between them. The curve is flat at the lower point and steep
+/// at the upper one, which gives wide plateaus ending in cliffs. Values below
+/// the first or above the last control point map to that endpoint.
+///
+/// Unlike `Curve`, the control points are plain output levels and every
+/// step has the same shape.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Terrace<G> {
+    source: G,
+    control_points: Vec<f64>,
+    invert: bool,
+    smooth: bool
+}
+
+impl<G: NoiseGen> Terrace<G> {
+    /// Wraps `source`, terracing its output at `control_points`. The points
+    /// are sorted.
+    ///
+    /// Panics if fewer than 2 control points are given or one is NaN.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    /// use noisy::gen::modifier::Terrace;
+    ///
+    /// let mesas = Terrace::new(Simplex::new(), vec![-1.0, -0.2, 0.3, 0.6, 1.0]);
+    /// let val = mesas.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, control_points: Vec<f64>) -> Terrace<G> {
+        assert!(control_points.len() >= 2, "terrace needs at least 2 control points, got {}", control_points.len());
+
+        let mut control_points = control_points;
+        control_points.sort_by(|a, b| a.partial_cmp(b).expect("terrace control point is NaN"));
+
+        Terrace { source: source, control_points: control_points, invert: false, smooth: false }
+    }
+
+    /// Flips every step upside down when `invert` is set, so the plateaus
+    /// sit at the upper control point and the cliffs at the lower one.
+    ///
+    /// Has no effect on smoothed steps, which are symmetric.
+    pub fn with_invert(self, invert: bool) -> Terrace<G> {
+        Terrace { invert: invert, ..self }
+    }
+
+    /// Eases each step along the quintic fade curve instead of `t²` when
+    /// `smooth` is set, giving plateaus at both control points joined by
+    /// rounded slopes.
+    pub fn with_smoothing(self, smooth: bool) -> Terrace<G> {
+        Terrace { smooth: smooth, ..self }
+    }
+
+    /// Returns the control points, sorted.
+    pub fn control_points(&self) -> &[f64] {
+        self.control_points.as_slice()
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Maps a value onto the terraces.
+    fn map(&self, val: f64) -> f64 {
+        let points: &[f64] = self.control_points.as_slice();
+        let last: usize = points.len() - 1;
+
+        // Find the first control point above the value.
+        let pos: usize = points.iter().position(|&p| val < p).unwrap_or(last + 1);
+        if pos == 0 {
+            return points[0];
+        }
+        if pos > last {
+            return points[last];
+        }
+
+        let (mut low, mut high): (f64, f64) = (points[pos - 1], points[pos]);
+        if low == high {
+            return low;
+        }
+
+        let mut t: f64 = (val - low) / (high - low);
+        if self.smooth {
+            t = fade(FadeKind::Quintic, t);
+        } else {
+            if self.invert {
+                t = 1.0 - t;
+                core::mem::swap(&mut low, &mut high);
+            }
+            t *= t;
+        }
+
+        lerp(t, low, high)
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Terrace<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.map(self.source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.map(self.source.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.map(self.source.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.map(self.source.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        // The terraces never decrease, so the source bounds map onto the
+        // output bounds.
+        let (lower, upper) = self.source.bounds();
+
+        (self.map(lower), self.map(upper))
+    }
+}
@@ -0,0 +1,63 @@
+//! Translates the input coordinate of a generator.
+
+use crate::gen::NoiseGen;
+
+/// A generator adding its own offset to each axis of the input coordinate
+/// before sampling its source.
+///
+/// The w axis of `noise4d` is passed through unchanged.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TranslatePoint<G> {
+    source: G,
+    /// The offset applied to the x axis.
+    pub x: f64,
+    /// The offset applied to the y axis.
+    pub y: f64,
+    /// The offset applied to the z axis.
+    pub z: f64
+}
+
+impl<G: NoiseGen> TranslatePoint<G> {
+    /// Wraps `source`, with a separate offset for each axis.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    /// use noisy::gen::modifier::TranslatePoint;
+    ///
+    /// // Samples `source` away from the origin, where lattice noise is zero.
+    /// let gen = TranslatePoint::new(Simplex::new(), 123.0, 132.0, 0.5);
+    /// let val = gen.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, x: f64, y: f64, z: f64) -> TranslatePoint<G> {
+        TranslatePoint { source: source, x: x, y: y, z: z }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for TranslatePoint<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.source.noise1d(xin + self.x)
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.source.noise2d(xin + self.x, yin + self.y)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.source.noise3d(xin + self.x, yin + self.y, zin + self.z)
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.source.noise4d(xin + self.x, yin + self.y, zin + self.z, win)
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
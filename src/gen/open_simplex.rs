@@ -0,0 +1,289 @@
+//! An implementation of [OpenSimplex Noise]
+//! (https://gist.github.com/KdotJPG/b1270127455a94ac5d19).
+//!
+//! Based on the 2D and 3D OpenSimplex noise algorithm by Kurt Spencer. It
+//! sums kernels centered on the vertices of a stretched cubic lattice instead
+//! of a simplex decomposition, and has no directional artifacts along the
+//! diagonals.
+
+use core::fmt;
+use alloc::vec::Vec;
+use alloc::string::String;
+
+use rand::Rng;
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand_xorshift::XorShiftRng;
+
+use crate::utils::{ fast_floor, perm_at, seeded_rng, validate_perm };
+use crate::gen::{ NoiseGen, Seedable };
+
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+#[cfg(feature = "serde")]
+use crate::utils::serde_perm;
+
+static STRETCH_2D: f64 = -0.211324865405187; // (1 / sqrt(2 + 1) - 1) / 2
+static SQUISH_2D: f64 = 0.366025403784439; // (sqrt(2 + 1) - 1) / 2
+static STRETCH_3D: f64 = -1.0 / 6.0; // (1 / sqrt(3 + 1) - 1) / 3
+static SQUISH_3D: f64 = 1.0 / 3.0; // (sqrt(3 + 1) - 1) / 3
+static NORM_2D: f64 = 47.0;
+static NORM_3D: f64 = 103.0;
+
+static GRADIENTS_2D: [(f64, f64); 8] = [
+    ( 5.0,  2.0), ( 2.0,  5.0), (-5.0,  2.0), (-2.0,  5.0),
+    ( 5.0, -2.0), ( 2.0, -5.0), (-5.0, -2.0), (-2.0, -5.0)
+];
+
+static GRADIENTS_3D: [(f64, f64, f64); 24] = [
+    (-11.0,  4.0,  4.0), (-4.0,  11.0,  4.0), (-4.0,  4.0,  11.0),
+    ( 11.0,  4.0,  4.0), ( 4.0,  11.0,  4.0), ( 4.0,  4.0,  11.0),
+    (-11.0, -4.0,  4.0), (-4.0, -11.0,  4.0), (-4.0, -4.0,  11.0),
+    ( 11.0, -4.0,  4.0), ( 4.0, -11.0,  4.0), ( 4.0, -4.0,  11.0),
+    (-11.0,  4.0, -4.0), (-4.0,  11.0, -4.0), (-4.0,  4.0, -11.0),
+    ( 11.0,  4.0, -4.0), ( 4.0,  11.0, -4.0), ( 4.0,  4.0, -11.0),
+    (-11.0, -4.0, -4.0), (-4.0, -11.0, -4.0), (-4.0, -4.0, -11.0),
+    ( 11.0, -4.0, -4.0), ( 4.0, -11.0, -4.0), ( 4.0, -4.0, -11.0)
+];
+
+// The lattice vertices, relative to the stretched cell containing the
+// sample, whose kernel can reach into that cell. Every other vertex is at
+// least sqrt(2) away and contributes nothing.
+static VERTICES_2D: [(i64, i64); 8] = [
+    (0, 0), (1, 0), (0, 1), (1, 1), (2, 0), (0, 2), (1, -1), (-1, 1)
+];
+
+static VERTICES_3D: [(i64, i64, i64); 26] = [
+    (0, 0, 0), (1, 0, 0), (0, 1, 0), (0, 0, 1),
+    (1, 1, 0), (1, 0, 1), (0, 1, 1), (1, 1, 1),
+    (2, 0, 0), (0, 2, 0), (0, 0, 2),
+    (2, 1, 0), (2, 0, 1), (1, 2, 0), (0, 2, 1), (1, 0, 2), (0, 1, 2),
+    (1, -1, 0), (1, 0, -1), (-1, 1, 0), (0, 1, -1), (-1, 0, 1), (0, -1, 1),
+    (1, 1, -1), (1, -1, 1), (-1, 1, 1)
+];
+
+/// An OpenSimplex noise generator.
+///
+/// The permutation table is seeded the same way as `Simplex`'s, so both
+/// generators accept the same seeds, random number generators and saved
+/// tables.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OpenSimplex {
+    seed: u64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_perm"))]
+    perm: Vec<u8>
+}
+
+impl OpenSimplex {
+    /// Initializes a new OpenSimplex instance with a random seed drawn from
+    /// `thread_rng`.
+    ///
+    /// Needs the `std` feature, which provides the entropy source.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::OpenSimplex;
+    ///
+    /// let open_simplex = OpenSimplex::new();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new() -> OpenSimplex {
+        OpenSimplex::from_seed(thread_rng().gen())
+    }
+
+    /// Initializes a new OpenSimplex instance deterministically from an
+    /// integer seed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::OpenSimplex;
+    ///
+    /// let open_simplex = OpenSimplex::from_seed(1337);
+    /// assert!(open_simplex == OpenSimplex::from_seed(1337));
+    /// ```
+    pub fn from_seed(seed: u64) -> OpenSimplex {
+        let mut rng: XorShiftRng = seeded_rng(seed);
+
+        OpenSimplex { seed: seed, ..OpenSimplex::from_rng(&mut rng) }
+    }
+
+    /// Initializes a new OpenSimplex instance with a random number generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
+    /// use noisy::gen::OpenSimplex;
+    ///
+    /// let mut rng: StdRng = StdRng::from_entropy();
+    /// let open_simplex = OpenSimplex::from_rng(&mut rng);
+    /// ```
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> OpenSimplex {
+        let p: Vec<u8> = (0..256).map(|_| (rng.next_u32() & 0xff) as u8).collect();
+        let perm: Vec<u8> = (0..512).map(|idx:i32| {p[(idx & 255) as usize]}).collect();
+
+        OpenSimplex { seed: 0, perm: perm }
+    }
+
+    /// Initializes a new OpenSimplex instance from a saved permutation table.
+    ///
+    /// The table must have 512 entries whose second half mirrors the first,
+    /// such as one returned by `perm`; an error describing the problem is
+    /// returned otherwise.
+    pub fn from_perm(perm: Vec<u8>) -> Result<OpenSimplex, String> {
+        validate_perm(perm.as_slice())?;
+
+        Ok(OpenSimplex { seed: 0, perm: perm })
+    }
+
+    /// Returns the permutation table used to hash the lattice coordinates.
+    pub fn perm(&self) -> &[u8] {
+        self.perm.as_slice()
+    }
+}
+
+/// Two generators are equal when they produce the same noise; the recorded
+/// seed is not compared.
+impl PartialEq for OpenSimplex {
+    fn eq(&self, other: &OpenSimplex) -> bool {
+        self.perm == other.perm
+    }
+}
+
+/// Prints the seed; the permutation table is summarized by its length.
+impl fmt::Debug for OpenSimplex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OpenSimplex")
+            .field("seed", &self.seed)
+            .field("perm_len", &self.perm.len())
+            .finish()
+    }
+}
+
+impl Seedable for OpenSimplex {
+    /// Rebuilds the permutation table from `seed`, as `from_seed` would.
+    fn set_seed(&mut self, seed: u64) {
+        self.perm = OpenSimplex::from_seed(seed).perm;
+        self.seed = seed;
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl NoiseGen for OpenSimplex {
+    /// Given an x coordinate, return a value in the interval [-1, 1].
+    ///
+    /// This is a slice of the 2D field along y = 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, OpenSimplex};
+    ///
+    /// let open_simplex = OpenSimplex::new();
+    /// let val = open_simplex.noise1d(123.0 * 0.02);
+    /// ```
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.noise2d(xin, 0.0)
+    }
+
+    /// Given a (x, y) coordinate, return a value in the interval [-1, 1].
+    ///
+    /// The 2D field peaks at about ±0.87.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, OpenSimplex};
+    ///
+    /// let open_simplex = OpenSimplex::new();
+    /// let val = open_simplex.noise2d(
+    ///     123.0 * 0.02,
+    ///     132.0 * 0.02
+    /// );
+    /// ```
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let perm: &[u8] = self.perm.as_slice();
+
+        // Place the input coordinate on the stretched lattice and find the
+        // cell containing it.
+        let stretch: f64 = (xin + yin) * STRETCH_2D;
+        let xsb: i64 = fast_floor(xin + stretch);
+        let ysb: i64 = fast_floor(yin + stretch);
+
+        let mut value: f64 = 0.0;
+        for &(i, j) in VERTICES_2D.iter() {
+            let (xsv, ysv) = (xsb + i, ysb + j);
+
+            // Squish the vertex back and take the offset to it.
+            let squish: f64 = (xsv + ysv) as f64 * SQUISH_2D;
+            let dx: f64 = xin - (xsv as f64 + squish);
+            let dy: f64 = yin - (ysv as f64 + squish);
+
+            let mut attn: f64 = 2.0 - dx * dx - dy * dy;
+            if attn > 0.0 {
+                let gi: u8 = perm_at(perm, perm_at(perm, (xsv & 255) as usize) as usize + (ysv & 255) as usize);
+                let (gx, gy) = GRADIENTS_2D[((gi & 0x0e) >> 1) as usize];
+
+                attn *= attn;
+                value += attn * attn * (gx * dx + gy * dy);
+            }
+        }
+
+        value / NORM_2D
+    }
+
+    /// Given a (x, y, z) coordinate, return a value in the interval [-1, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, OpenSimplex};
+    ///
+    /// let open_simplex = OpenSimplex::new();
+    /// let val = open_simplex.noise3d(
+    ///     123.0 * 0.02,
+    ///     231.0 * 0.02,
+    ///     321.0 * 0.02
+    /// );
+    /// ```
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let perm: &[u8] = self.perm.as_slice();
+
+        // Place the input coordinate on the stretched lattice and find the
+        // cell containing it.
+        let stretch: f64 = (xin + yin + zin) * STRETCH_3D;
+        let xsb: i64 = fast_floor(xin + stretch);
+        let ysb: i64 = fast_floor(yin + stretch);
+        let zsb: i64 = fast_floor(zin + stretch);
+
+        let mut value: f64 = 0.0;
+        for &(i, j, k) in VERTICES_3D.iter() {
+            let (xsv, ysv, zsv) = (xsb + i, ysb + j, zsb + k);
+
+            // Squish the vertex back and take the offset to it.
+            let squish: f64 = (xsv + ysv + zsv) as f64 * SQUISH_3D;
+            let dx: f64 = xin - (xsv as f64 + squish);
+            let dy: f64 = yin - (ysv as f64 + squish);
+            let dz: f64 = zin - (zsv as f64 + squish);
+
+            let mut attn: f64 = 2.0 - dx * dx - dy * dy - dz * dz;
+            if attn > 0.0 {
+                let hash: u8 = perm_at(perm, perm_at(perm, (xsv & 255) as usize) as usize + (ysv & 255) as usize);
+                let gi: u8 = perm_at(perm, hash as usize + (zsv & 255) as usize);
+                let (gx, gy, gz) = GRADIENTS_3D[(gi % 24) as usize];
+
+                attn *= attn;
+                value += attn * attn * (gx * dx + gy * dy + gz * dz);
+            }
+        }
+
+        value / NORM_3D
+    }
+}
@@ -5,20 +5,56 @@
 //! Which is based on example code by Ken Perlin at Siggraph 2002.
 //! With optimisations by Stefan Gustavson (stegu@itn.liu.se).
 
-use std::rand::{ Rng, XorShiftRng, weak_rng };
+use core::fmt;
+use alloc::vec::Vec;
+use alloc::string::String;
 
-use utils::{ fade, fast_floor, lerp };
-use utils::grad::{ grad1, grad2, grad3 };
-use gen::NoiseGen;
+use rand::Rng;
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand_xorshift::XorShiftRng;
+
+use crate::utils::{ fade, fast_floor, lerp, perm_at, seeded_rng, validate_perm, FadeKind };
+use crate::utils::grad::{ grad1, grad2, grad3 };
+use crate::gen::{ NoiseGen, Seedable };
+#[cfg(feature = "simd")]
+use crate::gen::lanes::{ self, LANES };
+
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+#[cfg(feature = "serde")]
+use crate::utils::serde_perm;
 
 /// A Perlin noise generator.
-#[derive(Clone, PartialEq, Eq)]
+///
+/// With the `serde` feature enabled, only the 256-byte base of the
+/// permutation table is serialized; the mirrored half is rebuilt on
+/// deserialization.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Perlin {
-    perm: Vec<u8>
+    seed: u64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_perm"))]
+    perm: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    fade: FadeKind,
+    #[cfg_attr(feature = "serde", serde(default))]
+    offset: u8,
+    #[cfg_attr(feature = "serde", serde(default = "unit_frequency"))]
+    frequency: (f64, f64, f64)
+}
+
+/// The frequencies assumed for tables serialized before they were stored.
+#[cfg(feature = "serde")]
+fn unit_frequency() -> (f64, f64, f64) {
+    (1.0, 1.0, 1.0)
 }
 
 impl Perlin {
-    /// Initializes a new Perlin instance with a random seed using XorShiftRng.
+    /// Initializes a new Perlin instance with a random seed drawn from
+    /// `thread_rng`.
+    ///
+    /// Needs the `std` feature, which provides the entropy source.
     ///
     /// # Example
     ///
@@ -27,13 +63,29 @@ impl Perlin {
     ///
     /// let perlin = Perlin::new();
     /// ```
+    #[cfg(feature = "std")]
     pub fn new() -> Perlin {
-        let mut rng: XorShiftRng = weak_rng();
+        Perlin::from_seed(thread_rng().gen())
+    }
 
-        let p: Vec<u8> = (0..256).map(|_| rng.gen::<u8>()).collect();
-        let perm: Vec<u8> = (0..512).map(|idx:i32| {p[(idx & 255) as usize]}).collect();
+    /// Initializes a new Perlin instance deterministically from an integer seed.
+    ///
+    /// Two instances built from the same seed are equal and produce
+    /// bit-identical noise, which makes it easy to save and regenerate a
+    /// procedural world from a single number.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::from_seed(1337);
+    /// assert!(perlin == Perlin::from_seed(1337));
+    /// ```
+    pub fn from_seed(seed: u64) -> Perlin {
+        let mut rng: XorShiftRng = seeded_rng(seed);
 
-        Perlin { perm: perm }
+        Perlin { seed: seed, ..Perlin::from_rng(&mut rng) }
     }
 
     /// Initializes a new Perlin instance with a random number generator.
@@ -41,10 +93,11 @@ impl Perlin {
     /// # Example
     ///
     /// ```rust
-    /// # use std::rand::StdRng;
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
     /// use noisy::gen::Perlin;
     ///
-    /// let mut rng: StdRng = StdRng::new().unwrap();
+    /// let mut rng: StdRng = StdRng::from_entropy();
     /// let perlin = Perlin::from_rng(&mut rng);
     /// ```
     ///
@@ -53,18 +106,496 @@ impl Perlin {
     /// # Example
     ///
     /// ```rust
-    /// # use std::rand::{StdRng, SeedableRng};
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
     /// use noisy::gen::Perlin;
     ///
-    /// let seed: &[_] = &[1337];
-    /// let mut rng: StdRng = SeedableRng::from_seed(seed);
+    /// let mut rng: StdRng = SeedableRng::seed_from_u64(1337);
     /// let perlin = Perlin::from_rng(&mut rng);
     /// ```
-    pub fn from_rng<R: Rng>(rng: &mut R) -> Perlin {
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Perlin {
         let p: Vec<u8> = (0..256).map(|_| rng.gen::<u8>()).collect();
         let perm: Vec<u8> = (0..512).map(|idx:i32| {p[(idx & 255) as usize]}).collect();
 
-        Perlin { perm: perm }
+        Perlin { seed: 0, perm: perm, fade: FadeKind::Quintic, offset: 0, frequency: (1.0, 1.0, 1.0) }
+    }
+
+    /// Initializes a new Perlin instance from a saved permutation table.
+    ///
+    /// The table must have 512 entries whose second half mirrors the first,
+    /// such as one returned by `perm`; an error describing the problem is
+    /// returned otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::new();
+    /// let restored = Perlin::from_perm(perlin.perm().to_vec()).unwrap();
+    /// assert!(perlin == restored);
+    /// ```
+    pub fn from_perm(perm: Vec<u8>) -> Result<Perlin, String> {
+        validate_perm(perm.as_slice())?;
+
+        Ok(Perlin { seed: 0, perm: perm, fade: FadeKind::Quintic, offset: 0, frequency: (1.0, 1.0, 1.0) })
+    }
+
+    /// Sets the curve used to interpolate between lattice points.
+    ///
+    /// Defaults to `FadeKind::Quintic`, Perlin's improved curve.
+    /// `FadeKind::Cubic` is cheaper to evaluate but its second derivative is
+    /// discontinuous at cell borders, which can show up as creases in
+    /// lighting computed from the noise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    /// use noisy::utils::FadeKind;
+    ///
+    /// let perlin = Perlin::new().with_fade(FadeKind::Cubic);
+    /// ```
+    pub fn with_fade(self, fade: FadeKind) -> Perlin {
+        Perlin { fade: fade, ..self }
+    }
+
+    /// Offsets the y and z lattice indices by `offset` before they are hashed.
+    ///
+    /// Since the permutation table repeats every 256 cells, this is the same
+    /// as shifting the field by `offset` cells along y and z. It lets several
+    /// layers share one table, e.g. clones of one generator, without sampling
+    /// identical noise. It does not change the character of the noise, so
+    /// grid-aligned features stay visible. Defaults to 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let base = Perlin::new();
+    /// let detail = base.clone().with_offset(97);
+    /// ```
+    pub fn with_offset(self, offset: u8) -> Perlin {
+        Perlin { offset: offset, ..self }
+    }
+
+    /// Sets the per-axis frequencies applied to the input coordinates.
+    ///
+    /// Each coordinate is multiplied by its frequency before sampling, so
+    /// `(2.0, 1.0, 1.0)` packs twice as much detail along the x axis.
+    /// The default of `(1.0, 1.0, 1.0)` leaves the coordinates untouched.
+    /// `noise2d_split` and `noise2d_tiled` address the lattice directly and
+    /// ignore the frequencies.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// // Thin horizontal strata: eight times the detail along y.
+    /// let perlin = Perlin::new().with_frequencies((1.0, 8.0, 1.0));
+    /// ```
+    pub fn with_frequencies(self, frequency: (f64, f64, f64)) -> Perlin {
+        Perlin { frequency: frequency, ..self }
+    }
+
+    /// Same as `with_frequencies`, taking the x, y and z frequencies as an
+    /// array.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::new().with_frequency([2.0, 1.0, 1.0]);
+    /// ```
+    pub fn with_frequency(self, frequency: [f64; 3]) -> Perlin {
+        self.with_frequencies((frequency[0], frequency[1], frequency[2]))
+    }
+
+    /// Returns the permutation table used to hash the lattice coordinates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::new();
+    /// assert_eq!(perlin.perm().len(), 512);
+    /// ```
+    pub fn perm(&self) -> &[u8] {
+        self.perm.as_slice()
+    }
+
+    /// Given a lattice cell and a fractional offset within it, return a value
+    /// in the interval [-1, 1].
+    ///
+    /// `cell` and `frac` are in lattice units: the per-axis frequencies are
+    /// not applied, so at the default frequencies this samples the same field
+    /// as `noise2d(cell.0 as f64 + frac.0, ...)`. Scale world coordinates by
+    /// the frequencies before splitting them otherwise.
+    ///
+    /// The integer and fractional parts never get combined into a single
+    /// `f64`. Far away from the origin, where an `f64` no longer has enough
+    /// mantissa bits left for the fractional part, the output therefore keeps
+    /// full precision instead of degrading into blocky artifacts. Offsets
+    /// outside of [0, 1) are folded into the cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::new();
+    /// let val = perlin.noise2d_split((1 << 40, -(1 << 40)), (0.25, 0.75));
+    /// ```
+    pub fn noise2d_split(&self, cell: (i64, i64), frac: (f64, f64)) -> f64 {
+        let dx: i64 = fast_floor(frac.0);
+        let dy: i64 = fast_floor(frac.1);
+
+        self.lattice2d(
+            self.perm.as_slice(),
+            cell.0.wrapping_add(dx),
+            cell.1.wrapping_add(dy),
+            frac.0 - dx as f64,
+            frac.1 - dy as f64
+        )
+    }
+
+    /// Fills `out` with 2D noise sampled on a grid, in row-major order.
+    ///
+    /// The grid is `width` samples wide and `out.len() / width` rows tall;
+    /// trailing elements that don't make up a whole row are left untouched.
+    /// The sample at column `x` and row `y` equals
+    /// `noise2d(origin.0 + x * step.0, origin.1 + y * step.1)`, but the
+    /// frequency scaling, the integer and fractional parts of each row and
+    /// the permutation table lookup are hoisted out of the inner loop.
+    ///
+    /// Panics if `width` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::new();
+    /// let mut heights = vec![0.0; 64 * 64];
+    /// perlin.fill2d(heights.as_mut_slice(), 64, (0.0, 0.0), (0.05, 0.05));
+    /// ```
+    pub fn fill2d(&self, out: &mut [f64], width: usize, origin: (f64, f64), step: (f64, f64)) {
+        assert!(width > 0, "width must be at least 1");
+
+        let perm: &[u8] = self.perm.as_slice();
+        let height: usize = out.len() / width;
+        for (y, row) in out.chunks_mut(width).take(height).enumerate() {
+            let yin: f64 = (origin.1 + y as f64 * step.1) * self.frequency.1;
+            let iy0: i64 = fast_floor(yin);
+            let fy0: f64 = yin - iy0 as f64;
+            for (x, sample) in row.iter_mut().enumerate() {
+                let xin: f64 = (origin.0 + x as f64 * step.0) * self.frequency.0;
+                let ix0: i64 = fast_floor(xin);
+                *sample = self.lattice2d(perm, ix0, iy0, xin - ix0 as f64, fy0);
+            }
+        }
+    }
+
+    /// Fills `out` with 3D noise sampled on a `dims.0` by `dims.1` by `dims.2`
+    /// grid, with x varying fastest, then y, then z.
+    ///
+    /// The sample at `(x, y, z)` is stored at
+    /// `(z * dims.1 + y) * dims.0 + x` and equals
+    /// `noise3d(origin[0] + x * step[0], origin[1] + y * step[1], origin[2] + z * step[2])`,
+    /// but the frequency scaling and the integer and fractional parts of each
+    /// slice and row are hoisted out of the inner loop. Trailing elements past the grid are left
+    /// untouched.
+    ///
+    /// Panics if `out` holds fewer than `dims.0 * dims.1 * dims.2` elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::new();
+    /// let mut density = vec![0.0; 32 * 32 * 32];
+    /// perlin.fill3d(density.as_mut_slice(), (32, 32, 32), [0.0, 0.0, 0.0], [0.1, 0.1, 0.1]);
+    /// ```
+    pub fn fill3d(&self, out: &mut [f64], dims: (usize, usize, usize), origin: [f64; 3], step: [f64; 3]) {
+        let (width, height, depth) = dims;
+        assert!(out.len() >= width * height * depth,
+                "buffer too small for a {}x{}x{} volume", width, height, depth);
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let perm: &[u8] = self.perm.as_slice();
+        let slices = out.chunks_mut(width * height).take(depth);
+        for (z, slice) in slices.enumerate() {
+            let zin: f64 = (origin[2] + z as f64 * step[2]) * self.frequency.2;
+            let iz0: i64 = fast_floor(zin);
+            let fz0: f64 = zin - iz0 as f64;
+            for (y, row) in slice.chunks_mut(width).enumerate() {
+                let yin: f64 = (origin[1] + y as f64 * step[1]) * self.frequency.1;
+                let iy0: i64 = fast_floor(yin);
+                let fy0: f64 = yin - iy0 as f64;
+                for (x, sample) in row.iter_mut().enumerate() {
+                    let xin: f64 = (origin[0] + x as f64 * step[0]) * self.frequency.0;
+                    let ix0: i64 = fast_floor(xin);
+                    *sample = self.lattice3d(perm, (ix0, iy0, iz0), (xin - ix0 as f64, fy0, fz0));
+                }
+            }
+        }
+    }
+
+    /// Fills `buf` with a `width` by `height` grid of 2D noise values in
+    /// row-major order, narrowed to `f32`, evaluating four samples at once.
+    ///
+    /// The samples equal those of `fill_grid2d_f32`. The lattice cell, fade
+    /// curves, gradient dot products and interpolation of each group of four
+    /// samples run as loops over the lanes without branches, which the
+    /// optimizer can turn into vector instructions; the lattice row is shared
+    /// by the whole group. Only the permutation table lookups are done lane
+    /// by lane. Needs the `simd` feature.
+    ///
+    /// Panics if `buf` holds fewer than `width * height` values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::new();
+    /// let mut texture = vec![0.0f32; 64 * 64];
+    /// perlin.fill_grid2d_f32_simd(texture.as_mut_slice(), 64, 64, (0.0, 0.0), (0.02, 0.02));
+    /// ```
+    #[cfg(feature = "simd")]
+    pub fn fill_grid2d_f32_simd(&self, buf: &mut [f32], width: usize, height: usize, origin: (f64, f64), step: (f64, f64)) {
+        lanes::fill_grid2d_f32(buf, width, height, origin, step, |xs, yin| self.lanes2d(xs, yin), |xin, yin| self.noise2d(xin, yin));
+    }
+
+    /// Computes `noise2d` at four x coordinates sharing one y coordinate.
+    #[cfg(feature = "simd")]
+    #[inline]
+    fn lanes2d(&self, xin: [f64; LANES], yin: f64) -> [f64; LANES] {
+        let perm: &[u8] = self.perm.as_slice();
+
+        // The row is shared by every lane.
+        let yin: f64 = yin * self.frequency.1;
+        let iy0: i64 = fast_floor(yin);
+        let fy0: f64 = yin - iy0 as f64;
+        let fy1: f64 = fy0 - 1.0;
+        let jj: usize = (iy0.wrapping_add(self.offset as i64) & 255) as usize;
+        let iy1: usize = (jj + 1) & 255;
+        let t: f64 = fade(self.fade, fy0);
+
+        // Split each lane into its cell and the offset within it.
+        let mut ii: [usize; LANES] = [0; LANES];
+        let mut fx0: [f64; LANES] = [0.0; LANES];
+        for l in 0..LANES {
+            let xin: f64 = xin[l] * self.frequency.0;
+            let ix0: i64 = fast_floor(xin);
+            ii[l] = (ix0 & 255) as usize;
+            fx0[l] = xin - ix0 as f64;
+        }
+
+        // Hash the four corners of each lane.
+        let mut gi: [[u8; LANES]; 4] = [[0; LANES]; 4];
+        let (row0, row1): (usize, usize) = (perm_at(perm, jj) as usize, perm_at(perm, iy1) as usize);
+        for l in 0..LANES {
+            let ix1: usize = (ii[l] + 1) & 255;
+            gi[0][l] = perm_at(perm, ii[l] + row0);
+            gi[1][l] = perm_at(perm, ii[l] + row1);
+            gi[2][l] = perm_at(perm, ix1 + row0);
+            gi[3][l] = perm_at(perm, ix1 + row1);
+        }
+
+        let mut out: [f64; LANES] = [0.0; LANES];
+        for l in 0..LANES {
+            let fx1: f64 = fx0[l] - 1.0;
+            let s: f64 = fade(self.fade, fx0[l]);
+
+            let n0: f64 = lerp(t, lanes::grad2(gi[0][l], fx0[l], fy0), lanes::grad2(gi[1][l], fx0[l], fy1));
+            let n1: f64 = lerp(t, lanes::grad2(gi[2][l], fx1, fy0), lanes::grad2(gi[3][l], fx1, fy1));
+
+            // The result is scaled to return values in the interval [-1, 1].
+            out[l] = 0.507 * lerp(s, n0, n1);
+        }
+
+        out
+    }
+
+    /// Given a (x, y) coordinate, return a value in the interval [-1, 1]
+    /// from a field that repeats every `period_x` units along x and every
+    /// `period_y` units along y.
+    ///
+    /// The lattice indices are wrapped modulo the period before they are
+    /// hashed, which tiles far more cheaply than `Tileable2d`. Any positive
+    /// period works, not only powers of two. Panics if a period is not
+    /// positive.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Perlin;
+    ///
+    /// let perlin = Perlin::new();
+    /// let val = perlin.noise2d_tiled(1.5, 2.5, 16, 16);
+    /// assert_eq!(val, perlin.noise2d_tiled(17.5, -13.5, 16, 16));
+    /// ```
+    pub fn noise2d_tiled(&self, xin: f64, yin: f64, period_x: i64, period_y: i64) -> f64 {
+        assert!(period_x > 0 && period_y > 0, "tile periods must be positive");
+
+        let ix0: i64 = fast_floor(xin); // Integer part of x
+        let iy0: i64 = fast_floor(yin); // Integer part of y
+        let fx0: f64 = xin - ix0 as f64; // Fractional part of x
+        let fy0: f64 = yin - iy0 as f64; // Fractional part of y
+
+        // Wrap the integer indices at the period, then at 256, to avoid
+        // indexing perm[] out of bounds
+        let wrap = |i: i64, period: i64| ((i % period) + period) % period;
+        let offset: i64 = self.offset as i64;
+        let ii: usize = (wrap(ix0, period_x) & 255) as usize;
+        let ix1: usize = (wrap(ix0.wrapping_add(1), period_x) & 255) as usize;
+        let jj: usize = ((wrap(iy0, period_y) + offset) & 255) as usize;
+        let iy1: usize = ((wrap(iy0.wrapping_add(1), period_y) + offset) & 255) as usize;
+
+        self.corners2d(self.perm.as_slice(), ii, ix1, jj, iy1, fx0, fy0)
+    }
+
+    /// Computes 2D noise from the integer cell and the offset within it.
+    #[inline]
+    fn lattice2d(&self, perm: &[u8], ix0: i64, iy0: i64, fx0: f64, fy0: f64) -> f64 {
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (ix0 & 255) as usize;
+        let jj: usize = (iy0.wrapping_add(self.offset as i64) & 255) as usize;
+        let ix1: usize = (ii + 1) & 255;
+        let iy1: usize = (jj + 1) & 255;
+
+        self.corners2d(perm, ii, ix1, jj, iy1, fx0, fy0)
+    }
+
+    /// Computes 3D noise from the integer and fractional parts of a
+    /// coordinate.
+    #[inline]
+    fn lattice3d(&self, perm: &[u8], cell: (i64, i64, i64), frac: (f64, f64, f64)) -> f64 {
+        let (ix0, iy0, iz0) = cell;
+        let (fx0, fy0, fz0) = frac;
+        let fx1: f64 = fx0 - 1.0;
+        let fy1: f64 = fy0 - 1.0;
+        let fz1: f64 = fz0 - 1.0;
+
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (ix0 & 255) as usize;
+        let jj: usize = (iy0.wrapping_add(self.offset as i64) & 255) as usize;
+        let kk: usize = (iz0.wrapping_add(self.offset as i64) & 255) as usize;
+        let ix1: usize = (ii + 1) & 255;
+        let iy1: usize = (jj + 1) & 255;
+        let iz1: usize = (kk + 1) & 255;
+
+        // Compute the fade curves.
+        let r: f64 = fade(self.fade, fz0);
+        let t: f64 = fade(self.fade, fy0);
+        let s: f64 = fade(self.fade, fx0);
+
+        // Work out the hashed gradient indices.
+        let gi0: u8 = perm_at(perm, ii + (perm_at(perm, jj + (perm_at(perm, kk) as usize)) as usize));
+        let gi1: u8 = perm_at(perm, ii + (perm_at(perm, jj + (perm_at(perm, iz1) as usize)) as usize));
+        let gi2: u8 = perm_at(perm, ii + (perm_at(perm, iy1 + (perm_at(perm, kk) as usize)) as usize));
+        let gi3: u8 = perm_at(perm, ii + (perm_at(perm, iy1 + (perm_at(perm, iz1) as usize)) as usize));
+        let gi4: u8 = perm_at(perm, ix1 + (perm_at(perm, jj + (perm_at(perm, kk) as usize)) as usize));
+        let gi5: u8 = perm_at(perm, ix1 + (perm_at(perm, jj + (perm_at(perm, iz1) as usize)) as usize));
+        let gi6: u8 = perm_at(perm, ix1 + (perm_at(perm, iy1 + (perm_at(perm, kk) as usize)) as usize));
+        let gi7: u8 = perm_at(perm, ix1 + (perm_at(perm, iy1 + (perm_at(perm, iz1) as usize)) as usize));
+
+        // Calculate the gradients.
+        let nxy0: f64 = grad3(gi0, fx0, fy0, fz0);
+        let nxy1: f64 = grad3(gi1, fx0, fy0, fz1);
+        let nxy2: f64 = grad3(gi2, fx0, fy1, fz0);
+        let nxy3: f64 = grad3(gi3, fx0, fy1, fz1);
+        let nxy4: f64 = grad3(gi4, fx1, fy0, fz0);
+        let nxy5: f64 = grad3(gi5, fx1, fy0, fz1);
+        let nxy6: f64 = grad3(gi6, fx1, fy1, fz0);
+        let nxy7: f64 = grad3(gi7, fx1, fy1, fz1);
+
+        let nx0: f64 = lerp(r, nxy0, nxy1);
+        let nx1: f64 = lerp(r, nxy2, nxy3);
+        let nx2: f64 = lerp(r, nxy4, nxy5);
+        let nx3: f64 = lerp(r, nxy6, nxy7);
+
+        let n0: f64 = lerp(t, nx0, nx1);
+        let n1: f64 = lerp(t, nx2, nx3);
+
+        // The result is scaled to return values in the interval [-1, 1].
+        0.936 * lerp(s, n0, n1)
+    }
+
+    /// Computes 2D noise from the wrapped x and y indices of the cell's
+    /// corners and the offset within it.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn corners2d(&self, perm: &[u8], ii: usize, ix1: usize, jj: usize, iy1: usize, fx0: f64, fy0: f64) -> f64 {
+        let fx1: f64 = fx0 - 1.0;
+        let fy1: f64 = fy0 - 1.0;
+
+        // Compute the fade curves.
+        let t: f64 = fade(self.fade, fy0);
+        let s: f64 = fade(self.fade, fx0);
+
+        // Work out the hashed gradient indices.
+        let gi0: u8 = perm_at(perm, ii + (perm_at(perm, jj) as usize));
+        let gi1: u8 = perm_at(perm, ii + (perm_at(perm, iy1) as usize));
+        let gi2: u8 = perm_at(perm, ix1 + (perm_at(perm, jj) as usize));
+        let gi3: u8 = perm_at(perm, ix1 + (perm_at(perm, iy1) as usize));
+
+        // Calculate the gradients.
+        let nx0: f64 = grad2(gi0, fx0, fy0);
+        let nx1: f64 = grad2(gi1, fx0, fy1);
+        let nx2: f64 = grad2(gi2, fx1, fy0);
+        let nx3: f64 = grad2(gi3, fx1, fy1);
+
+        let n0: f64 = lerp(t, nx0, nx1);
+        let n1: f64 = lerp(t, nx2, nx3);
+
+        // The result is scaled to return values in the interval [-1, 1].
+        0.507 * lerp(s, n0, n1)
+    }
+}
+
+/// Two generators are equal when they produce the same noise; the recorded
+/// seed is not compared.
+impl PartialEq for Perlin {
+    fn eq(&self, other: &Perlin) -> bool {
+        self.perm == other.perm
+            && self.fade == other.fade
+            && self.offset == other.offset
+            && self.frequency == other.frequency
+    }
+}
+
+/// Prints the seed and settings; the permutation table is summarized by its
+/// length.
+impl fmt::Debug for Perlin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Perlin")
+            .field("seed", &self.seed)
+            .field("perm_len", &self.perm.len())
+            .field("fade", &self.fade)
+            .field("offset", &self.offset)
+            .field("frequency", &self.frequency)
+            .finish()
+    }
+}
+
+impl Seedable for Perlin {
+    /// Rebuilds the permutation table from `seed`, as `from_seed` would.
+    fn set_seed(&mut self, seed: u64) {
+        self.perm = Perlin::from_seed(seed).perm;
+        self.seed = seed;
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
     }
 }
 
@@ -80,21 +611,24 @@ impl NoiseGen for Perlin {
     /// let val = perlin.noise1d(123.0 * 0.04);
     /// ```
     fn noise1d(&self, xin: f64) -> f64 {
+        let xin: f64 = xin * self.frequency.0;
         let ix0: i64 = fast_floor(xin); // Integer part of x
         let fx0: f64 = xin - ix0 as f64; // Fractional part of x
         let fx1: f64 = fx0 - 1.0;
-        let ix1: i64 = ix0 + 1;
+        let ix1: i64 = ix0.wrapping_add(1);
+
+        let perm: &[u8] = self.perm.as_slice();
 
         // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
         let ii: usize = (ix0 & 255) as usize;
         let jj: usize = (ix1 & 255) as usize;
 
         // Compute the fade curve.
-        let s: f64 = fade(fx0);
+        let s: f64 = fade(self.fade, fx0);
 
         // Work out the hashed gradient indices.
-        let gi0: u8 = self.perm[ii] as u8;
-        let gi1: u8 = self.perm[jj] as u8;
+        let gi0: u8 = perm_at(perm, ii);
+        let gi1: u8 = perm_at(perm, jj);
 
         // Calculate the gradients.
         let nx0 = grad1(gi0, fx0);
@@ -118,40 +652,14 @@ impl NoiseGen for Perlin {
     /// );
     /// ```
     fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let xin: f64 = xin * self.frequency.0;
+        let yin: f64 = yin * self.frequency.1;
         let ix0: i64 = fast_floor(xin); // Integer part of x
         let iy0: i64 = fast_floor(yin); // Integer part of y
         let fx0: f64 = xin - ix0 as f64; // Fractional part of x
         let fy0: f64 = yin - iy0 as f64; // Fractional part of y
-        let fx1: f64 = fx0 - 1.0;
-        let fy1: f64 = fy0 - 1.0;
 
-        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
-        let ix1: usize = ((ix0 + 1) & 255) as usize;
-        let iy1: usize = ((iy0 + 1) & 255) as usize;
-        let ii: usize = (ix0 & 255) as usize;
-        let jj: usize = (iy0 & 255) as usize;
-
-        // Compute the fade curves.
-        let t: f64 = fade(fy0);
-        let s: f64 = fade(fx0);
-
-        // Work out the hashed gradient indices.
-        let gi0: u8 = self.perm[ii + (self.perm[jj] as usize)] as u8;
-        let gi1: u8 = self.perm[ii + (self.perm[iy1] as usize)] as u8;
-        let gi2: u8 = self.perm[ix1 + (self.perm[jj] as usize)] as u8;
-        let gi3: u8 = self.perm[ix1 + (self.perm[iy1] as usize)] as u8;
-
-        // Calculate the gradients.
-        let nx0: f64 = grad2(gi0, fx0, fy0);
-        let nx1: f64 = grad2(gi1, fx0, fy1);
-        let nx2: f64 = grad2(gi2, fx1, fy0);
-        let nx3: f64 = grad2(gi3, fx1, fy1);
-
-        let n0: f64 = lerp(t, nx0, nx1);
-        let n1: f64 = lerp(t, nx2, nx3);
-
-        // The result is scaled to return values in the interval [-1, 1].
-        0.507 * lerp(s, n0, n1)
+        self.lattice2d(self.perm.as_slice(), ix0, iy0, fx0, fy0)
     }
 
     /// Given a (x, y, z) coordinate, return a value in the interval [-1, 1].
@@ -169,58 +677,16 @@ impl NoiseGen for Perlin {
     /// );
     /// ```
     fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let xin: f64 = xin * self.frequency.0;
+        let yin: f64 = yin * self.frequency.1;
+        let zin: f64 = zin * self.frequency.2;
         let ix0: i64 = fast_floor(xin); // Integer part of x
         let iy0: i64 = fast_floor(yin); // Integer part of y
         let iz0: i64 = fast_floor(zin); // Integer part of z
         let fx0: f64 = xin - ix0 as f64; // Fractional part of x
         let fy0: f64 = yin - iy0 as f64; // Fractional part of y
         let fz0: f64 = zin - iz0 as f64; // Fractional part of z
-        let fx1: f64 = fx0 - 1.0;
-        let fy1: f64 = fy0 - 1.0;
-        let fz1: f64 = fz0 - 1.0;
-
-        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
-        let ix1: usize = ((ix0 + 1) & 255) as usize;
-        let iy1: usize = ((iy0 + 1) & 255) as usize;
-        let iz1: usize = ((iz0 + 1) & 255) as usize;
-        let ii: usize = (ix0 & 255) as usize;
-        let jj: usize = (iy0 & 255) as usize;
-        let kk: usize = (iz0 & 255) as usize;
-
-        // Compute the fade curves.
-        let r: f64 = fade(fz0);
-        let t: f64 = fade(fy0);
-        let s: f64 = fade(fx0);
-
-        // Work out the hashed gradient indices.
-        let gi0: u8 = self.perm[ii + (self.perm[jj + (self.perm[kk] as usize)] as usize)] as u8;
-        let gi1: u8 = self.perm[ii + (self.perm[jj + (self.perm[iz1] as usize)] as usize)] as u8;
-        let gi2: u8 = self.perm[ii + (self.perm[iy1 + (self.perm[kk] as usize)] as usize)] as u8;
-        let gi3: u8 = self.perm[ii + (self.perm[iy1 + (self.perm[iz1] as usize)] as usize)] as u8;
-        let gi4: u8 = self.perm[ix1 + (self.perm[jj + (self.perm[kk] as usize)] as usize)] as u8;
-        let gi5: u8 = self.perm[ix1 + (self.perm[jj + (self.perm[iz1] as usize)] as usize)] as u8;
-        let gi6: u8 = self.perm[ix1 + (self.perm[iy1 + (self.perm[kk] as usize)] as usize)] as u8;
-        let gi7: u8 = self.perm[ix1 + (self.perm[iy1 + (self.perm[iz1] as usize)] as usize)] as u8;
-
-        // Calculate the gradients.
-        let nxy0: f64 = grad3(gi0, fx0, fy0, fz0);
-        let nxy1: f64 = grad3(gi1, fx0, fy0, fz1);
-        let nxy2: f64 = grad3(gi2, fx0, fy1, fz0);
-        let nxy3: f64 = grad3(gi3, fx0, fy1, fz1);
-        let nxy4: f64 = grad3(gi4, fx1, fy0, fz0);
-        let nxy5: f64 = grad3(gi5, fx1, fy0, fz1);
-        let nxy6: f64 = grad3(gi6, fx1, fy1, fz0);
-        let nxy7: f64 = grad3(gi7, fx1, fy1, fz1);
 
-        let nx0: f64 = lerp(r, nxy0, nxy1);
-        let nx1: f64 = lerp(r, nxy2, nxy3);
-        let nx2: f64 = lerp(r, nxy4, nxy5);
-        let nx3: f64 = lerp(r, nxy6, nxy7);
-
-        let n0: f64 = lerp(t, nx0, nx1);
-        let n1: f64 = lerp(t, nx2, nx3);
-
-        // The result is scaled to return values in the interval [-1, 1].
-        0.936 * lerp(s, n0, n1)
+        self.lattice3d(self.perm.as_slice(), (ix0, iy0, iz0), (fx0, fy0, fz0))
     }
 }
@@ -0,0 +1,130 @@
+//! Ridged multifractal noise, as described by F. Kenton Musgrave.
+
+use crate::gen::NoiseGen;
+
+/// A generator summing ridged octaves of its source, each weighted by the
+/// octave before it.
+///
+/// Every octave turns the source into a ridge with
+/// `signal = (offset - |noise|)^2`, so the zero crossings of the source become
+/// sharp crests. Each signal also scales the next octave through
+/// `gain`, which keeps the valleys smooth while the detail piles up along the
+/// ridgelines. Octave `i` is weighted by `lacunarity^-i`, and the sum is
+/// normalized so the output stays within [-1, 1].
+#[derive(Clone, PartialEq, Debug)]
+pub struct RidgedMulti<G> {
+    source: G,
+    octaves: u32,
+    frequency: f64,
+    lacunarity: f64,
+    gain: f64,
+    offset: f64
+}
+
+impl<G: NoiseGen> RidgedMulti<G> {
+    /// Stacks `octaves` ridged octaves of `source`, with a frequency of 1.0,
+    /// a lacunarity of 2.0, a gain of 2.0 and an offset of 1.0.
+    ///
+    /// Panics if `octaves` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, RidgedMulti, Simplex};
+    ///
+    /// let mountains = RidgedMulti::new(Simplex::new(), 6);
+    /// let val = mountains.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, octaves: u32) -> RidgedMulti<G> {
+        assert!(octaves > 0, "ridged multifractal needs at least one octave");
+
+        RidgedMulti {
+            source: source,
+            octaves: octaves,
+            frequency: 1.0,
+            lacunarity: 2.0,
+            gain: 2.0,
+            offset: 1.0
+        }
+    }
+
+    /// Sets the frequency of the first octave.
+    pub fn with_frequency(self, frequency: f64) -> RidgedMulti<G> {
+        RidgedMulti { frequency: frequency, ..self }
+    }
+
+    /// Sets the frequency multiplier between successive octaves.
+    pub fn with_lacunarity(self, lacunarity: f64) -> RidgedMulti<G> {
+        RidgedMulti { lacunarity: lacunarity, ..self }
+    }
+
+    /// Sets how strongly each octave's signal weighs the next octave.
+    ///
+    /// Higher gains concentrate the detail along the ridgelines.
+    pub fn with_gain(self, gain: f64) -> RidgedMulti<G> {
+        RidgedMulti { gain: gain, ..self }
+    }
+
+    /// Sets the offset the absolute noise value is subtracted from.
+    ///
+    /// Values above 1.0 raise and widen the ridges.
+    ///
+    /// Panics if `offset` is not positive.
+    pub fn with_offset(self, offset: f64) -> RidgedMulti<G> {
+        assert!(offset > 0.0, "ridged multifractal offset must be positive, got {}", offset);
+
+        RidgedMulti { offset: offset, ..self }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Sums the ridged octaves of `sample(frequency)` and normalizes by the
+    /// largest possible sum.
+    #[inline]
+    fn accumulate<F: Fn(f64) -> f64>(&self, sample: F) -> f64 {
+        let mut sum: f64 = 0.0;
+        let mut total: f64 = 0.0;
+        let mut frequency: f64 = self.frequency;
+        let mut amplitude: f64 = 1.0;
+        let mut weight: f64 = 1.0;
+
+        for _ in 0..self.octaves {
+            let mut signal: f64 = self.offset - sample(frequency).abs();
+            signal = signal * signal * weight;
+            weight = (signal * self.gain).clamp(0.0, 1.0);
+
+            sum += amplitude * signal;
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude /= self.lacunarity;
+        }
+
+        // With the source in [-1, 1], a signal peaks at `offset^2` on a
+        // crest, or at `(1 - offset)^2` where the source reaches an extreme
+        // and the offset is below 0.5.
+        let peak: f64 = (self.offset * self.offset).max((1.0 - self.offset) * (1.0 - self.offset));
+
+        2.0 * sum / (total * peak) - 1.0
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for RidgedMulti<G> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise1d(xin * f))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise2d(xin * f, yin * f))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.accumulate(|f| self.source.noise3d(xin * f, yin * f, zin * f))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.accumulate(|f| self.source.noise4d(xin * f, yin * f, zin * f, win * f))
+    }
+}
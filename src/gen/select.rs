@@ -0,0 +1,143 @@
+//! Chooses between two generators based on a control generator.
+
+use crate::utils::{ fade, lerp, FadeKind };
+use crate::gen::NoiseGen;
+
+/// A generator returning the output of `second` wherever the control value
+/// lies within `[lower_bound, upper_bound]`, and the output of `first`
+/// everywhere else.
+///
+/// With a non-zero `edge_falloff` the two sources are blended smoothly over
+/// a band of that half-width around each bound, which is how biomes fade
+/// into each other instead of meeting at a hard seam. The falloff is
+/// limited to half of the selection range.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Select<A, B, C> {
+    first: A,
+    second: B,
+    control: C,
+    /// The lower bound of the control range selecting `second`.
+    pub lower_bound: f64,
+    /// The upper bound of the control range selecting `second`.
+    pub upper_bound: f64,
+    /// The half-width of the blending band around each bound.
+    pub edge_falloff: f64
+}
+
+impl<A: NoiseGen, B: NoiseGen, C: NoiseGen> Select<A, B, C> {
+    /// Wraps `first` and `second`, picking `second` wherever `control` lies
+    /// within `[lower_bound, upper_bound]`. The transition is hard until an
+    /// edge falloff is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Perlin, Select, Simplex, Worley};
+    ///
+    /// let biomes = Select::new(Perlin::new(), Worley::new(), Simplex::new(), 0.0, 1.0)
+    ///     .with_edge_falloff(0.1);
+    /// let val = biomes.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(first: A, second: B, control: C, lower_bound: f64, upper_bound: f64) -> Select<A, B, C> {
+        Select {
+            first: first,
+            second: second,
+            control: control,
+            lower_bound: lower_bound,
+            upper_bound: upper_bound,
+            edge_falloff: 0.0
+        }
+    }
+
+    /// Sets the half-width of the band over which the sources are blended.
+    pub fn with_edge_falloff(self, edge_falloff: f64) -> Select<A, B, C> {
+        Select { edge_falloff: edge_falloff, ..self }
+    }
+
+    /// Returns the generator used outside of the selection range.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the generator used within the selection range.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Returns the control generator.
+    pub fn control(&self) -> &C {
+        &self.control
+    }
+
+    /// Combines the sources for a control value, only evaluating the sources
+    /// that contribute.
+    fn select<FA, FB>(&self, control: f64, first: FA, second: FB) -> f64
+        where FA: Fn() -> f64, FB: Fn() -> f64
+    {
+        let falloff: f64 = self.edge_falloff
+            .min(0.5 * (self.upper_bound - self.lower_bound))
+            .max(0.0);
+
+        if falloff > 0.0 {
+            let lower: f64 = self.lower_bound;
+            let upper: f64 = self.upper_bound;
+
+            if control < lower - falloff || control > upper + falloff {
+                first()
+            } else if control < lower + falloff {
+                let alpha: f64 = fade(FadeKind::Quintic, (control - (lower - falloff)) / (2.0 * falloff));
+                lerp(alpha, first(), second())
+            } else if control <= upper - falloff {
+                second()
+            } else {
+                let alpha: f64 = fade(FadeKind::Quintic, (control - (upper - falloff)) / (2.0 * falloff));
+                lerp(alpha, second(), first())
+            }
+        } else if control < self.lower_bound || control > self.upper_bound {
+            first()
+        } else {
+            second()
+        }
+    }
+}
+
+impl<A: NoiseGen, B: NoiseGen, C: NoiseGen> NoiseGen for Select<A, B, C> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.select(
+            self.control.noise1d(xin),
+            || self.first.noise1d(xin),
+            || self.second.noise1d(xin)
+        )
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.select(
+            self.control.noise2d(xin, yin),
+            || self.first.noise2d(xin, yin),
+            || self.second.noise2d(xin, yin)
+        )
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.select(
+            self.control.noise3d(xin, yin, zin),
+            || self.first.noise3d(xin, yin, zin),
+            || self.second.noise3d(xin, yin, zin)
+        )
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.select(
+            self.control.noise4d(xin, yin, zin, win),
+            || self.first.noise4d(xin, yin, zin, win),
+            || self.second.noise4d(xin, yin, zin, win)
+        )
+    }
+
+    /// The output is either source or a blend of both.
+    fn bounds(&self) -> (f64, f64) {
+        let (first, second) = (self.first.bounds(), self.second.bounds());
+
+        (first.0.min(second.0), first.1.max(second.1))
+    }
+}
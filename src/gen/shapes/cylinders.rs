@@ -0,0 +1,60 @@
+//! Concentric cylinders around the y-axis.
+
+use core::f64::consts::PI;
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// A generator outputting concentric cylinders centered on the y-axis.
+///
+/// The output is `cos(2 * pi * frequency * r)`, where `r` is the distance
+/// of the coordinate from the y-axis, so a new cylinder wall (a value of 1)
+/// appears every `1 / frequency` units. The output does not depend on y.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Cylinders {
+    /// The number of cylinders per unit of radius.
+    pub frequency: f64
+}
+
+impl Cylinders {
+    /// Initializes a new Cylinders instance with `frequency` cylinders per
+    /// unit of radius.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::NoiseGen;
+    /// use noisy::gen::shapes::Cylinders;
+    ///
+    /// let cylinders = Cylinders::new(2.0);
+    /// let val = cylinders.noise3d(1.0, 2.0, 3.0);
+    /// ```
+    pub fn new(frequency: f64) -> Cylinders {
+        Cylinders { frequency: frequency }
+    }
+
+    #[inline]
+    fn ring(&self, radius: f64) -> f64 {
+        (2.0 * PI * self.frequency * radius).cos()
+    }
+}
+
+impl NoiseGen for Cylinders {
+    /// Given an x coordinate, return a value in the interval [-1, 1].
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.ring(xin.abs())
+    }
+
+    /// Given a (x, y) coordinate, return a value in the interval [-1, 1].
+    ///
+    /// The plane cuts the cylinders along their axis, producing stripes.
+    fn noise2d(&self, xin: f64, _yin: f64) -> f64 {
+        self.ring(xin.abs())
+    }
+
+    /// Given a (x, y, z) coordinate, return a value in the interval [-1, 1].
+    fn noise3d(&self, xin: f64, _yin: f64, zin: f64) -> f64 {
+        self.ring((xin * xin + zin * zin).sqrt())
+    }
+}
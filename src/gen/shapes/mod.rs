@@ -0,0 +1,8 @@
+//! Simple geometric pattern generators, mostly useful as predictable inputs
+//! to modifiers and for ring textures.
+
+pub use crate::gen::shapes::cylinders::Cylinders;
+pub use crate::gen::shapes::spheres::Spheres;
+
+mod cylinders;
+mod spheres;
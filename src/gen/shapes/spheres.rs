@@ -0,0 +1,63 @@
+//! Concentric spheres around the origin.
+
+use core::f64::consts::PI;
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// A generator outputting concentric spheres centered on the origin.
+///
+/// The output is `cos(2 * pi * frequency * r)`, where `r` is the distance
+/// of the coordinate from the origin, so a new sphere shell (a value of 1)
+/// appears every `1 / frequency` units.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Spheres {
+    /// The number of spheres per unit of radius.
+    pub frequency: f64
+}
+
+impl Spheres {
+    /// Initializes a new Spheres instance with `frequency` spheres per unit
+    /// of radius.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::NoiseGen;
+    /// use noisy::gen::shapes::Spheres;
+    ///
+    /// let spheres = Spheres::new(2.0);
+    /// let val = spheres.noise3d(1.0, 2.0, 3.0);
+    /// ```
+    pub fn new(frequency: f64) -> Spheres {
+        Spheres { frequency: frequency }
+    }
+
+    #[inline]
+    fn ring(&self, radius: f64) -> f64 {
+        (2.0 * PI * self.frequency * radius).cos()
+    }
+}
+
+impl NoiseGen for Spheres {
+    /// Given an x coordinate, return a value in the interval [-1, 1].
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.ring(xin.abs())
+    }
+
+    /// Given a (x, y) coordinate, return a value in the interval [-1, 1].
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.ring((xin * xin + yin * yin).sqrt())
+    }
+
+    /// Given a (x, y, z) coordinate, return a value in the interval [-1, 1].
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.ring((xin * xin + yin * yin + zin * zin).sqrt())
+    }
+
+    /// Given a (x, y, z, w) coordinate, return a value in the interval [-1, 1].
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        self.ring((xin * xin + yin * yin + zin * zin + win * win).sqrt())
+    }
+}
@@ -6,25 +6,1018 @@
 //! With Optimisations by Peter Eastman (peastman@drizzle.stanford.edu).
 //! Better rank ordering method by Stefan Gustavson in 2012.
 
-use std::rand::{ Rng, XorShiftRng, weak_rng };
+use core::fmt;
+use core::f64::consts::PI;
+use alloc::vec::Vec;
+use alloc::string::String;
 
-use utils::fast_floor;
-use utils::grad::{ grad1, grad2, grad3 };
-use gen::NoiseGen;
+use rand::Rng;
+#[cfg(feature = "std")]
+use rand::thread_rng;
+
+use crate::utils::{ fast_floor, perm_at, portable_perm, validate_perm };
+#[cfg(feature = "simd")]
+use crate::utils::if_else;
+use crate::utils::grad::{ grad1, grad2, grad2_vec, grad3, grad3_vec, grad4 };
+use crate::gen::{ NoiseGen, Seedable, Fbm, Tileable2d };
+#[cfg(feature = "simd")]
+use crate::gen::lanes::{ self, LANES };
+
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+#[cfg(feature = "serde")]
+use serde::{ Deserializer, de::Error };
+#[cfg(feature = "serde")]
+use crate::utils::serde_perm;
+#[cfg(feature = "serde")]
+use alloc::format;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
 
 static F2: f64 = 0.366025403784_f64;
 static G2: f64 = 0.211324865405_f64;
 static F3: f64 = 0.333333333333_f64;
 static G3: f64 = 0.166666666667_f64;
+static F4: f64 = 0.309016994375_f64;
+static G4: f64 = 0.138196601125_f64;
+
+// The smallest magnitude the contrast derivatives are evaluated at. The
+// slope of `|v|^a` is infinite at 0 for `a < 1`, its curvature for `a < 2`.
+static CONTRAST_FLOOR: f64 = 1e-6;
+
+// The seed used by `Simplex::new_default`.
+static DEFAULT_SEED: u64 = 0x6e6f697379;
 
 /// A simplex noise generator.
-#[derive(Clone, PartialEq, Eq)]
+///
+/// With the `serde` feature enabled, a generator can be serialized along
+/// with its settings. Only the 256-byte base of the permutation table is
+/// stored; the mirrored half is rebuilt on deserialization. Settings that
+/// would make sampling panic, such as a truncated gradient map, are rejected
+/// with an error.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Simplex {
-    perm: Vec<u8>
+    seed: u64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_perm"))]
+    perm: Vec<u8>,
+    frequency: (f64, f64, f64),
+    contrast: f64,
+    #[cfg_attr(feature = "serde", serde(default, deserialize_with = "deserialize_grad_map"))]
+    grad_map: Option<Vec<u8>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    channel: u8,
+    #[cfg_attr(feature = "serde", serde(default, deserialize_with = "deserialize_gradients"))]
+    gradients: Option<Vec<[f64; 3]>>
+}
+
+/// Deserializes a gradient map, which must cover all 256 hashes.
+#[cfg(feature = "serde")]
+fn deserialize_grad_map<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error> {
+    let map: Option<Vec<u8>> = Deserialize::deserialize(deserializer)?;
+    match map {
+        Some(ref map) if map.len() != 256 => {
+            Err(D::Error::custom(format!("gradient map has {} entries, expected 256", map.len())))
+        }
+        _ => Ok(map)
+    }
+}
+
+/// Deserializes a gradient set, which must hold at least one gradient and
+/// only finite components, as `with_gradients` requires.
+#[cfg(feature = "serde")]
+fn deserialize_gradients<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<[f64; 3]>>, D::Error> {
+    let grads: Option<Vec<[f64; 3]>> = Deserialize::deserialize(deserializer)?;
+    if let Some(ref grads) = grads {
+        if grads.is_empty() {
+            return Err(D::Error::custom("gradient set must not be empty"));
+        }
+        if !grads.iter().flatten().all(|c| c.is_finite()) {
+            return Err(D::Error::custom("gradient set must only hold finite components"));
+        }
+    }
+
+    Ok(grads)
 }
 
 impl Simplex {
-    /// Initializes a new simplex instance with a random seed using XorShiftRng.
+    /// Initializes a new simplex instance with a random seed drawn from
+    /// `thread_rng`.
+    ///
+    /// Needs the `std` feature, which provides the entropy source.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new() -> Simplex {
+        Simplex::from_seed(thread_rng().gen())
+    }
+
+    /// Initializes a new simplex instance from a fixed, built-in seed.
+    ///
+    /// Every call returns the same generator, on every run and every
+    /// platform. Use it for examples, tests and anything else that should be
+    /// reproducible without picking a seed; use `new` when each run should
+    /// look different.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new_default();
+    /// assert!(simplex == Simplex::new_default());
+    /// ```
+    pub fn new_default() -> Simplex {
+        Simplex::from_seed(DEFAULT_SEED)
+    }
+
+    /// Initializes a new simplex instance deterministically from an integer seed.
+    ///
+    /// Two instances built from the same seed are equal and produce
+    /// bit-identical noise, which makes it easy to save and regenerate a
+    /// procedural world from a single number.
+    ///
+    /// The permutation table is shuffled by `utils::portable_perm`, which
+    /// does not depend on `rand`, so a seed yields the same table on every
+    /// platform and in every version of the crate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::from_seed(1337);
+    /// assert!(simplex == Simplex::from_seed(1337));
+    /// ```
+    pub fn from_seed(seed: u64) -> Simplex {
+        Simplex { seed: seed, perm: portable_perm(seed), frequency: (1.0, 1.0, 1.0), contrast: 1.0, grad_map: None, channel: 0, gradients: None }
+    }
+
+    /// Initializes a new simplex instance with a random number generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
+    /// use noisy::gen::Simplex;
+    ///
+    /// let mut rng: StdRng = StdRng::from_entropy();
+    /// let simplex = Simplex::from_rng(&mut rng);
+    /// ```
+    ///
+    /// This also allows you to initialize the instance with a seed:
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
+    /// use noisy::gen::Simplex;
+    ///
+    /// let mut rng: StdRng = SeedableRng::seed_from_u64(1337);
+    /// let simplex = Simplex::from_rng(&mut rng);
+    /// ```
+    ///
+    /// # Determinism
+    ///
+    /// The permutation table is built from the low byte of 256 successive
+    /// `next_u32` calls, in order, so a given sequence of random numbers yields
+    /// the same table (and therefore the same noise) on every target,
+    /// regardless of its endianness. Seeding the same deterministic generator
+    /// on two machines is enough to reproduce a world across platforms.
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Simplex {
+        let p: Vec<u8> = (0..256).map(|_| (rng.next_u32() & 0xff) as u8).collect();
+        let perm: Vec<u8> = (0..512).map(|idx: usize| p[idx & 255]).collect();
+
+        Simplex { seed: 0, perm: perm, frequency: (1.0, 1.0, 1.0), contrast: 1.0, grad_map: None, channel: 0, gradients: None }
+    }
+
+    /// Initializes a new simplex instance from a saved permutation table.
+    ///
+    /// The table must have 512 entries whose second half mirrors the first,
+    /// such as one returned by `perm`; an error describing the problem is
+    /// returned otherwise. Frequency, contrast, gradient and channel settings
+    /// are not part of the table and start out at their defaults.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let restored = Simplex::from_perm(simplex.perm().to_vec()).unwrap();
+    /// assert!(simplex == restored);
+    /// ```
+    pub fn from_perm(perm: Vec<u8>) -> Result<Simplex, String> {
+        validate_perm(perm.as_slice())?;
+
+        Ok(Simplex { seed: 0, perm: perm, frequency: (1.0, 1.0, 1.0), contrast: 1.0, grad_map: None, channel: 0, gradients: None })
+    }
+
+    /// Returns the permutation table used to hash the lattice coordinates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// assert_eq!(simplex.perm().len(), 512);
+    /// ```
+    pub fn perm(&self) -> &[u8] {
+        self.perm.as_slice()
+    }
+
+    /// Given a cell of the skewed simplex lattice and a fractional offset
+    /// within it, return a value in the interval [-1, 1].
+    ///
+    /// `cell` and `frac` are in lattice units: the skewed point
+    /// `cell + frac` is the input coordinate after the frequencies are
+    /// applied and the skew `(x + y) * (sqrt(3) - 1) / 2` is added to each
+    /// axis. The integer and fractional parts never get combined into a
+    /// single `f64`, so far from the origin the output keeps full precision
+    /// instead of degrading into blocky artifacts. Offsets outside of
+    /// [0, 1) are folded into the cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let val = simplex.noise2d_split((1 << 40, -(1 << 40)), (0.25, 0.75));
+    /// ```
+    pub fn noise2d_split(&self, cell: (i64, i64), frac: (f64, f64)) -> f64 {
+        let di: i64 = fast_floor(frac.0);
+        let dj: i64 = fast_floor(frac.1);
+        let (u, v): (f64, f64) = (frac.0 - di as f64, frac.1 - dj as f64);
+
+        // Unskew the offset within the cell back to (x, y) space.
+        let t: f64 = (u + v) * G2;
+
+        self.shape(self.simplex2d(self.perm.as_slice(), cell.0.wrapping_add(di), cell.1.wrapping_add(dj), u - t, v - t))
+    }
+
+    /// Sets the per-axis frequencies applied to the input coordinates.
+    ///
+    /// Each coordinate is multiplied by its frequency before sampling, so
+    /// `(2.0, 1.0, 1.0)` packs twice as much detail along the x axis.
+    /// The default of `(1.0, 1.0, 1.0)` leaves the coordinates untouched.
+    /// `noise2d_split` addresses the lattice directly and ignores the
+    /// frequencies.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new().with_frequencies((1.0, 4.0, 1.0));
+    /// ```
+    pub fn with_frequencies(self, frequency: (f64, f64, f64)) -> Simplex {
+        Simplex { frequency: frequency, ..self }
+    }
+
+    /// Same as `with_frequencies`, taking the x, y and z frequencies as an
+    /// array.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new().with_frequency([2.0, 1.0, 1.0]);
+    /// ```
+    pub fn with_frequency(self, frequency: [f64; 3]) -> Simplex {
+        self.with_frequencies((frequency[0], frequency[1], frequency[2]))
+    }
+
+    /// Sets the contrast applied to the output values.
+    ///
+    /// Every value `v` is reshaped to `sign(v) * |v|^(1 / contrast)`, which
+    /// keeps the output within [-1, 1]. Contrasts above 1.0 push values
+    /// towards the extremes, contrasts below 1.0 flatten them towards 0.
+    /// The default of 1.0 leaves the output untouched.
+    ///
+    /// Panics if `contrast` is not positive.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new().with_contrast(2.0);
+    /// ```
+    pub fn with_contrast(self, contrast: f64) -> Simplex {
+        assert!(contrast > 0.0, "simplex contrast must be positive, got {}", contrast);
+
+        Simplex { contrast: contrast, ..self }
+    }
+
+    /// Reshuffles which gradient each hashed lattice corner receives, seeded
+    /// independently of the permutation table.
+    ///
+    /// The permutation table decides how lattice coordinates are hashed, the
+    /// gradient seed decides which gradient a given hash selects. Two
+    /// generators with the same permutation but different gradient seeds
+    /// therefore produce unrelated fields, while keeping `perm()` identical.
+    /// The gradient map is shuffled the same way as `from_seed` shuffles its
+    /// table, so a gradient seed gives the same field on every platform and
+    /// in every version of the crate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let variant = simplex.clone().with_gradient_seed(7);
+    /// assert_eq!(simplex.perm(), variant.perm());
+    /// ```
+    pub fn with_gradient_seed(self, seed: u64) -> Simplex {
+        // The second half of the table only mirrors the first.
+        let mut map: Vec<u8> = portable_perm(seed);
+        map.truncate(256);
+
+        Simplex { grad_map: Some(map), ..self }
+    }
+
+    /// Derives one of 256 decorrelated channels of this generator.
+    ///
+    /// Channel 0 is the generator itself. Any other channel XORs `channel`
+    /// into each lattice hash and runs it through the permutation table once
+    /// more before selecting a gradient, so every channel picks its own
+    /// gradients while sharing `perm()`. This gives several independent
+    /// looking, reproducible layers for the cost of one table.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    ///
+    /// let simplex = Simplex::from_seed(1337);
+    /// let moisture = simplex.clone().with_channel(1);
+    /// let temperature = simplex.clone().with_channel(2);
+    /// assert!(moisture.noise2d(1.0, 2.0) != temperature.noise2d(1.0, 2.0));
+    /// ```
+    pub fn with_channel(self, channel: u8) -> Simplex {
+        Simplex { channel: channel, ..self }
+    }
+
+    /// Replaces the 3D gradient directions with `grads`.
+    ///
+    /// Each lattice hash selects `grads[hash % grads.len()]` instead of one of
+    /// the twelve built-in edge directions, which allows directional or
+    /// anisotropic noise, e.g. gradients squashed along z for stretched
+    /// strata. Only `noise3d` and the functions built on it use the set.
+    /// Passing `default_gradients()` reproduces the built-in output exactly.
+    ///
+    /// The output stays within [-1, 1] as long as no gradient is longer than
+    /// the built-in ones, sqrt(2).
+    ///
+    /// Panics if `grads` is empty or a component is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    ///
+    /// // Flatten the gradients towards the xy plane.
+    /// let grads: Vec<[f64; 3]> = Simplex::default_gradients().iter()
+    ///     .map(|g| [g[0], g[1], g[2] * 0.25])
+    ///     .collect();
+    /// let strata = Simplex::new().with_gradients(grads);
+    /// let val = strata.noise3d(1.0, 2.0, 3.0);
+    /// ```
+    pub fn with_gradients(self, grads: Vec<[f64; 3]>) -> Simplex {
+        assert!(!grads.is_empty(), "gradient set must not be empty");
+        assert!(grads.iter().flatten().all(|c| c.is_finite()), "gradient set must only hold finite components");
+
+        Simplex { gradients: Some(grads), ..self }
+    }
+
+    /// Returns the 16 built-in 3D gradient directions, in hash order.
+    ///
+    /// These are the twelve edges of a cube, with four of them repeated so a
+    /// 4-bit hash can select them.
+    pub fn default_gradients() -> Vec<[f64; 3]> {
+        (0..16).map(|hash: u8| {
+            let (x, y, z) = grad3_vec(hash);
+            [x, y, z]
+        }).collect()
+    }
+
+    /// Creates an iterator over 3D noise values sampled along a ray.
+    ///
+    /// The n-th value is taken at `origin + n * step * dir`. The sampler
+    /// precomputes the frequency-scaled per-step offset along with its skew
+    /// increment, so successive samples skip that part of the `noise3d` setup.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let density: f64 = simplex.ray_sampler((0.0, 0.0, 0.0), (0.0, 0.6, 0.8), 0.05)
+    ///     .take(64)
+    ///     .fold(0.0, |acc, val| acc + val);
+    /// ```
+    pub fn ray_sampler(&self, origin: (f64, f64, f64), dir: (f64, f64, f64), step: f64) -> RaySampler<'_> {
+        let origin: (f64, f64, f64) = (
+            origin.0 * self.frequency.0,
+            origin.1 * self.frequency.1,
+            origin.2 * self.frequency.2
+        );
+        let delta: (f64, f64, f64) = (
+            dir.0 * step * self.frequency.0,
+            dir.1 * step * self.frequency.1,
+            dir.2 * step * self.frequency.2
+        );
+
+        RaySampler {
+            simplex: self,
+            origin: origin,
+            delta: delta,
+            skew: (origin.0 + origin.1 + origin.2) * F3,
+            skew_delta: (delta.0 + delta.1 + delta.2) * F3,
+            step: 0
+        }
+    }
+
+    /// Given a (x, y) coordinate, return the divergence-free flow vector
+    /// `(-dn/dy, dn/dx)` of the 2D noise field.
+    ///
+    /// The vector is the noise gradient rotated by 90 degrees, computed
+    /// analytically from the simplex kernel. It is the curl of the noise
+    /// field, so advecting particles along it never bunches them up.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let (vx, vy) = simplex.flow2d(123.0 * 0.02, 132.0 * 0.02);
+    /// ```
+    pub fn flow2d(&self, xin: f64, yin: f64) -> (f64, f64) {
+        let (_, grad, _) = self.noise2d_dd(xin, yin);
+
+        (-grad[1], grad[0])
+    }
+
+    /// Given a (longitude, latitude) in radians, return a value in the
+    /// interval [-1, 1] sampled on the unit sphere.
+    ///
+    /// The angles are mapped to the point
+    /// `(cos(lat) cos(lon), cos(lat) sin(lon), sin(lat))` and sampled with
+    /// `noise3d`, so an equirectangular map built from it wraps around in
+    /// longitude and has no seam or pinching at the poles. The sphere has a
+    /// radius of 1; raise the frequencies for finer features.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::f64::consts::PI;
+    /// use noisy::gen::Simplex;
+    ///
+    /// let planet = Simplex::new().with_frequencies((4.0, 4.0, 4.0));
+    /// let (width, height) = (256, 128);
+    /// let val = planet.noise2d_seamless_radial(
+    ///     10.0 / width as f64 * 2.0 * PI,
+    ///     20.0 / height as f64 * PI - PI / 2.0
+    /// );
+    /// ```
+    pub fn noise2d_seamless_radial(&self, longitude: f64, latitude: f64) -> f64 {
+        // Reduce the longitude first so a full turn lands on exactly the same
+        // point, despite the rounding in sin and cos.
+        let longitude: f64 = longitude.rem_euclid(2.0 * PI);
+        let ring: f64 = latitude.cos();
+
+        self.noise3d(ring * longitude.cos(), ring * longitude.sin(), latitude.sin())
+    }
+
+    /// Returns a `size` by `size` texture of fbm noise that tiles seamlessly,
+    /// in row-major order.
+    ///
+    /// The texture spans `frequency` units of the noise on each axis, so
+    /// about `frequency` features of the first octave fit across it. It is
+    /// sampled through `Tileable2d` from `octaves` octaves of `Fbm`, with a
+    /// lacunarity of 2.0 and a persistence of 0.5: each octave samples the
+    /// same 4D torus at a larger scale, so every octave tiles along with the
+    /// first. The right column continues into the left one and the bottom
+    /// row into the top one.
+    ///
+    /// Panics if `frequency` is not positive or `octaves` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let texture = simplex.tileable_texture(256, 4.0, 5);
+    /// assert_eq!(texture.len(), 256 * 256);
+    /// ```
+    pub fn tileable_texture(&self, size: u32, frequency: f64, octaves: u32) -> Vec<f64> {
+        assert!(frequency > 0.0, "texture frequency must be positive, got {}", frequency);
+
+        let tile = Tileable2d::new(Fbm::new(self, octaves), (frequency, frequency));
+        let step: f64 = frequency / size as f64;
+        let size: usize = size as usize;
+
+        (0..size * size).map(|idx| {
+            tile.noise2d((idx % size) as f64 * step, (idx / size) as f64 * step)
+        }).collect()
+    }
+
+    /// Given a (x, y) coordinate, return the 2D noise value, its gradient and
+    /// its 2x2 Hessian matrix of second derivatives.
+    ///
+    /// All derivatives are computed analytically from the simplex kernel, so
+    /// curvature-based shading and erosion need no finite differencing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let (val, grad, hessian) = simplex.noise2d_dd(123.0 * 0.02, 132.0 * 0.02);
+    /// let laplacian = hessian[0][0] + hessian[1][1];
+    /// ```
+    pub fn noise2d_dd(&self, xin: f64, yin: f64) -> (f64, [f64; 2], [[f64; 2]; 2]) {
+        let freq: [f64; 2] = [self.frequency.0, self.frequency.1];
+        let (val, g, h) = self.raw2d_dd(xin * freq[0], yin * freq[1]);
+
+        // First and second derivatives of the contrast curve at val.
+        let d1: f64 = self.contrast_slope(val);
+        let d2: f64 = self.contrast_curvature(val);
+
+        // Chain rule through the contrast curve and the input frequencies.
+        let mut grad: [f64; 2] = [0.0; 2];
+        let mut hessian: [[f64; 2]; 2] = [[0.0; 2]; 2];
+        for a in 0..2 {
+            grad[a] = d1 * g[a] * freq[a];
+            for b in 0..2 {
+                hessian[a][b] = (d2 * g[a] * g[b] + d1 * h[a][b]) * freq[a] * freq[b];
+            }
+        }
+
+        (self.shape(val), grad, hessian)
+    }
+
+    /// Given a (x, y) coordinate, return the 2D noise value together with its
+    /// partial derivatives as `(value, d/dx, d/dy)`.
+    ///
+    /// The derivatives are computed analytically from the corner
+    /// contributions, so no finite differencing is needed for normal maps.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let (val, dx, dy) = simplex.noise2d_deriv(123.0 * 0.02, 132.0 * 0.02);
+    /// ```
+    pub fn noise2d_deriv(&self, xin: f64, yin: f64) -> (f64, f64, f64) {
+        let (val, grad, _) = self.noise2d_dd(xin, yin);
+
+        (val, grad[0], grad[1])
+    }
+
+    /// Given a (x, y, z) coordinate, return the 3D noise value together with
+    /// its partial derivatives as `(value, d/dx, d/dy, d/dz)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    ///
+    /// let simplex = Simplex::new();
+    /// let (val, dx, dy, dz) = simplex.noise3d_deriv(
+    ///     123.0 * 0.02,
+    ///     231.0 * 0.02,
+    ///     321.0 * 0.02
+    /// );
+    /// ```
+    pub fn noise3d_deriv(&self, xin: f64, yin: f64, zin: f64) -> (f64, f64, f64, f64) {
+        let freq: [f64; 3] = [self.frequency.0, self.frequency.1, self.frequency.2];
+        let (val, g) = self.raw3d_deriv(xin * freq[0], yin * freq[1], zin * freq[2]);
+
+        // Derivative of the contrast curve at val.
+        let d1: f64 = self.contrast_slope(val);
+
+        (self.shape(val), d1 * g[0] * freq[0], d1 * g[1] * freq[1], d1 * g[2] * freq[2])
+    }
+
+    /// Computes the unshaped 2D noise value, its gradient and its Hessian.
+    ///
+    /// Each corner contributes `t^4 * (g . d)` with `t = 0.5 - |d|^2`, whose
+    /// gradient is `-8 * t^3 * (g . d) * d + t^4 * g`.
+    #[allow(non_snake_case)]
+    fn raw2d_dd(&self, xin: f64, yin: f64) -> (f64, [f64; 2], [[f64; 2]; 2]) {
+        // Skew the input space to determine which simplex cell we're in
+        let s: f64 = (xin + yin) * F2;
+        let i: i64 = fast_floor(xin + s);
+        let j: i64 = fast_floor(yin + s);
+        let t: f64 = (i.wrapping_add(j) as f64) * G2;
+
+        // The x and y distances from the cell origin
+        let X0: f64 = (i as f64) - t;
+        let Y0: f64 = (j as f64) - t;
+        let x0: f64 = xin - X0;
+        let y0: f64 = yin - Y0;
+
+        // Offsets for the middle corner in (i, j) coords
+        let (i1, j1): (usize, usize) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let perm: &[u8] = self.perm.as_slice();
+
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (i & 255) as usize;
+        let jj: usize = (j & 255) as usize;
+
+        let corners: [(f64, f64, u8); 3] = [
+            (x0, y0, self.grad_index(perm_at(perm, ii + perm_at(perm, jj) as usize))),
+            (x0 - (i1 as f64) + G2, y0 - (j1 as f64) + G2,
+             self.grad_index(perm_at(perm, ii + i1 + (perm_at(perm, jj + j1) as usize)))),
+            (x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2,
+             self.grad_index(perm_at(perm, ii + 1 + (perm_at(perm, jj + 1) as usize))))
+        ];
+
+        let mut n: f64 = 0.0;
+        let mut grad: [f64; 2] = [0.0; 2];
+        let mut hessian: [[f64; 2]; 2] = [[0.0; 2]; 2];
+        for &(x, y, gi) in corners.iter() {
+            let t: f64 = 0.5 - x * x - y * y;
+            if t > 0.0 {
+                let (gx, gy) = grad2_vec(gi);
+                let dot: f64 = gx * x + gy * y;
+                let t2: f64 = t * t;
+                let t3: f64 = t2 * t;
+                let t4: f64 = t2 * t2;
+
+                n += t4 * dot;
+                grad[0] += -8.0 * t3 * dot * x + t4 * gx;
+                grad[1] += -8.0 * t3 * dot * y + t4 * gy;
+
+                hessian[0][0] += -8.0 * t3 * dot + 48.0 * x * x * t2 * dot - 16.0 * x * t3 * gx;
+                hessian[1][1] += -8.0 * t3 * dot + 48.0 * y * y * t2 * dot - 16.0 * y * t3 * gy;
+                hessian[0][1] += 48.0 * x * y * t2 * dot - 8.0 * t3 * (x * gy + y * gx);
+            }
+        }
+        hessian[1][0] = hessian[0][1];
+
+        for (g, row) in grad.iter_mut().zip(hessian.iter_mut()) {
+            *g *= 40.0;
+            for h in row.iter_mut() {
+                *h *= 40.0;
+            }
+        }
+
+        (40.0 * n, grad, hessian)
+    }
+
+    /// Computes the unshaped 3D noise value and its gradient.
+    ///
+    /// Each corner contributes `t^4 * (g . d)` with `t = 0.6 - |d|^2`.
+    #[allow(non_snake_case)]
+    fn raw3d_deriv(&self, xin: f64, yin: f64, zin: f64) -> (f64, [f64; 3]) {
+        // Skew the input space to determine which simplex cell we're in
+        let s: f64 = (xin + yin + zin) * F3;
+        let i: i64 = fast_floor(xin + s);
+        let j: i64 = fast_floor(yin + s);
+        let k: i64 = fast_floor(zin + s);
+        let t: f64 = (i.wrapping_add(j).wrapping_add(k) as f64) * G3;
+
+        // The x, y and z distances from the cell origin
+        let X0: f64 = (i as f64) - t;
+        let Y0: f64 = (j as f64) - t;
+        let Z0: f64 = (k as f64) - t;
+        let x0: f64 = xin - X0;
+        let y0: f64 = yin - Y0;
+        let z0: f64 = zin - Z0;
+
+        // Offsets for the second and third corners in (i, j, k) coords,
+        // picked in the same order as noise3d.
+        let ((i1, j1, k1), (i2, j2, k2)): ((usize, usize, usize), (usize, usize, usize)) =
+            if x0 >= y0 {
+                if y0 >= z0 {
+                    ((1, 0, 0), (1, 1, 0))
+                } else if x0 >= z0 {
+                    ((1, 0, 0), (1, 0, 1))
+                } else {
+                    ((0, 0, 1), (1, 0, 1))
+                }
+            } else {
+                if y0 < z0 {
+                    ((0, 0, 1), (0, 1, 1))
+                } else if x0 < z0 {
+                    ((0, 1, 0), (0, 1, 1))
+                } else {
+                    ((0, 1, 0), (1, 1, 0))
+                }
+            };
+
+        let perm: &[u8] = self.perm.as_slice();
+
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (i & 255) as usize;
+        let jj: usize = (j & 255) as usize;
+        let kk: usize = (k & 255) as usize;
+
+        let corners: [(f64, f64, f64, u8); 4] = [
+            (x0, y0, z0,
+             self.grad_index(perm_at(perm, ii + (perm_at(perm, jj + (perm_at(perm, kk) as usize)) as usize)))),
+            (x0 - (i1 as f64) + G3, y0 - (j1 as f64) + G3, z0 - (k1 as f64) + G3,
+             self.grad_index(perm_at(perm, ii + i1 + (perm_at(perm, jj + j1 + (perm_at(perm, kk + k1) as usize)) as usize)))),
+            (x0 - (i2 as f64) + 2.0 * G3, y0 - (j2 as f64) + 2.0 * G3, z0 - (k2 as f64) + 2.0 * G3,
+             self.grad_index(perm_at(perm, ii + i2 + (perm_at(perm, jj + j2 + (perm_at(perm, kk + k2) as usize)) as usize)))),
+            (x0 - 1.0 + 3.0 * G3, y0 - 1.0 + 3.0 * G3, z0 - 1.0 + 3.0 * G3,
+             self.grad_index(perm_at(perm, ii + 1 + (perm_at(perm, jj + 1 + (perm_at(perm, kk + 1) as usize)) as usize))))
+        ];
+
+        let mut n: f64 = 0.0;
+        let mut grad: [f64; 3] = [0.0; 3];
+        for &(x, y, z, gi) in corners.iter() {
+            let t: f64 = 0.6 - x * x - y * y - z * z;
+            if t > 0.0 {
+                let (gx, gy, gz) = self.grad3_vec(gi);
+                let dot: f64 = gx * x + gy * y + gz * z;
+                let t3: f64 = t * t * t;
+                let t4: f64 = t3 * t;
+
+                n += t4 * dot;
+                grad[0] += -8.0 * t3 * dot * x + t4 * gx;
+                grad[1] += -8.0 * t3 * dot * y + t4 * gy;
+                grad[2] += -8.0 * t3 * dot * z + t4 * gz;
+            }
+        }
+
+        (32.0 * n, [32.0 * grad[0], 32.0 * grad[1], 32.0 * grad[2]])
+    }
+
+    /// Computes 3D noise at a coordinate whose skew offset
+    /// `(xin + yin + zin) * F3` has already been computed.
+    #[allow(non_snake_case)]
+    fn skewed3d(&self, xin: f64, yin: f64, zin: f64, s: f64) -> f64 {
+        // Noise contributions from the four corners
+        let n0: f64;
+        let n1: f64;
+        let n2: f64;
+        let n3: f64;
+
+        // Find the simplex cell from the skewed input
+        let i: i64 = fast_floor(xin + s);
+        let j: i64 = fast_floor(yin + s);
+        let k: i64 = fast_floor(zin + s);
+        let t: f64 = (i.wrapping_add(j).wrapping_add(k) as f64) * G3;
+
+        // Unskew the cell origin back to (x, y, z) space
+        let X0: f64 = (i as f64) - t;
+        let Y0: f64 = (j as f64) - t;
+        let Z0: f64 = (k as f64) - t;
+        // The x, y, and distances from the cell origin
+        let x0: f64 = xin - X0;
+        let y0: f64 = yin - Y0;
+        let z0: f64 = zin - Z0;
+
+        // For the 3D case, the simplex shape is a slightly irregular tetrahedron.
+        // Determine which simplex we are in.
+        let i1: usize; // Offsets for second corner of simplex in (i, j, k) coords
+        let j1: usize;
+        let k1: usize;
+        let i2: usize; // Offsets for third corner of simplex in (i, j, k) coords
+        let j2: usize;
+        let k2: usize;
+        if x0 >= y0 {
+            if y0 >= z0 { // X Y Z order
+                i1 = 1;
+                j1 = 0;
+                k1 = 0;
+                i2 = 1;
+                j2 = 1;
+                k2 = 0;
+            } else if x0 >= z0 { // X Z Y order
+                i1 = 1;
+                j1 = 0;
+                k1 = 0;
+                i2 = 1;
+                j2 = 0;
+                k2 = 1;
+            } else {  // Z X Y order
+                i1 = 0;
+                j1 = 0;
+                k1 = 1;
+                i2 = 1;
+                j2 = 0;
+                k2 = 1;
+            }
+        } else { // x0 < y0
+            if y0 < z0 { // Z Y X order
+                i1 = 0;
+                j1 = 0;
+                k1 = 1;
+                i2 = 0;
+                j2 = 1;
+                k2 = 1;
+            } else if x0 < z0 { // Y Z X order
+                i1 = 0;
+                j1 = 1;
+                k1 = 0;
+                i2 = 0;
+                j2 = 1;
+                k2 = 1;
+            } else { // Y X Z order
+                i1 = 0;
+                j1 = 1;
+                k1 = 0;
+                i2 = 1;
+                j2 = 1;
+                k2 = 0;
+            }
+        }
+
+        // A step of (1, 0, 0) in (i, j, k) means a step of (1 - c, -c, -c) in (x, y, z),
+        // a step of (0, 1, 0) in (i, j, k) means a step of (-c, 1 - c, -c) in (x, y, z), and
+        // a step of (0, 0, 1) in (i, j, k) means a step of (-c, -c, 1 - c) in (x, y, z), where
+        // c = 1/6.
+
+        // Offsets for second corner in (x, y, z) coords
+        let x1: f64 = x0 - (i1 as f64) + G3;
+        let y1: f64 = y0 - (j1 as f64) + G3;
+        let z1: f64 = z0 - (k1 as f64) + G3;
+        // Offsets for third corner in (x, y, z) coords
+        let x2: f64 = x0 - (i2 as f64) + 2.0 * G3;
+        let y2: f64 = y0 - (j2 as f64) + 2.0 * G3;
+        let z2: f64 = z0 - (k2 as f64) + 2.0 * G3;
+        // Offsets for last corner in (x, y, z) coords
+        let x3: f64 = x0 - 1.0 + 3.0 * G3;
+        let y3: f64 = y0 - 1.0 + 3.0 * G3;
+        let z3: f64 = z0 - 1.0 + 3.0 * G3;
+
+        let perm: &[u8] = self.perm.as_slice();
+
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (i & 255) as usize;
+        let jj: usize = (j & 255) as usize;
+        let kk: usize = (k & 255) as usize;
+        // Work out the hashed gradient indices of the four simplex corners
+        let gi0: u8 = self.grad_index(perm_at(perm, ii + (perm_at(perm, jj + (perm_at(perm, kk) as usize)) as usize)));
+        let gi1: u8 = self.grad_index(perm_at(perm, ii + i1 + (perm_at(perm, jj + j1 + (perm_at(perm, kk + k1) as usize)) as usize)));
+        let gi2: u8 = self.grad_index(perm_at(perm, ii + i2 + (perm_at(perm, jj + j2 + (perm_at(perm, kk + k2) as usize)) as usize)));
+        let gi3: u8 = self.grad_index(perm_at(perm, ii + 1 + (perm_at(perm, jj + 1 + (perm_at(perm, kk + 1) as usize)) as usize)));
+
+        // Calculate the contribution from the four corners
+        let mut t0: f64 = 0.6 - x0 * x0 - y0 * y0 - z0 * z0;
+        if t0 < 0.0 {
+            n0 = 0.0;
+        } else {
+            t0 *= t0;
+            n0 = t0 * t0 * self.grad3(gi0, x0, y0, z0);
+        }
+
+        let mut t1: f64 = 0.6 - x1 * x1 - y1 * y1 - z1 * z1;
+        if t1 < 0.0 {
+            n1 = 0.0;
+        } else {
+            t1 *= t1;
+            n1 = t1 * t1 * self.grad3(gi1, x1, y1, z1);
+        }
+
+        let mut t2: f64 = 0.6 - x2 * x2 - y2 * y2 - z2 * z2;
+        if t2 < 0.0 {
+            n2 = 0.0;
+        } else {
+            t2 *= t2;
+            n2 = t2 * t2 * self.grad3(gi2, x2, y2, z2);
+        }
+
+        let mut t3: f64 = 0.6 - x3 * x3 - y3 * y3 - z3 * z3;
+        if t3 < 0.0 {
+            n3 = 0.0;
+        } else {
+            t3 *= t3;
+            n3 = t3 * t3 * self.grad3(gi3, x3, y3, z3);
+        }
+
+        // Add contributions from each corner to get the final noise value.
+        // The result is scaled to return values in the interval [-1,1].
+        self.shape(32.0 * (n0 + n1 + n2 + n3))
+    }
+
+    /// Computes unshaped 2D noise at an already scaled coordinate.
+    ///
+    /// Takes the permutation table as a slice so that batched callers can
+    /// look it up once instead of once per sample.
+    #[allow(non_snake_case)]
+    #[inline]
+    fn raw2d(&self, perm: &[u8], xin: f64, yin: f64) -> f64 {
+        // Skew the input space to determine which simplex cell we're in
+        let s: f64 = (xin + yin) * F2; // Hairy factor for 2D
+        let i: i64 = fast_floor(xin + s);
+        let j: i64 = fast_floor(yin + s);
+        let t: f64 = (i.wrapping_add(j) as f64) * G2;
+
+        // Unskew the cell origin back to (x, y) space
+        let X0: f64 = (i as f64) - t;
+        let Y0: f64 = (j as f64) - t;
+        // The x and y distances from the cell origin
+        let x0: f64 = xin - X0;
+        let y0: f64 = yin - Y0;
+
+        self.simplex2d(perm, i, j, x0, y0)
+    }
+
+    /// Computes unshaped 2D noise from the skewed lattice cell `(i, j)` and
+    /// the unskewed distances `(x0, y0)` from its origin.
+    #[inline]
+    fn simplex2d(&self, perm: &[u8], i: i64, j: i64, x0: f64, y0: f64) -> f64 {
+        // Noise contributions from the three corners
+        let n0: f64;
+        let n1: f64;
+        let n2: f64;
+
+        // For the 2D case, the simplex shape is an equilateral triangle.
+        // Determine which shape we are in.
+        let i1: usize; // Offsets for second (middle) corner of simplex in (i, j) coords
+        let j1: usize;
+        if x0 > y0 { // Lower triangle, XY order: (0, 0) -> (1, 0) -> (1, 1)
+            i1 = 1;
+            j1 = 0;
+        } else { // Upper triangle, YX order: (0, 0) -> (0, 1) -> (1, 1)
+            i1 = 0;
+            j1 = 1;
+        }
+
+        // A step of (1, 0) in (i, j) means a step of (1 - c, -c) in (x, y), and
+        // a step of (0, 1) in (i, j) means a step of (-c, 1 - c) in (x, y), where
+        // c = (3 - sqrt(3.0))/6.
+
+        // Offsets for middle corner in (x,y) unskewed coords
+        let x1: f64 = x0 - (i1 as f64) + G2;
+        let y1: f64 = y0 - (j1 as f64) + G2;
+        // Offsets for last corner in (x,y) unskewed coords
+        let x2: f64 = x0 - 1.0 + 2.0 * G2;
+        let y2: f64 = y0 - 1.0 + 2.0 * G2;
+
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (i & 255) as usize;
+        let jj: usize = (j & 255) as usize;
+        // Work out the hashed gradient indices of the three simplex corners
+        let gi0: u8 = self.grad_index(perm_at(perm, ii + perm_at(perm, jj) as usize));
+        let gi1: u8 = self.grad_index(perm_at(perm, ii + i1 + (perm_at(perm, jj + j1) as usize)));
+        let gi2: u8 = self.grad_index(perm_at(perm, ii + 1 + (perm_at(perm, jj + 1) as usize)));
+
+        // Calculate the contribution from the three corners
+        let mut t0: f64 = 0.5 - x0 * x0 - y0 * y0;
+        if t0 < 0.0 {
+            n0 = 0.0;
+        } else {
+            t0 *= t0;
+            n0 = t0 * t0 * grad2(gi0, x0, y0);
+        }
+
+        let mut t1: f64 = 0.5 - x1 * x1 - y1 * y1;
+        if t1 < 0.0 {
+            n1 = 0.0;
+        } else {
+            t1 *= t1;
+            n1 = t1 * t1 * grad2(gi1, x1, y1);
+        }
+
+        let mut t2: f64 = 0.5 - x2 * x2 - y2 * y2;
+        if t2 < 0.0 {
+            n2 = 0.0;
+        } else {
+            t2 *= t2;
+            n2 = t2 * t2 * grad2(gi2, x2, y2);
+        }
+
+        // Add contributions from each corner to get the final noise value.
+        // The result is scaled to return values in the interval [-1, 1].
+        40.0 * (n0 + n1 + n2)
+    }
+
+    /// Fills `out` with 2D noise sampled on a grid, in row-major order.
+    ///
+    /// The grid is `width` samples wide and `out.len() / width` rows tall;
+    /// trailing elements that don't make up a whole row are left untouched.
+    /// The sample at column `x` and row `y` equals
+    /// `noise2d(origin.0 + x * step.0, origin.1 + y * step.1)`, but the
+    /// frequency scaling of each row and the permutation table lookup are
+    /// hoisted out of the inner loop.
+    ///
+    /// Panics if `width` is 0.
     ///
     /// # Example
     ///
@@ -32,45 +1025,313 @@ impl Simplex {
     /// use noisy::gen::Simplex;
     ///
     /// let simplex = Simplex::new();
+    /// let mut heights = vec![0.0; 64 * 64];
+    /// simplex.fill2d(heights.as_mut_slice(), 64, (0.0, 0.0), (0.05, 0.05));
     /// ```
-    pub fn new() -> Simplex {
-        let mut rng: XorShiftRng = weak_rng();
+    pub fn fill2d(&self, out: &mut [f64], width: usize, origin: (f64, f64), step: (f64, f64)) {
+        assert!(width > 0, "width must be at least 1");
 
-        let p: Vec<u8> = (0..256).map(|_| rng.gen::<u8>()).collect();
-        let perm: Vec<u8> = (0..512).map(|idx:i32| {p[(idx & 255) as usize]}).collect();
-
-        Simplex { perm: perm }
+        let perm: &[u8] = self.perm.as_slice();
+        let height: usize = out.len() / width;
+        for (y, row) in out.chunks_mut(width).take(height).enumerate() {
+            let yin: f64 = (origin.1 + y as f64 * step.1) * self.frequency.1;
+            for (x, sample) in row.iter_mut().enumerate() {
+                let xin: f64 = (origin.0 + x as f64 * step.0) * self.frequency.0;
+                *sample = self.shape(self.raw2d(perm, xin, yin));
+            }
+        }
     }
 
-    /// Initializes a new simplex instance with a random number generator.
+    /// Fills `out` with 3D noise sampled on a `dims.0` by `dims.1` by `dims.2`
+    /// grid, with x varying fastest, then y, then z.
+    ///
+    /// The sample at `(x, y, z)` is stored at
+    /// `(z * dims.1 + y) * dims.0 + x` and equals
+    /// `noise3d(origin[0] + x * step[0], origin[1] + y * step[1], origin[2] + z * step[2])`,
+    /// but the frequency scaling of each slice and row is hoisted out of the
+    /// inner loop. Trailing elements past the grid are left untouched.
+    ///
+    /// Panics if `out` holds fewer than `dims.0 * dims.1 * dims.2` elements.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use std::rand::StdRng;
     /// use noisy::gen::Simplex;
     ///
-    /// let mut rng: StdRng = StdRng::new().unwrap();
-    /// let simplex = Simplex::from_rng(&mut rng);
+    /// let simplex = Simplex::new();
+    /// let mut density = vec![0.0; 32 * 32 * 32];
+    /// simplex.fill3d(density.as_mut_slice(), (32, 32, 32), [0.0, 0.0, 0.0], [0.05, 0.05, 0.05]);
     /// ```
+    pub fn fill3d(&self, out: &mut [f64], dims: (usize, usize, usize), origin: [f64; 3], step: [f64; 3]) {
+        let (width, height, depth) = dims;
+        assert!(out.len() >= width * height * depth,
+                "buffer too small for a {}x{}x{} volume", width, height, depth);
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let slices = out.chunks_mut(width * height).take(depth);
+        for (z, slice) in slices.enumerate() {
+            let zin: f64 = (origin[2] + z as f64 * step[2]) * self.frequency.2;
+            for (y, row) in slice.chunks_mut(width).enumerate() {
+                let yin: f64 = (origin[1] + y as f64 * step[1]) * self.frequency.1;
+                for (x, sample) in row.iter_mut().enumerate() {
+                    let xin: f64 = (origin[0] + x as f64 * step[0]) * self.frequency.0;
+                    *sample = self.skewed3d(xin, yin, zin, (xin + yin + zin) * F3);
+                }
+            }
+        }
+    }
+
+    /// Fills `buf` with a `width` by `height` grid of 2D noise values in
+    /// row-major order, narrowed to `f32`, evaluating four samples at once.
     ///
-    /// This also allows you to initialize the instance with a seed:
+    /// The samples equal those of `fill_grid2d_f32`. Each step of the simplex
+    /// kernel (skewing, picking the triangle, the gradient dot products and
+    /// the radial falloff) runs as a loop over four lanes without branches,
+    /// which the optimizer can turn into vector instructions. Only the
+    /// permutation table lookups are done lane by lane. Needs the `simd`
+    /// feature.
+    ///
+    /// Panics if `buf` holds fewer than `width * height` values.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use std::rand::{StdRng, SeedableRng};
     /// use noisy::gen::Simplex;
     ///
-    /// let seed: &[_] = &[1337];
-    /// let mut rng: StdRng = SeedableRng::from_seed(seed);
-    /// let simplex = Simplex::from_rng(&mut rng);
+    /// let simplex = Simplex::new();
+    /// let mut texture = vec![0.0f32; 64 * 64];
+    /// simplex.fill_grid2d_f32_simd(texture.as_mut_slice(), 64, 64, (0.0, 0.0), (0.02, 0.02));
     /// ```
-    pub fn from_rng<R: Rng>(rng: &mut R) -> Simplex {
-        let p: Vec<u8> = (0..256).map(|_| rng.gen::<u8>()).collect();
-        let perm: Vec<u8> = (0..512).map(|idx:i32| {p[(idx & 255) as usize]}).collect();
+    #[cfg(feature = "simd")]
+    pub fn fill_grid2d_f32_simd(&self, buf: &mut [f32], width: usize, height: usize, origin: (f64, f64), step: (f64, f64)) {
+        lanes::fill_grid2d_f32(buf, width, height, origin, step, |xs, yin| self.lanes2d(xs, yin), |xin, yin| self.noise2d(xin, yin));
+    }
+
+    /// Computes `noise2d` at four x coordinates sharing one y coordinate.
+    #[cfg(feature = "simd")]
+    #[inline]
+    #[allow(non_snake_case)]
+    fn lanes2d(&self, xin: [f64; LANES], yin: f64) -> [f64; LANES] {
+        let perm: &[u8] = self.perm.as_slice();
+        let yin: f64 = yin * self.frequency.1;
+
+        // Skew each lane to find its simplex cell, then unskew the cell
+        // origin back to (x, y) space.
+        let mut i: [i64; LANES] = [0; LANES];
+        let mut j: [i64; LANES] = [0; LANES];
+        let mut x0: [f64; LANES] = [0.0; LANES];
+        let mut y0: [f64; LANES] = [0.0; LANES];
+        for l in 0..LANES {
+            let xin: f64 = xin[l] * self.frequency.0;
+            let s: f64 = (xin + yin) * F2;
+            i[l] = fast_floor(xin + s);
+            j[l] = fast_floor(yin + s);
+            let t: f64 = (i[l].wrapping_add(j[l]) as f64) * G2;
+            x0[l] = xin - ((i[l] as f64) - t);
+            y0[l] = yin - ((j[l] as f64) - t);
+        }
+
+        // Pick the lower or upper triangle as a 0/1 offset, and place the
+        // middle and last corners.
+        let mut i1: [usize; LANES] = [0; LANES];
+        let mut x1: [f64; LANES] = [0.0; LANES];
+        let mut y1: [f64; LANES] = [0.0; LANES];
+        let mut x2: [f64; LANES] = [0.0; LANES];
+        let mut y2: [f64; LANES] = [0.0; LANES];
+        for l in 0..LANES {
+            let lower: bool = x0[l] > y0[l];
+            i1[l] = lower as usize;
+            x1[l] = x0[l] - if_else(lower, 1.0, 0.0) + G2;
+            y1[l] = y0[l] - if_else(lower, 0.0, 1.0) + G2;
+            x2[l] = x0[l] - 1.0 + 2.0 * G2;
+            y2[l] = y0[l] - 1.0 + 2.0 * G2;
+        }
+
+        // Hash the three corners of each lane.
+        let mut gi: [[u8; LANES]; 3] = [[0; LANES]; 3];
+        for l in 0..LANES {
+            let ii: usize = (i[l] & 255) as usize;
+            let jj: usize = (j[l] & 255) as usize;
+            let j1: usize = 1 - i1[l];
+            gi[0][l] = self.grad_index(perm_at(perm, ii + perm_at(perm, jj) as usize));
+            gi[1][l] = self.grad_index(perm_at(perm, ii + i1[l] + (perm_at(perm, jj + j1) as usize)));
+            gi[2][l] = self.grad_index(perm_at(perm, ii + 1 + (perm_at(perm, jj + 1) as usize)));
+        }
+
+        // Sum the corner contributions, clamping the falloff at 0 instead of
+        // branching on it.
+        let mut out: [f64; LANES] = [0.0; LANES];
+        for l in 0..LANES {
+            let t0: f64 = (0.5 - x0[l] * x0[l] - y0[l] * y0[l]).max(0.0);
+            let t1: f64 = (0.5 - x1[l] * x1[l] - y1[l] * y1[l]).max(0.0);
+            let t2: f64 = (0.5 - x2[l] * x2[l] - y2[l] * y2[l]).max(0.0);
+            let (t0, t1, t2) = (t0 * t0, t1 * t1, t2 * t2);
+            let n0: f64 = t0 * t0 * lanes::grad2(gi[0][l], x0[l], y0[l]);
+            let n1: f64 = t1 * t1 * lanes::grad2(gi[1][l], x1[l], y1[l]);
+            let n2: f64 = t2 * t2 * lanes::grad2(gi[2][l], x2[l], y2[l]);
+            out[l] = self.shape(40.0 * (n0 + n1 + n2));
+        }
+
+        out
+    }
+
+    /// Maps a permutation hash to the hash used for gradient selection.
+    #[inline]
+    fn grad_index(&self, hash: u8) -> u8 {
+        let hash: u8 = if self.channel == 0 {
+            hash
+        } else {
+            perm_at(self.perm.as_slice(), (hash ^ self.channel) as usize)
+        };
+
+        match self.grad_map {
+            Some(ref map) => map[hash as usize],
+            None => hash
+        }
+    }
+
+    /// Dots the 3D gradient selected by `hash` with (x, y, z).
+    #[inline]
+    fn grad3(&self, hash: u8, x: f64, y: f64, z: f64) -> f64 {
+        match self.gradients {
+            Some(ref grads) => {
+                let g: [f64; 3] = grads[hash as usize % grads.len()];
+                g[0] * x + g[1] * y + g[2] * z
+            }
+            None => grad3(hash, x, y, z)
+        }
+    }
+
+    /// Looks up the 3D gradient selected by `hash`.
+    #[inline]
+    fn grad3_vec(&self, hash: u8) -> (f64, f64, f64) {
+        match self.gradients {
+            Some(ref grads) => {
+                let g: [f64; 3] = grads[hash as usize % grads.len()];
+                (g[0], g[1], g[2])
+            }
+            None => grad3_vec(hash)
+        }
+    }
+
+    /// Returns the slope of the contrast curve at a raw noise value.
+    ///
+    /// For contrasts above 1.0 the curve is vertical at 0, where lattice
+    /// points such as the origin land exactly. The magnitude of `val` is
+    /// floored at 1e-6 there, so the slope is large but finite and the
+    /// derivatives built on it stay usable.
+    #[inline]
+    fn contrast_slope(&self, val: f64) -> f64 {
+        if self.contrast == 1.0 {
+            1.0
+        } else {
+            let a: f64 = 1.0 / self.contrast;
+            a * val.abs().max(CONTRAST_FLOOR).powf(a - 1.0)
+        }
+    }
+
+    /// Returns the second derivative of the contrast curve at a raw noise
+    /// value.
+    ///
+    /// It diverges at 0 for any contrast other than 1.0, with opposite signs
+    /// on either side. Exactly at 0 the two sides cancel and 0 is returned;
+    /// elsewhere the magnitude of `val` is floored at 1e-6, as in
+    /// `contrast_slope`.
+    #[inline]
+    fn contrast_curvature(&self, val: f64) -> f64 {
+        if self.contrast == 1.0 || val == 0.0 {
+            0.0
+        } else {
+            let a: f64 = 1.0 / self.contrast;
+            a * (a - 1.0) * val.abs().max(CONTRAST_FLOOR).powf(a - 2.0) * val.signum()
+        }
+    }
+
+    /// Applies the contrast curve to a raw noise value.
+    #[inline]
+    fn shape(&self, val: f64) -> f64 {
+        if self.contrast == 1.0 {
+            val
+        } else {
+            val.signum() * val.abs().powf(1.0 / self.contrast)
+        }
+    }
+}
+
+/// Two generators are equal when they produce the same noise; the recorded
+/// seed is not compared.
+impl PartialEq for Simplex {
+    fn eq(&self, other: &Simplex) -> bool {
+        self.perm == other.perm
+            && self.frequency == other.frequency
+            && self.contrast == other.contrast
+            && self.grad_map == other.grad_map
+            && self.channel == other.channel
+            && self.gradients == other.gradients
+    }
+}
 
-        Simplex { perm: perm }
+/// Prints the seed and settings; the permutation table is summarized by its
+/// length.
+impl fmt::Debug for Simplex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Simplex")
+            .field("seed", &self.seed)
+            .field("perm_len", &self.perm.len())
+            .field("frequency", &self.frequency)
+            .field("contrast", &self.contrast)
+            .field("grad_map", &self.grad_map.is_some())
+            .field("channel", &self.channel)
+            .field("gradients", &self.gradients.as_ref().map(|grads| grads.len()))
+            .finish()
+    }
+}
+
+impl Seedable for Simplex {
+    /// Rebuilds the permutation table from `seed`, as `from_seed` would.
+    ///
+    /// Frequency, contrast, gradient and channel settings are kept.
+    fn set_seed(&mut self, seed: u64) {
+        self.perm = Simplex::from_seed(seed).perm;
+        self.seed = seed;
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// An iterator over simplex noise values sampled at regular steps along a ray.
+///
+/// Created by `Simplex::ray_sampler`. The iterator never ends; bound it with
+/// `take` or `take_while`.
+#[derive(Debug)]
+pub struct RaySampler<'a> {
+    simplex: &'a Simplex,
+    origin: (f64, f64, f64),
+    delta: (f64, f64, f64),
+    skew: f64,
+    skew_delta: f64,
+    step: u64
+}
+
+impl<'a> Iterator for RaySampler<'a> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        // Scale by the step count rather than accumulating, so the position
+        // does not drift over long rays.
+        let n: f64 = self.step as f64;
+        self.step += 1;
+
+        Some(self.simplex.skewed3d(
+            self.origin.0 + n * self.delta.0,
+            self.origin.1 + n * self.delta.1,
+            self.origin.2 + n * self.delta.2,
+            self.skew + n * self.skew_delta
+        ))
     }
 }
 
@@ -87,18 +1348,22 @@ impl NoiseGen for Simplex {
     /// ```
     #[allow(non_snake_case)]
     fn noise1d(&self, xin: f64) -> f64 {
+        let xin: f64 = xin * self.frequency.0;
+
         // Noise contributions
-        let mut n0: f64;
-        let mut n1: f64;
+        let n0: f64;
+        let n1: f64;
 
         let i0: i64 = fast_floor(xin);
-        let i1: i64 = i0 + 1;
+        let i1: i64 = i0.wrapping_add(1);
         let x0: f64 = xin - i0 as f64;
         let x1: f64 = x0 - 1.0;
 
+        let perm: &[u8] = self.perm.as_slice();
+
         // Work out the hashed gradient indices
-        let gi0: u8 = self.perm[(i0 & 255) as usize] as u8;
-        let gi1: u8 = self.perm[(i1 & 255) as usize] as u8;
+        let gi0: u8 = self.grad_index(perm_at(perm, (i0 & 255) as usize));
+        let gi1: u8 = self.grad_index(perm_at(perm, (i1 & 255) as usize));
 
         // Calculate the contributions
         let mut t0: f64 = 1.0 - x0 * x0;
@@ -109,9 +1374,12 @@ impl NoiseGen for Simplex {
         t1 *= t1;
         n1 = t1 * t1 * grad1(gi1, x1);
 
-        // The maximum value of this noise is 8*(3/4)^4 = 2.53125.
-        // A factor of 0.395 scales to fit exactly within [-1,1].
-        0.395 * (n0 + n1)
+        // The maximum value of this noise is 8*(3/4)^4 = 2.53125, reached
+        // halfway between two corners with gradients of 8 and -8. A factor of
+        // 0.395 scales that to 0.99984. About 1 cell in 128 has such a
+        // gradient pair, so a sweep over the 256 cells of a table typically
+        // reaches both ends of [-1,1].
+        self.shape(0.395 * (n0 + n1))
     }
 
     /// Given a (x, y) coordinate, return a value in the interval [-1, 1].
@@ -127,85 +1395,11 @@ impl NoiseGen for Simplex {
     ///     132.0 * 0.02
     /// );
     /// ```
-    #[allow(non_snake_case)]
     fn noise2d(&self, xin: f64, yin: f64) -> f64 {
-        // Noise contributions from the three corners
-        let mut n0: f64;
-        let mut n1: f64;
-        let mut n2: f64;
-
-        // Skew the input space to determine which simplex cell we're in
-        let s: f64 = (xin + yin) * F2; // Hairy factor for 2D
-        let i: i64 = fast_floor(xin + s);
-        let j: i64 = fast_floor(yin + s);
-        let t: f64 = ((i + j) as f64) * G2;
-
-        // Unskew the cell origin back to (x, y) space
-        let X0: f64 = (i as f64) - t;
-        let Y0: f64 = (j as f64) - t;
-        // The x and y distances from the cell origin
-        let x0: f64 = xin - X0;
-        let y0: f64 = yin - Y0;
-
-        // For the 2D case, the simplex shape is an equilateral triangle.
-        // Determine which shape we are in.
-        let i1: usize; // Offsets for second (middle) corner of simplex in (i, j) coords
-        let j1: usize;
-        if x0 > y0 { // Lower triangle, XY order: (0, 0) -> (1, 0) -> (1, 1)
-            i1 = 1;
-            j1 = 0;
-        } else { // Upper triangle, YX order: (0, 0) -> (0, 1) -> (1, 1)
-            i1 = 0;
-            j1 = 1;
-        }
-
-        // A step of (1, 0) in (i, j) means a step of (1 - c, -c) in (x, y), and
-        // a step of (0, 1) in (i, j) means a step of (-c, 1 - c) in (x, y), where
-        // c = (3 - sqrt(3.0))/6.
-
-        // Offsets for middle corner in (x,y) unskewed coords
-        let x1: f64 = x0 - (i1 as f64) + G2;
-        let y1: f64 = y0 - (j1 as f64) + G2;
-        // Offsets for last corner in (x,y) unskewed coords
-        let x2: f64 = x0 - 1.0 + 2.0 * G2;
-        let y2: f64 = y0 - 1.0 + 2.0 * G2;
-
-        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
-        let ii: usize = (i & 255) as usize;
-        let jj: usize = (j & 255) as usize;
-        // Work out the hashed gradient indices of the three simplex corners
-        let gi0: u8 = self.perm[ii + self.perm[jj] as usize] as u8;
-        let gi1: u8 = self.perm[ii + i1 + (self.perm[jj + j1] as usize)] as u8;
-        let gi2: u8 = self.perm[ii + 1 + (self.perm[jj + 1] as usize)] as u8;
-
-        // Calculate the contribution from the three corners
-        let mut t0: f64 = 0.5 - x0 * x0 - y0 * y0;
-        if t0 < 0.0 {
-            n0 = 0.0;
-        } else {
-            t0 *= t0;
-            n0 = t0 * t0 * grad2(gi0, x0, y0);
-        }
-
-        let mut t1: f64 = 0.5 - x1 * x1 - y1 * y1;
-        if t1 < 0.0 {
-            n1 = 0.0;
-        } else {
-            t1 *= t1;
-            n1 = t1 * t1 * grad2(gi1, x1, y1);
-        }
-
-        let mut t2: f64 = 0.5 - x2 * x2 - y2 * y2;
-        if t2 < 0.0 {
-            n2 = 0.0;
-        } else {
-            t2 *= t2;
-            n2 = t2 * t2 * grad2(gi2, x2, y2);
-        }
+        let xin: f64 = xin * self.frequency.0;
+        let yin: f64 = yin * self.frequency.1;
 
-        // Add contributions from each corner to get the final noise value.
-        // The result is scaled to return values in the interval [-1, 1].
-        40.0 * (n0 + n1 + n2)
+        self.shape(self.raw2d(self.perm.as_slice(), xin, yin))
     }
 
     /// Given a (x, y, z) coordinate, return a value in the interval [-1, 1].
@@ -222,149 +1416,182 @@ impl NoiseGen for Simplex {
     ///     321.0 * 0.02
     /// );
     /// ```
-    #[allow(non_snake_case)]
     fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
-        // Noise contributions from the four corners
-        let mut n0: f64;
-        let mut n1: f64;
-        let mut n2: f64;
-        let mut n3: f64;
+        let xin: f64 = xin * self.frequency.0;
+        let yin: f64 = yin * self.frequency.1;
+        let zin: f64 = zin * self.frequency.2;
 
         // Skew the input space to determine which simplex cell we're in
         let s: f64 = (xin + yin + zin) * F3; // Very nice and simple skew factor for 3D
+
+        self.skewed3d(xin, yin, zin, s)
+    }
+    /// Given a (x, y, z, w) coordinate, return a value in the interval [-1, 1].
+    ///
+    /// The x, y and z frequencies apply to the first three axes; the w axis
+    /// is sampled as is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex};
+    ///
+    /// let simplex = Simplex::new();
+    /// let val = simplex.noise4d(
+    ///     123.0 * 0.02,
+    ///     231.0 * 0.02,
+    ///     321.0 * 0.02,
+    ///     0.5
+    /// );
+    /// ```
+    #[allow(non_snake_case)]
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        let xin: f64 = xin * self.frequency.0;
+        let yin: f64 = yin * self.frequency.1;
+        let zin: f64 = zin * self.frequency.2;
+
+        // Noise contributions from the five corners
+        let n0: f64;
+        let n1: f64;
+        let n2: f64;
+        let n3: f64;
+        let n4: f64;
+
+        // Skew the (x, y, z, w) space to determine which cell of 24 simplices we're in
+        let s: f64 = (xin + yin + zin + win) * F4; // Factor for 4D skewing
         let i: i64 = fast_floor(xin + s);
         let j: i64 = fast_floor(yin + s);
         let k: i64 = fast_floor(zin + s);
-        let t: f64 = ((i + j + k) as f64) * G3;
+        let l: i64 = fast_floor(win + s);
+        let t: f64 = (i.wrapping_add(j).wrapping_add(k).wrapping_add(l) as f64) * G4; // Factor for 4D unskewing
 
-        // Unskew the cell origin back to (x, y, z) space
+        // Unskew the cell origin back to (x, y, z, w) space
         let X0: f64 = (i as f64) - t;
         let Y0: f64 = (j as f64) - t;
         let Z0: f64 = (k as f64) - t;
-        // The x, y, and distances from the cell origin
+        let W0: f64 = (l as f64) - t;
+        // The x, y, z and w distances from the cell origin
         let x0: f64 = xin - X0;
         let y0: f64 = yin - Y0;
         let z0: f64 = zin - Z0;
+        let w0: f64 = win - W0;
 
-        // For the 3D case, the simplex shape is a slightly irregular tetrahedron.
-        // Determine which simplex we are in.
-        let i1: usize; // Offsets for second corner of simplex in (i, j, k) coords
-        let j1: usize;
-        let k1: usize;
-        let i2: usize; // Offsets for third corner of simplex in (i, j, k) coords
-        let j2: usize;
-        let k2: usize;
-        if x0 >= y0 {
-            if y0 >= z0 { // X Y Z order
-                i1 = 1;
-                j1 = 0;
-                k1 = 0;
-                i2 = 1;
-                j2 = 1;
-                k2 = 0;
-            } else if x0 >= z0 { // X Z Y order
-                i1 = 1;
-                j1 = 0;
-                k1 = 0;
-                i2 = 1;
-                j2 = 0;
-                k2 = 1;
-            } else {  // Z X Y order
-                i1 = 0;
-                j1 = 0;
-                k1 = 1;
-                i2 = 1;
-                j2 = 0;
-                k2 = 1;
-            }
-        } else { // x0 < y0
-            if y0 < z0 { // Z Y X order
-                i1 = 0;
-                j1 = 0;
-                k1 = 1;
-                i2 = 0;
-                j2 = 1;
-                k2 = 1;
-            } else if x0 < z0 { // Y Z X order
-                i1 = 0;
-                j1 = 1;
-                k1 = 0;
-                i2 = 0;
-                j2 = 1;
-                k2 = 1;
-            } else { // Y X Z order
-                i1 = 0;
-                j1 = 1;
-                k1 = 0;
-                i2 = 1;
-                j2 = 1;
-                k2 = 0;
-            }
-        }
+        // For the 4D case, the simplex is a 4D shape. To find out which of the
+        // 24 possible simplices we're in, we need to determine the magnitude
+        // ordering of x0, y0, z0 and w0. Six pair-wise comparisons are
+        // performed between each possible pair of the four coordinates, and
+        // the results are used to rank the numbers.
+        let mut rankx: usize = 0;
+        let mut ranky: usize = 0;
+        let mut rankz: usize = 0;
+        let mut rankw: usize = 0;
+        if x0 > y0 { rankx += 1; } else { ranky += 1; }
+        if x0 > z0 { rankx += 1; } else { rankz += 1; }
+        if x0 > w0 { rankx += 1; } else { rankw += 1; }
+        if y0 > z0 { ranky += 1; } else { rankz += 1; }
+        if y0 > w0 { ranky += 1; } else { rankw += 1; }
+        if z0 > w0 { rankz += 1; } else { rankw += 1; }
 
-        // A step of (1, 0, 0) in (i, j, k) means a step of (1 - c, -c, -c) in (x, y, z),
-        // a step of (0, 1, 0) in (i, j, k) means a step of (-c, 1 - c, -c) in (x, y, z), and
-        // a step of (0, 0, 1) in (i, j, k) means a step of (-c, -c, 1 - c) in (x, y, z), where
-        // c = 1/6.
+        // The rank of a coordinate decides at which corner its offset
+        // becomes 1: rank 3 at the second corner, rank 2 at the third and
+        // rank 1 at the fourth.
+        let i1: usize = if rankx >= 3 { 1 } else { 0 };
+        let j1: usize = if ranky >= 3 { 1 } else { 0 };
+        let k1: usize = if rankz >= 3 { 1 } else { 0 };
+        let l1: usize = if rankw >= 3 { 1 } else { 0 };
+        let i2: usize = if rankx >= 2 { 1 } else { 0 };
+        let j2: usize = if ranky >= 2 { 1 } else { 0 };
+        let k2: usize = if rankz >= 2 { 1 } else { 0 };
+        let l2: usize = if rankw >= 2 { 1 } else { 0 };
+        let i3: usize = if rankx >= 1 { 1 } else { 0 };
+        let j3: usize = if ranky >= 1 { 1 } else { 0 };
+        let k3: usize = if rankz >= 1 { 1 } else { 0 };
+        let l3: usize = if rankw >= 1 { 1 } else { 0 };
 
-        // Offsets for second corner in (x, y, z) coords
-        let x1: f64 = x0 - (i1 as f64) + G3;
-        let y1: f64 = y0 - (j1 as f64) + G3;
-        let z1: f64 = z0 - (k1 as f64) + G3;
-        // Offsets for third corner in (x, y, z) coords
-        let x2: f64 = x0 - (i2 as f64) + 2.0 * G3;
-        let y2: f64 = y0 - (j2 as f64) + 2.0 * G3;
-        let z2: f64 = z0 - (k2 as f64) + 2.0 * G3;
-        // Offsets for last corner in (x, y, z) coords
-        let x3: f64 = x0 - 1.0 + 3.0 * G3;
-        let y3: f64 = y0 - 1.0 + 3.0 * G3;
-        let z3: f64 = z0 - 1.0 + 3.0 * G3;
+        // Offsets for second corner in (x, y, z, w) coords
+        let x1: f64 = x0 - (i1 as f64) + G4;
+        let y1: f64 = y0 - (j1 as f64) + G4;
+        let z1: f64 = z0 - (k1 as f64) + G4;
+        let w1: f64 = w0 - (l1 as f64) + G4;
+        // Offsets for third corner in (x, y, z, w) coords
+        let x2: f64 = x0 - (i2 as f64) + 2.0 * G4;
+        let y2: f64 = y0 - (j2 as f64) + 2.0 * G4;
+        let z2: f64 = z0 - (k2 as f64) + 2.0 * G4;
+        let w2: f64 = w0 - (l2 as f64) + 2.0 * G4;
+        // Offsets for fourth corner in (x, y, z, w) coords
+        let x3: f64 = x0 - (i3 as f64) + 3.0 * G4;
+        let y3: f64 = y0 - (j3 as f64) + 3.0 * G4;
+        let z3: f64 = z0 - (k3 as f64) + 3.0 * G4;
+        let w3: f64 = w0 - (l3 as f64) + 3.0 * G4;
+        // Offsets for last corner in (x, y, z, w) coords
+        let x4: f64 = x0 - 1.0 + 4.0 * G4;
+        let y4: f64 = y0 - 1.0 + 4.0 * G4;
+        let z4: f64 = z0 - 1.0 + 4.0 * G4;
+        let w4: f64 = w0 - 1.0 + 4.0 * G4;
+
+        let perm: &[u8] = self.perm.as_slice();
 
         // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
         let ii: usize = (i & 255) as usize;
         let jj: usize = (j & 255) as usize;
         let kk: usize = (k & 255) as usize;
-        // Work out the hashed gradient indices of the four simplex corners
-        let gi0: u8 = self.perm[ii + (self.perm[jj + (self.perm[kk] as usize)] as usize)] as u8;
-        let gi1: u8 = self.perm[ii + i1 + (self.perm[jj + j1 + (self.perm[kk + k1] as usize)] as usize)] as u8;
-        let gi2: u8 = self.perm[ii + i2 + (self.perm[jj + j2 + (self.perm[kk + k2] as usize)] as usize)] as u8;
-        let gi3: u8 = self.perm[ii + 1 + (self.perm[jj + 1 + (self.perm[kk + 1] as usize)] as usize)] as u8;
+        let ll: usize = (l & 255) as usize;
+        // Work out the hashed gradient indices of the five simplex corners
+        let gi0: u8 = self.grad_index(perm_at(perm, ii + (perm_at(perm, jj + (perm_at(perm, kk + (perm_at(perm, ll) as usize)) as usize)) as usize)));
+        let gi1: u8 = self.grad_index(perm_at(perm, ii + i1 + (perm_at(perm, jj + j1 + (perm_at(perm, kk + k1 + (perm_at(perm, ll + l1) as usize)) as usize)) as usize)));
+        let gi2: u8 = self.grad_index(perm_at(perm, ii + i2 + (perm_at(perm, jj + j2 + (perm_at(perm, kk + k2 + (perm_at(perm, ll + l2) as usize)) as usize)) as usize)));
+        let gi3: u8 = self.grad_index(perm_at(perm, ii + i3 + (perm_at(perm, jj + j3 + (perm_at(perm, kk + k3 + (perm_at(perm, ll + l3) as usize)) as usize)) as usize)));
+        let gi4: u8 = self.grad_index(perm_at(perm, ii + 1 + (perm_at(perm, jj + 1 + (perm_at(perm, kk + 1 + (perm_at(perm, ll + 1) as usize)) as usize)) as usize)));
 
-        // Calculate the contribution from the four corners
-        let mut t0: f64 = 0.6 - x0 * x0 - y0 * y0 - z0 * z0;
+        // Calculate the contribution from the five corners
+        let mut t0: f64 = 0.6 - x0 * x0 - y0 * y0 - z0 * z0 - w0 * w0;
         if t0 < 0.0 {
             n0 = 0.0;
         } else {
             t0 *= t0;
-            n0 = t0 * t0 * grad3(gi0, x0, y0, z0);
+            n0 = t0 * t0 * grad4(gi0, x0, y0, z0, w0);
         }
 
-        let mut t1: f64 = 0.6 - x1 * x1 - y1 * y1 - z1 * z1;
+        let mut t1: f64 = 0.6 - x1 * x1 - y1 * y1 - z1 * z1 - w1 * w1;
         if t1 < 0.0 {
             n1 = 0.0;
         } else {
             t1 *= t1;
-            n1 = t1 * t1 * grad3(gi1, x1, y1, z1);
+            n1 = t1 * t1 * grad4(gi1, x1, y1, z1, w1);
         }
 
-        let mut t2: f64 = 0.6 - x2 * x2 - y2 * y2 - z2 * z2;
+        let mut t2: f64 = 0.6 - x2 * x2 - y2 * y2 - z2 * z2 - w2 * w2;
         if t2 < 0.0 {
             n2 = 0.0;
         } else {
             t2 *= t2;
-            n2 = t2 * t2 * grad3(gi2, x2, y2, z2);
+            n2 = t2 * t2 * grad4(gi2, x2, y2, z2, w2);
         }
 
-        let mut t3: f64 = 0.6 - x3 * x3 - y3 * y3 - z3 * z3;
+        let mut t3: f64 = 0.6 - x3 * x3 - y3 * y3 - z3 * z3 - w3 * w3;
         if t3 < 0.0 {
             n3 = 0.0;
         } else {
             t3 *= t3;
-            n3 = t3 * t3 * grad3(gi3, x3, y3, z3);
+            n3 = t3 * t3 * grad4(gi3, x3, y3, z3, w3);
         }
 
-        // Add contributions from each corner to get the final noise value.
-        // The result is scaled to return values in the interval [-1,1].
-        32.0 * (n0 + n1 + n2 + n3)
+        let mut t4: f64 = 0.6 - x4 * x4 - y4 * y4 - z4 * z4 - w4 * w4;
+        if t4 < 0.0 {
+            n4 = 0.0;
+        } else {
+            t4 *= t4;
+            n4 = t4 * t4 * grad4(gi4, x4, y4, z4, w4);
+        }
+
+        // Sum up and scale the result to cover the interval [-1, 1].
+        self.shape(27.0 * (n0 + n1 + n2 + n3 + n4))
+    }
+
+    /// Returns the analytic gradient from `noise2d_deriv`.
+    fn gradient2d(&self, xin: f64, yin: f64) -> (f64, f64) {
+        let (_, dx, dy) = self.noise2d_deriv(xin, yin);
+
+        (dx, dy)
     }
 }
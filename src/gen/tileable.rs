@@ -0,0 +1,85 @@
+//! Seamlessly tiling noise, sampled on a torus.
+
+use core::f64::consts::PI;
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// A generator whose 2D output repeats every `period` units on each axis.
+///
+/// Each axis is wrapped around a circle whose circumference equals its
+/// period, and the two circles span a torus in 4D that is sampled with the
+/// source's `noise4d`. Features keep roughly the same size as in the source,
+/// and opposite edges of a tile line up exactly, which makes it suitable for
+/// texture atlases. The source must implement `noise4d`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Tileable2d<G> {
+    source: G,
+    period: (f64, f64)
+}
+
+impl<G: NoiseGen> Tileable2d<G> {
+    /// Wraps `source` so its output tiles every `period.0` units along x and
+    /// every `period.1` units along y.
+    ///
+    /// `noise2d` samples the source with `noise4d`, so `source` must
+    /// implement it; otherwise sampling panics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex, Tileable2d};
+    ///
+    /// let tile = Tileable2d::new(Simplex::new(), (16.0, 16.0));
+    /// let val = tile.noise2d(3.0, 4.0);
+    /// ```
+    pub fn new(source: G, period: (f64, f64)) -> Tileable2d<G> {
+        Tileable2d { source: source, period: period }
+    }
+
+    /// Returns the tiling period of each axis.
+    pub fn period(&self) -> (f64, f64) {
+        self.period
+    }
+
+    /// Maps a coordinate onto the circle of the given period.
+    #[inline]
+    fn wrap(coord: f64, period: f64) -> (f64, f64) {
+        let radius: f64 = period / (2.0 * PI);
+        let angle: f64 = coord / period * 2.0 * PI;
+
+        (radius * angle.cos(), radius * angle.sin())
+    }
+}
+
+impl<G: NoiseGen> NoiseGen for Tileable2d<G> {
+    /// Given an x coordinate, return a value that repeats every `period.0`.
+    ///
+    /// The circle is sampled with the source's `noise2d`.
+    fn noise1d(&self, xin: f64) -> f64 {
+        let (x, y) = Tileable2d::<G>::wrap(xin, self.period.0);
+
+        self.source.noise2d(x, y)
+    }
+
+    /// Given a (x, y) coordinate, return a value that repeats every
+    /// `period.0` along x and every `period.1` along y.
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let (x, y) = Tileable2d::<G>::wrap(xin, self.period.0);
+        let (z, w) = Tileable2d::<G>::wrap(yin, self.period.1);
+
+        self.source.noise4d(x, y, z, w)
+    }
+
+    /// Tiling in three dimensions would need 6D noise, so this ignores z and
+    /// returns the tiled `noise2d` at (x, y).
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let _ = zin;
+        self.noise2d(xin, yin)
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
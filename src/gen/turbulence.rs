@@ -0,0 +1,121 @@
+//! Turbulence: perturbs the input coordinate of a generator with noise.
+
+use crate::gen::NoiseGen;
+
+// Fixed offsets at which the warp generator is sampled for each displaced
+// axis, so the displacements along different axes are decorrelated.
+static X_OFFSET: (f64, f64, f64, f64) = (0.189422607421875, 0.993926405906677, 0.478164672851563, 0.312957763671875);
+static Y_OFFSET: (f64, f64, f64, f64) = (0.404724121093750, 0.276611328125000, 0.923904418945313, 0.759368896484375);
+static Z_OFFSET: (f64, f64, f64, f64) = (0.821258544921875, 0.171373367309570, 0.684280395507813, 0.046295166015625);
+static W_OFFSET: (f64, f64, f64, f64) = (0.578033447265625, 0.851348876953125, 0.130462646484375, 0.397125244140625);
+
+/// A generator that displaces the input coordinate of its source by the
+/// output of a warp generator before sampling it.
+///
+/// Along each axis, the coordinate is moved by `power` times the warp
+/// generator sampled at `frequency` times the coordinate plus a fixed,
+/// per-axis offset. This is the classic way to distort stripes into marble
+/// veins or rings into wood grain.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Turbulence<G, W> {
+    source: G,
+    warp: W,
+    power: f64,
+    frequency: f64
+}
+
+impl<G: NoiseGen, W: NoiseGen> Turbulence<G, W> {
+    /// Wraps `source`, displacing its input by `warp` with a power and a
+    /// frequency of 1.0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Perlin, Simplex, Turbulence};
+    ///
+    /// let turbulence = Turbulence::new(Perlin::new(), Simplex::new()).with_power(0.25);
+    /// let val = turbulence.noise2d(1.0, 2.0);
+    /// ```
+    pub fn new(source: G, warp: W) -> Turbulence<G, W> {
+        Turbulence {
+            source: source,
+            warp: warp,
+            power: 1.0,
+            frequency: 1.0
+        }
+    }
+
+    /// Sets how far, in input units, the coordinate may be displaced.
+    ///
+    /// A power of 0.0 leaves the source undistorted.
+    pub fn with_power(self, power: f64) -> Turbulence<G, W> {
+        Turbulence { power: power, ..self }
+    }
+
+    /// Sets the frequency at which the warp generator is sampled.
+    ///
+    /// Higher frequencies give a rougher, more jittery distortion.
+    pub fn with_frequency(self, frequency: f64) -> Turbulence<G, W> {
+        Turbulence { frequency: frequency, ..self }
+    }
+
+    /// Returns the displacement power.
+    pub fn power(&self) -> f64 {
+        self.power
+    }
+
+    /// Returns the frequency of the warp generator.
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &G {
+        &self.source
+    }
+
+    /// Returns the warp generator.
+    pub fn warp(&self) -> &W {
+        &self.warp
+    }
+}
+
+impl<G: NoiseGen, W: NoiseGen> NoiseGen for Turbulence<G, W> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        let x: f64 = xin * self.frequency;
+
+        self.source.noise1d(xin + self.power * self.warp.noise1d(x + X_OFFSET.0))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let (x, y): (f64, f64) = (xin * self.frequency, yin * self.frequency);
+        let dx: f64 = self.warp.noise2d(x + X_OFFSET.0, y + X_OFFSET.1);
+        let dy: f64 = self.warp.noise2d(x + Y_OFFSET.0, y + Y_OFFSET.1);
+
+        self.source.noise2d(xin + self.power * dx, yin + self.power * dy)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let (x, y, z): (f64, f64, f64) = (xin * self.frequency, yin * self.frequency, zin * self.frequency);
+        let dx: f64 = self.warp.noise3d(x + X_OFFSET.0, y + X_OFFSET.1, z + X_OFFSET.2);
+        let dy: f64 = self.warp.noise3d(x + Y_OFFSET.0, y + Y_OFFSET.1, z + Y_OFFSET.2);
+        let dz: f64 = self.warp.noise3d(x + Z_OFFSET.0, y + Z_OFFSET.1, z + Z_OFFSET.2);
+
+        self.source.noise3d(xin + self.power * dx, yin + self.power * dy, zin + self.power * dz)
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        let (x, y, z, w): (f64, f64, f64, f64) =
+            (xin * self.frequency, yin * self.frequency, zin * self.frequency, win * self.frequency);
+        let dx: f64 = self.warp.noise4d(x + X_OFFSET.0, y + X_OFFSET.1, z + X_OFFSET.2, w + X_OFFSET.3);
+        let dy: f64 = self.warp.noise4d(x + Y_OFFSET.0, y + Y_OFFSET.1, z + Y_OFFSET.2, w + Y_OFFSET.3);
+        let dz: f64 = self.warp.noise4d(x + Z_OFFSET.0, y + Z_OFFSET.1, z + Z_OFFSET.2, w + Z_OFFSET.3);
+        let dw: f64 = self.warp.noise4d(x + W_OFFSET.0, y + W_OFFSET.1, z + W_OFFSET.2, w + W_OFFSET.3);
+
+        self.source.noise4d(xin + self.power * dx, yin + self.power * dy, zin + self.power * dz, win + self.power * dw)
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        self.source.bounds()
+    }
+}
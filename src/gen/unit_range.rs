@@ -0,0 +1,60 @@
+//! Remaps a generator's output from [-1, 1] to [0, 1].
+
+use crate::gen::NoiseGen;
+use crate::utils::to_unit;
+
+/// A generator adapter whose noise methods return values in [0, 1].
+///
+/// Each value is `(source + 1) / 2`, the remap that colors, heights and most
+/// other consumers want. The result is clamped to [0, 1], so composed
+/// sources that overshoot [-1, 1] slightly still land in range.
+#[derive(Clone, PartialEq, Debug)]
+pub struct UnitRange<N> {
+    source: N
+}
+
+impl<N: NoiseGen> UnitRange<N> {
+    /// Wraps `source` so its output is remapped to [0, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Simplex, UnitRange};
+    ///
+    /// let simplex = UnitRange::new(Simplex::new());
+    /// let val = simplex.noise2d(1.0, 2.0);
+    /// assert!(val >= 0.0 && val <= 1.0);
+    /// ```
+    pub fn new(source: N) -> UnitRange<N> {
+        UnitRange { source: source }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn source(&self) -> &N {
+        &self.source
+    }
+}
+
+impl<N: NoiseGen> NoiseGen for UnitRange<N> {
+    fn noise1d(&self, xin: f64) -> f64 {
+        to_unit(self.source.noise1d(xin))
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        to_unit(self.source.noise2d(xin, yin))
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        to_unit(self.source.noise3d(xin, yin, zin))
+    }
+
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        to_unit(self.source.noise4d(xin, yin, zin, win))
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        let (lower, upper) = self.source.bounds();
+
+        (to_unit(lower), to_unit(upper))
+    }
+}
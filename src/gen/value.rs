@@ -0,0 +1,216 @@
+//! An implementation of [Value Noise]
+//! (https://en.wikipedia.org/wiki/Value_noise).
+//!
+//! Every integer lattice point is hashed through the permutation table into a
+//! pseudo-random value, and the values of the surrounding lattice points are
+//! blended with the quintic fade curve. This is cheaper than gradient noise
+//! and has a blockier look.
+
+use core::fmt;
+use alloc::vec::Vec;
+
+use rand::Rng;
+#[cfg(feature = "std")]
+use rand::thread_rng;
+
+use crate::utils::{ fade, fast_floor, lerp, FadeKind };
+use crate::gen::NoiseGen;
+
+/// A value noise generator.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Value {
+    perm: Vec<u8>
+}
+
+impl Value {
+    /// Initializes a new Value instance with a random seed drawn from
+    /// `thread_rng`.
+    ///
+    /// Needs the `std` feature, which provides the entropy source.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Value;
+    ///
+    /// let value = Value::new();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new() -> Value {
+        Value::from_rng(&mut thread_rng())
+    }
+
+    /// Initializes a new Value instance with a random number generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
+    /// use noisy::gen::Value;
+    ///
+    /// let mut rng: StdRng = StdRng::from_entropy();
+    /// let value = Value::from_rng(&mut rng);
+    /// ```
+    ///
+    /// This also allows you to initialize the instance with a seed:
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
+    /// use noisy::gen::Value;
+    ///
+    /// let mut rng: StdRng = SeedableRng::seed_from_u64(1337);
+    /// let value = Value::from_rng(&mut rng);
+    /// ```
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Value {
+        let p: Vec<u8> = (0..256).map(|_| (rng.next_u32() & 0xff) as u8).collect();
+        let perm: Vec<u8> = (0..512).map(|idx:i32| {p[(idx & 255) as usize]}).collect();
+
+        Value { perm: perm }
+    }
+}
+
+/// Summarizes the permutation table by its length.
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Value")
+            .field("perm_len", &self.perm.len())
+            .finish()
+    }
+}
+
+/// Maps a lattice hash to a value in the interval [-1, 1].
+#[inline]
+fn lattice_value(hash: u8) -> f64 {
+    hash as f64 / 127.5 - 1.0
+}
+
+impl NoiseGen for Value {
+    /// Given an x coordinate, return a value in the interval [-1, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Value};
+    ///
+    /// let value = Value::new();
+    /// let val = value.noise1d(123.0 * 0.04);
+    /// ```
+    fn noise1d(&self, xin: f64) -> f64 {
+        let ix0: i64 = fast_floor(xin); // Integer part of x
+        let fx0: f64 = xin - ix0 as f64; // Fractional part of x
+
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (ix0 & 255) as usize;
+        let ix1: usize = ii + 1;
+
+        // Compute the fade curve.
+        let s: f64 = fade(FadeKind::Quintic, fx0);
+
+        // Look up the hashed lattice values.
+        let v0: f64 = lattice_value(self.perm[ii]);
+        let v1: f64 = lattice_value(self.perm[ix1]);
+
+        lerp(s, v0, v1)
+    }
+
+    /// Given a (x, y) coordinate, return a value in the interval [-1, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Value};
+    ///
+    /// let value = Value::new();
+    /// let val = value.noise2d(
+    ///     123.0 * 0.04,
+    ///     132.0 * 0.04
+    /// );
+    /// ```
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let ix0: i64 = fast_floor(xin); // Integer part of x
+        let iy0: i64 = fast_floor(yin); // Integer part of y
+        let fx0: f64 = xin - ix0 as f64; // Fractional part of x
+        let fy0: f64 = yin - iy0 as f64; // Fractional part of y
+
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (ix0 & 255) as usize;
+        let jj: usize = (iy0 & 255) as usize;
+        let ix1: usize = ii + 1;
+        let iy1: usize = jj + 1;
+
+        // Compute the fade curves.
+        let t: f64 = fade(FadeKind::Quintic, fy0);
+        let s: f64 = fade(FadeKind::Quintic, fx0);
+
+        // Look up the hashed lattice values.
+        let v0: f64 = lattice_value(self.perm[ii + (self.perm[jj] as usize)]);
+        let v1: f64 = lattice_value(self.perm[ii + (self.perm[iy1] as usize)]);
+        let v2: f64 = lattice_value(self.perm[ix1 + (self.perm[jj] as usize)]);
+        let v3: f64 = lattice_value(self.perm[ix1 + (self.perm[iy1] as usize)]);
+
+        let n0: f64 = lerp(t, v0, v1);
+        let n1: f64 = lerp(t, v2, v3);
+
+        lerp(s, n0, n1)
+    }
+
+    /// Given a (x, y, z) coordinate, return a value in the interval [-1, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Value};
+    ///
+    /// let value = Value::new();
+    /// let val = value.noise3d(
+    ///     123.0 * 0.04,
+    ///     231.0 * 0.04,
+    ///     321.0 * 0.04
+    /// );
+    /// ```
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let ix0: i64 = fast_floor(xin); // Integer part of x
+        let iy0: i64 = fast_floor(yin); // Integer part of y
+        let iz0: i64 = fast_floor(zin); // Integer part of z
+        let fx0: f64 = xin - ix0 as f64; // Fractional part of x
+        let fy0: f64 = yin - iy0 as f64; // Fractional part of y
+        let fz0: f64 = zin - iz0 as f64; // Fractional part of z
+
+        // Wrap the integer indices at 256, to avoid indexing perm[] out of bounds
+        let ii: usize = (ix0 & 255) as usize;
+        let jj: usize = (iy0 & 255) as usize;
+        let kk: usize = (iz0 & 255) as usize;
+        let ix1: usize = ii + 1;
+        let iy1: usize = jj + 1;
+        let iz1: usize = kk + 1;
+
+        // Compute the fade curves.
+        let r: f64 = fade(FadeKind::Quintic, fz0);
+        let t: f64 = fade(FadeKind::Quintic, fy0);
+        let s: f64 = fade(FadeKind::Quintic, fx0);
+
+        // Look up the hashed lattice values.
+        let v0: f64 = lattice_value(self.perm[ii + (self.perm[jj + (self.perm[kk] as usize)] as usize)]);
+        let v1: f64 = lattice_value(self.perm[ii + (self.perm[jj + (self.perm[iz1] as usize)] as usize)]);
+        let v2: f64 = lattice_value(self.perm[ii + (self.perm[iy1 + (self.perm[kk] as usize)] as usize)]);
+        let v3: f64 = lattice_value(self.perm[ii + (self.perm[iy1 + (self.perm[iz1] as usize)] as usize)]);
+        let v4: f64 = lattice_value(self.perm[ix1 + (self.perm[jj + (self.perm[kk] as usize)] as usize)]);
+        let v5: f64 = lattice_value(self.perm[ix1 + (self.perm[jj + (self.perm[iz1] as usize)] as usize)]);
+        let v6: f64 = lattice_value(self.perm[ix1 + (self.perm[iy1 + (self.perm[kk] as usize)] as usize)]);
+        let v7: f64 = lattice_value(self.perm[ix1 + (self.perm[iy1 + (self.perm[iz1] as usize)] as usize)]);
+
+        let nx0: f64 = lerp(r, v0, v1);
+        let nx1: f64 = lerp(r, v2, v3);
+        let nx2: f64 = lerp(r, v4, v5);
+        let nx3: f64 = lerp(r, v6, v7);
+
+        let n0: f64 = lerp(t, nx0, nx1);
+        let n1: f64 = lerp(t, nx2, nx3);
+
+        lerp(s, n0, n1)
+    }
+}
@@ -0,0 +1,386 @@
+//! An implementation of [Worley noise]
+//! (https://en.wikipedia.org/wiki/Worley_noise), also known as cellular noise.
+//!
+//! Every unit cell of the lattice holds one feature point, placed by hashing
+//! the cell coordinate through the same kind of permutation table the
+//! gradient noise generators use. The noise value is derived from the
+//! distances between the sampled coordinate and its nearest feature points,
+//! or from the cell holding the nearest one.
+
+use core::fmt;
+use alloc::vec::Vec;
+
+use rand::Rng;
+#[cfg(feature = "std")]
+use rand::thread_rng;
+
+use crate::utils::fast_floor;
+use crate::gen::NoiseGen;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::utils::float::Float;
+
+/// The metric used to measure the distance to a feature point.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DistanceKind {
+    /// Straight-line distance, giving round cells.
+    Euclidean,
+    /// Squared straight-line distance. The cells are the same as with
+    /// `Euclidean`, since squaring keeps the order of distances, but no
+    /// square root is taken. The values within a cell fall off faster.
+    EuclideanSquared,
+    /// Sum of the per-axis distances, giving diamond-shaped cells.
+    Manhattan,
+    /// Largest per-axis distance, giving square cells.
+    Chebyshev
+}
+
+/// The feature point quantity returned as the noise value.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FeatureKind {
+    /// Distance to the nearest feature point.
+    F1,
+    /// Distance to the second nearest feature point.
+    F2,
+    /// Difference between the second nearest and the nearest distances,
+    /// which is zero along the cell borders.
+    F2MinusF1,
+    /// A random value hashed from the cell of the nearest feature point,
+    /// constant across each Voronoi cell. Gives flat-shaded regions, such as
+    /// biomes or stylized stone.
+    CellValue
+}
+
+/// A Worley noise generator.
+#[derive(Clone, PartialEq)]
+pub struct Worley {
+    perm: Vec<u8>,
+    distance: DistanceKind,
+    feature: FeatureKind
+}
+
+impl Worley {
+    /// Initializes a new Worley instance with a random seed drawn from
+    /// `thread_rng`.
+    ///
+    /// Needs the `std` feature, which provides the entropy source.
+    ///
+    /// The instance measures Euclidean distances to the nearest feature point.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Worley;
+    ///
+    /// let worley = Worley::new();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new() -> Worley {
+        Worley::from_rng(&mut thread_rng())
+    }
+
+    /// Initializes a new Worley instance with a random number generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
+    /// use noisy::gen::Worley;
+    ///
+    /// let mut rng: StdRng = SeedableRng::seed_from_u64(1337);
+    /// let worley = Worley::from_rng(&mut rng);
+    /// ```
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Worley {
+        let p: Vec<u8> = (0..256).map(|_| (rng.next_u32() & 0xff) as u8).collect();
+        let perm: Vec<u8> = (0..512).map(|idx:i32| {p[(idx & 255) as usize]}).collect();
+
+        Worley { perm: perm, distance: DistanceKind::Euclidean, feature: FeatureKind::F1 }
+    }
+
+    /// Sets the distance metric.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Worley, DistanceKind};
+    ///
+    /// let worley = Worley::new().with_distance(DistanceKind::Manhattan);
+    /// ```
+    pub fn with_distance(self, distance: DistanceKind) -> Worley {
+        Worley { distance: distance, ..self }
+    }
+
+    /// Sets which feature point distance is returned, or switches to
+    /// returning a value per cell with `FeatureKind::CellValue`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Worley, FeatureKind};
+    ///
+    /// let worley = Worley::new().with_feature(FeatureKind::F2MinusF1);
+    /// ```
+    pub fn with_feature(self, feature: FeatureKind) -> Worley {
+        Worley { feature: feature, ..self }
+    }
+
+    /// Given a (x, y) coordinate, return a color hashed from the cell of the
+    /// nearest feature point.
+    ///
+    /// Every point of a Voronoi cell gets the same color, which makes
+    /// stained-glass and mosaic textures a single call per pixel. The color
+    /// is drawn from the same cell hash as `FeatureKind::CellValue`, so
+    /// neighbouring cells almost always differ. Needs the `image` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use image::{ ImageBuffer, Rgb, RgbImage };
+    /// use noisy::gen::Worley;
+    ///
+    /// let worley = Worley::new();
+    /// let mosaic: RgbImage = ImageBuffer::from_fn(64, 64, |x, y| {
+    ///     Rgb(worley.cell_color2(x as f64 * 0.1, y as f64 * 0.1))
+    /// });
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn cell_color2(&self, xin: f64, yin: f64) -> [u8; 3] {
+        let (_, cell) = self.nearest2d(xin, yin);
+
+        // Skip the entries already used for the feature point offsets.
+        [self.perm[cell + 4], self.perm[cell + 5], self.perm[cell + 6]]
+    }
+
+    /// Finds the two nearest feature point distances around a 2D coordinate,
+    /// along with the hash of the cell holding the nearest one.
+    fn nearest2d(&self, xin: f64, yin: f64) -> ((f64, f64), usize) {
+        let ix: i64 = fast_floor(xin);
+        let iy: i64 = fast_floor(yin);
+        let mut nearest: (f64, f64) = (f64::INFINITY, f64::INFINITY);
+        let mut cell: usize = 0;
+
+        for di in -1..2 {
+            for dj in -1..2 {
+                let (cx, cy): (i64, i64) = (ix.wrapping_add(di), iy.wrapping_add(dj));
+                // Hash the cell coordinate into a feature point
+                let ii: usize = (cx & 255) as usize;
+                let jj: usize = (cy & 255) as usize;
+                let hash: usize = self.perm[ii + self.perm[jj] as usize] as usize;
+
+                let dx: f64 = cx as f64 + self.offset(hash) - xin;
+                let dy: f64 = cy as f64 + self.offset(hash + 1) - yin;
+                insert(&mut nearest, &mut cell, self.measure(dx, dy, 0.0, 0.0), hash);
+            }
+        }
+
+        (nearest, cell)
+    }
+
+    /// Returns the position of a feature point within its cell, in [0, 1).
+    #[inline]
+    fn offset(&self, hash: usize) -> f64 {
+        (self.perm[hash] as f64 + 0.5) / 256.0
+    }
+
+    /// Measures the distance of a residual vector.
+    #[inline]
+    fn measure(&self, dx: f64, dy: f64, dz: f64, dw: f64) -> f64 {
+        match self.distance {
+            DistanceKind::Euclidean => (dx * dx + dy * dy + dz * dz + dw * dw).sqrt(),
+            DistanceKind::EuclideanSquared => dx * dx + dy * dy + dz * dz + dw * dw,
+            DistanceKind::Manhattan => dx.abs() + dy.abs() + dz.abs() + dw.abs(),
+            DistanceKind::Chebyshev => dx.abs().max(dy.abs()).max(dz.abs()).max(dw.abs())
+        }
+    }
+
+    /// Turns the two nearest distances, or the hash of the nearest feature
+    /// point's cell, into a value in the interval [-1, 1].
+    ///
+    /// Distances are scaled by the farthest a point can be from the feature
+    /// point of its own cell, which bounds F1.
+    fn finish(&self, f1: f64, f2: f64, cell: usize, dims: usize) -> f64 {
+        let dist: f64 = match self.feature {
+            FeatureKind::F1 => f1,
+            FeatureKind::F2 => f2,
+            FeatureKind::F2MinusF1 => f2 - f1,
+            // Skip the entries already used for the feature point offsets.
+            FeatureKind::CellValue => return (self.perm[cell + 4] as f64 + 0.5) / 128.0 - 1.0
+        };
+        let max: f64 = match self.distance {
+            DistanceKind::Euclidean => (dims as f64).sqrt(),
+            DistanceKind::EuclideanSquared | DistanceKind::Manhattan => dims as f64,
+            DistanceKind::Chebyshev => 1.0
+        };
+
+        (2.0 * dist / max - 1.0).min(1.0)
+    }
+}
+
+/// Prints the settings; the permutation table is summarized by its length.
+impl fmt::Debug for Worley {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Worley")
+            .field("perm_len", &self.perm.len())
+            .field("distance", &self.distance)
+            .field("feature", &self.feature)
+            .finish()
+    }
+}
+
+/// Keeps the two smallest distances seen so far, along with the hash of the
+/// cell holding the nearest feature point.
+#[inline]
+fn insert(nearest: &mut (f64, f64), cell: &mut usize, dist: f64, hash: usize) {
+    if dist < nearest.0 {
+        nearest.1 = nearest.0;
+        nearest.0 = dist;
+        *cell = hash;
+    } else if dist < nearest.1 {
+        nearest.1 = dist;
+    }
+}
+
+impl NoiseGen for Worley {
+    /// Given an x coordinate, return a value in the interval [-1, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Worley};
+    ///
+    /// let worley = Worley::new();
+    /// let val = worley.noise1d(123.0 * 0.04);
+    /// ```
+    fn noise1d(&self, xin: f64) -> f64 {
+        let ix: i64 = fast_floor(xin);
+        let mut nearest: (f64, f64) = (f64::INFINITY, f64::INFINITY);
+        let mut cell: usize = 0;
+
+        for di in -1..2 {
+            let cx: i64 = ix.wrapping_add(di);
+            let hash: usize = self.perm[(cx & 255) as usize] as usize;
+
+            let dx: f64 = cx as f64 + self.offset(hash) - xin;
+            insert(&mut nearest, &mut cell, self.measure(dx, 0.0, 0.0, 0.0), hash);
+        }
+
+        self.finish(nearest.0, nearest.1, cell, 1)
+    }
+
+    /// Given a (x, y) coordinate, return a value in the interval [-1, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Worley};
+    ///
+    /// let worley = Worley::new();
+    /// let val = worley.noise2d(
+    ///     123.0 * 0.04,
+    ///     132.0 * 0.04
+    /// );
+    /// ```
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        let (nearest, cell) = self.nearest2d(xin, yin);
+
+        self.finish(nearest.0, nearest.1, cell, 2)
+    }
+
+    /// Given a (x, y, z) coordinate, return a value in the interval [-1, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Worley};
+    ///
+    /// let worley = Worley::new();
+    /// let val = worley.noise3d(
+    ///     123.0 * 0.04,
+    ///     231.0 * 0.04,
+    ///     321.0 * 0.04
+    /// );
+    /// ```
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        let ix: i64 = fast_floor(xin);
+        let iy: i64 = fast_floor(yin);
+        let iz: i64 = fast_floor(zin);
+        let mut nearest: (f64, f64) = (f64::INFINITY, f64::INFINITY);
+        let mut cell: usize = 0;
+
+        for di in -1..2 {
+            for dj in -1..2 {
+                for dk in -1..2 {
+                    let (cx, cy, cz): (i64, i64, i64) = (ix.wrapping_add(di), iy.wrapping_add(dj), iz.wrapping_add(dk));
+                    // Hash the cell coordinate into a feature point
+                    let ii: usize = (cx & 255) as usize;
+                    let jj: usize = (cy & 255) as usize;
+                    let kk: usize = (cz & 255) as usize;
+                    let hash: usize = self.perm[ii + (self.perm[jj + (self.perm[kk] as usize)] as usize)] as usize;
+
+                    let dx: f64 = cx as f64 + self.offset(hash) - xin;
+                    let dy: f64 = cy as f64 + self.offset(hash + 1) - yin;
+                    let dz: f64 = cz as f64 + self.offset(hash + 2) - zin;
+                    insert(&mut nearest, &mut cell, self.measure(dx, dy, dz, 0.0), hash);
+                }
+            }
+        }
+
+        self.finish(nearest.0, nearest.1, cell, 3)
+    }
+
+    /// Given a (x, y, z, w) coordinate, return a value in the interval
+    /// [-1, 1].
+    ///
+    /// The feature points fill the 4D lattice and the search covers the
+    /// 3x3x3x3 neighbourhood of the cell. Sweeping w animates the cells
+    /// smoothly: feature points drift in and out of each 3D slice, rather
+    /// than jumping as they would if w were folded into a 3D coordinate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{NoiseGen, Worley};
+    ///
+    /// let worley = Worley::new();
+    /// let time: f64 = 0.25;
+    /// let val = worley.noise4d(
+    ///     123.0 * 0.04,
+    ///     231.0 * 0.04,
+    ///     321.0 * 0.04,
+    ///     time
+    /// );
+    /// ```
+    fn noise4d(&self, xin: f64, yin: f64, zin: f64, win: f64) -> f64 {
+        let ix: i64 = fast_floor(xin);
+        let iy: i64 = fast_floor(yin);
+        let iz: i64 = fast_floor(zin);
+        let iw: i64 = fast_floor(win);
+        let mut nearest: (f64, f64) = (f64::INFINITY, f64::INFINITY);
+        let mut cell: usize = 0;
+
+        for di in -1..2 {
+            for dj in -1..2 {
+                for dk in -1..2 {
+                    for dl in -1..2 {
+                        let (cx, cy, cz, cw): (i64, i64, i64, i64) = (ix.wrapping_add(di), iy.wrapping_add(dj), iz.wrapping_add(dk), iw.wrapping_add(dl));
+                        // Hash the cell coordinate into a feature point
+                        let ii: usize = (cx & 255) as usize;
+                        let jj: usize = (cy & 255) as usize;
+                        let kk: usize = (cz & 255) as usize;
+                        let ll: usize = (cw & 255) as usize;
+                        let hash: usize = self.perm[ii + (self.perm[jj + (self.perm[kk + (self.perm[ll] as usize)] as usize)] as usize)] as usize;
+
+                        let dx: f64 = cx as f64 + self.offset(hash) - xin;
+                        let dy: f64 = cy as f64 + self.offset(hash + 1) - yin;
+                        let dz: f64 = cz as f64 + self.offset(hash + 2) - zin;
+                        let dw: f64 = cw as f64 + self.offset(hash + 3) - win;
+                        insert(&mut nearest, &mut cell, self.measure(dx, dy, dz, dw), hash);
+                    }
+                }
+            }
+        }
+
+        self.finish(nearest.0, nearest.1, cell, 4)
+    }
+}
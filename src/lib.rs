@@ -1,4 +1,5 @@
-#![feature(rand)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(all(test, feature = "bench"), feature(test))]
 
 /*!
 # noisy
@@ -33,12 +34,11 @@ fn main() {
 includes a variety of generators including:
 
 * Simplex noise.
-* Imporoved Perlin noise.
-* Perlin noise (not implemented).
+* OpenSimplex noise.
+* Perlin noise, using Ken Perlin's improved algorithm.
 
 ## Compilation
-You will need the last rust compiler from the master branch.
-If you encounter problems, make sure you have the last version before creating an issue.
+**noisy** builds with the stable Rust compiler.
 
 ```ignore
 git clone --recursive git://github.com/cacteye/noisy.git
@@ -52,26 +52,66 @@ You can build the documentation using:
 cargo doc
 ```
 
-You can build the included examples using:
+You can run the tests using:
 
 ```ignore
 cargo test
 ```
+
+The benchmarks need a nightly compiler:
+
+```ignore
+cargo bench --features bench
+```
+
+## `no_std`
+**noisy** only needs `alloc` when built without the default `std` feature:
+
+```ignore
+[dependencies.noisy]
+version = "*"
+default-features = false
+```
+
+The generators are then seeded with `from_seed`, `from_rng` or `from_perm`,
+since `new` needs an entropy source. The `rayon` and `image` features
+require `std`.
 */
 
 #![warn(missing_docs)]
+// The generators spell out every field in constructors, declare locals ahead
+// of the branches that assign them, and keep `new` seeding from entropy
+// rather than exposing it as `Default`.
+#![allow(clippy::redundant_field_names, clippy::needless_late_init, clippy::new_without_default)]
 
+extern crate alloc;
 
 extern crate rand;
+extern crate rand_xorshift;
 
-#[cfg(test)]
+extern crate libm;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "image")]
+extern crate image;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+#[cfg(all(test, feature = "bench"))]
 extern crate test;
 
 pub mod utils;
 pub mod gen;
+pub mod map;
 
 #[cfg(test)]
 mod tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "bench"))]
 mod bench;
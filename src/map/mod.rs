@@ -0,0 +1,5 @@
+//! Containers for pre-sampled noise.
+
+pub use crate::map::noise_map::NoiseMap;
+
+mod noise_map;
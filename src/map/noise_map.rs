@@ -0,0 +1,170 @@
+//! A 2D heightmap sampled from a generator in one call.
+
+use alloc::vec::Vec;
+
+use crate::gen::NoiseGen;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "image")]
+use image::{ GrayImage, ImageBuffer, Luma };
+
+/// A `width` by `height` grid of noise values stored in row-major order.
+#[derive(Clone, PartialEq)]
+pub struct NoiseMap {
+    values: Vec<f64>,
+    width: usize,
+    height: usize
+}
+
+impl NoiseMap {
+    /// Samples `gen` over a `width` by `height` grid.
+    ///
+    /// The value at column `x` and row `y` is taken at
+    /// `(origin.0 + x * step.0, origin.1 + y * step.1)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    /// use noisy::map::NoiseMap;
+    ///
+    /// let map = NoiseMap::generate2d(&Simplex::new(), 64, 64, (0.0, 0.0), (0.02, 0.02));
+    /// let val = map.get(10, 20);
+    /// ```
+    pub fn generate2d<G: NoiseGen>(gen: &G, width: usize, height: usize, origin: (f64, f64), step: (f64, f64)) -> NoiseMap {
+        NoiseMap {
+            values: gen.fill_grid2d(width, height, origin, step),
+            width: width,
+            height: height
+        }
+    }
+
+    /// Same as `generate2d`, but the rows are sampled in parallel on rayon's
+    /// thread pool. Requires the `rayon` feature.
+    ///
+    /// Generators don't change once built, so any `Sync` generator can be
+    /// shared between the threads. The map is identical to the one
+    /// `generate2d` returns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    /// use noisy::map::NoiseMap;
+    ///
+    /// let map = NoiseMap::generate2d_parallel(&Simplex::new(), 1024, 1024, (0.0, 0.0), (0.02, 0.02));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn generate2d_parallel<G: NoiseGen + Sync>(gen: &G, width: usize, height: usize, origin: (f64, f64), step: (f64, f64)) -> NoiseMap {
+        let mut values: Vec<f64> = vec![0.0; width * height];
+        if width > 0 {
+            values.par_chunks_mut(width).enumerate().for_each(|(y, row)| {
+                let yin: f64 = origin.1 + y as f64 * step.1;
+                for (x, sample) in row.iter_mut().enumerate() {
+                    *sample = gen.noise2d(origin.0 + x as f64 * step.0, yin);
+                }
+            });
+        }
+
+        NoiseMap { values: values, width: width, height: height }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the value at column `x` and row `y`.
+    ///
+    /// Panics if the coordinate lies outside the map.
+    pub fn get(&self, x: usize, y: usize) -> f64 {
+        assert!(x < self.width && y < self.height,
+                "map index ({}, {}) out of bounds for {}x{}", x, y, self.width, self.height);
+
+        self.values[y * self.width + x]
+    }
+
+    /// Returns the smallest value in the map, or positive infinity if the map
+    /// is empty.
+    pub fn min(&self) -> f64 {
+        self.values.iter().fold(f64::INFINITY, |acc: f64, val| acc.min(*val))
+    }
+
+    /// Returns the largest value in the map, or negative infinity if the map
+    /// is empty.
+    pub fn max(&self) -> f64 {
+        self.values.iter().fold(f64::NEG_INFINITY, |acc: f64, val| acc.max(*val))
+    }
+
+    /// Rescales the values in place so the smallest becomes 0.0 and the
+    /// largest 1.0.
+    ///
+    /// Multi-octave pipelines rarely reach the ends of their nominal range,
+    /// so this stretches the values actually produced over [0, 1], e.g.
+    /// before writing an image. A flat map becomes 0.5 everywhere and an
+    /// empty map is left alone.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::{Fbm, Simplex};
+    /// use noisy::map::NoiseMap;
+    ///
+    /// let mut map = NoiseMap::generate2d(&Fbm::new(Simplex::new(), 6), 64, 64, (0.0, 0.0), (0.02, 0.02));
+    /// map.normalize();
+    /// assert_eq!((map.min(), map.max()), (0.0, 1.0));
+    /// ```
+    pub fn normalize(&mut self) {
+        let (min, max) = (self.min(), self.max());
+        let range: f64 = max - min;
+
+        for val in self.values.iter_mut() {
+            *val = if range > 0.0 { (*val - min) / range } else { 0.5 };
+        }
+    }
+
+    /// Returns all values, row after row.
+    pub fn as_slice(&self) -> &[f64] {
+        self.values.as_slice()
+    }
+
+    /// Renders the map as a grayscale image, e.g. to inspect a heightmap as
+    /// a PNG. Requires the `image` feature.
+    ///
+    /// Values are stretched from the map's own finite minimum and maximum to
+    /// 0..=255, so the darkest pixel is 0 and the brightest 255. NaN and
+    /// infinite values map to 0, as does every value of a flat map.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use noisy::gen::Simplex;
+    /// use noisy::map::NoiseMap;
+    ///
+    /// let map = NoiseMap::generate2d(&Simplex::new(), 64, 64, (0.0, 0.0), (0.02, 0.02));
+    /// let image = map.to_gray_image();
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn to_gray_image(&self) -> GrayImage {
+        let finite = self.values.iter().cloned().filter(|val| val.is_finite());
+        let (min, max) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max): (f64, f64), val| {
+            (min.min(val), max.max(val))
+        });
+        let range: f64 = max - min;
+
+        ImageBuffer::from_fn(self.width as u32, self.height as u32, |x, y| {
+            let val: f64 = self.values[y as usize * self.width + x as usize];
+            if !val.is_finite() || range <= 0.0 {
+                return Luma([0]);
+            }
+
+            Luma([((val - min) / range * 255.0).round() as u8])
+        })
+    }
+}
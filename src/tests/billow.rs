@@ -0,0 +1,58 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Billow, Fbm, Simplex };
+use crate::gen::modifier::Abs;
+
+#[test]
+fn test_billow_single_octave_is_abs() {
+    let simplex = Simplex::new();
+    let billow = Billow::new(simplex.clone(), 1);
+    let abs = Abs::new(simplex.clone());
+    for _ in 0..10000 {
+        let (x, y, z, w): (f64, f64, f64, f64) = (random(), random(), random(), random());
+        assert_eq!(billow.noise1d(x), abs.noise1d(x));
+        assert_eq!(billow.noise2d(x, y), abs.noise2d(x, y));
+        assert_eq!(billow.noise3d(x, y, z), abs.noise3d(x, y, z));
+        assert_eq!(billow.noise4d(x, y, z, w), abs.noise4d(x, y, z, w));
+    }
+}
+
+#[test]
+fn test_billow_range() {
+    let billow = Billow::new(Simplex::new(), 6).with_persistence(0.7).with_lacunarity(1.9);
+    assert_eq!(billow.bounds(), (-1.0, 1.0));
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 50.0, random::<f64>() * 50.0, random::<f64>() * 50.0);
+        let val2 = billow.noise2d(x, y);
+        let val3 = billow.noise3d(x, y, z);
+        assert!(val2 >= -1.0 && val2 <= 1.0);
+        assert!(val3 >= -1.0 && val3 <= 1.0);
+    }
+}
+
+#[test]
+fn test_billow_matches_fbm_of_abs() {
+    let simplex = Simplex::new();
+    let billow = Billow::new(simplex.clone(), 4).with_persistence(0.6);
+    let composed = Fbm::new(Abs::new(simplex.clone()), 4).with_persistence(0.6);
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0);
+        assert_eq!(billow.noise2d(x, y), composed.noise2d(x, y));
+    }
+    assert!(*billow.source() == simplex);
+}
+
+#[test]
+fn test_billow_axis_lacunarity() {
+    let simplex = Simplex::new();
+    let scalar = Billow::new(simplex.clone(), 4).with_lacunarity(1.8);
+    let uniform = Billow::new(simplex.clone(), 4).with_axis_lacunarity((1.8, 1.8, 1.8));
+    let stretched = Billow::new(simplex.clone(), 4).with_axis_lacunarity((1.8, 3.0, 1.8));
+    let composed = Fbm::new(Abs::new(simplex.clone()), 4).with_axis_lacunarity((1.8, 3.0, 1.8));
+    assert!(scalar == uniform);
+    for _ in 0..1000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+        assert_eq!(uniform.noise3d(x, y, z), scalar.noise3d(x, y, z));
+        assert_eq!(stretched.noise3d(x, y, z), composed.noise3d(x, y, z));
+    }
+}
@@ -0,0 +1,24 @@
+use crate::gen::{ NoiseGen, Blend, Checkerboard, Constant };
+
+#[test]
+fn test_blend_endpoints() {
+    let first = Constant::new(0.25);
+    let second = Constant::new(-0.5);
+
+    let low = Blend::new(first, second, Constant::new(-1.0));
+    assert_eq!(low.noise1d(0.3), 0.25);
+    assert_eq!(low.noise2d(0.3, 0.7), 0.25);
+    assert_eq!(low.noise3d(0.3, 0.7, 1.1), 0.25);
+
+    let high = Blend::new(first, second, Constant::new(1.0));
+    assert_eq!(high.noise1d(0.3), -0.5);
+    assert_eq!(high.noise2d(0.3, 0.7), -0.5);
+    assert_eq!(high.noise3d(0.3, 0.7, 1.1), -0.5);
+}
+
+#[test]
+fn test_blend_midpoint() {
+    let blend = Blend::new(Checkerboard::new(), Constant::new(0.5), Constant::new(0.0));
+    assert_eq!(blend.noise2d(0.5, 0.5), 0.75);
+    assert_eq!(blend.noise2d(1.5, 0.5), -0.25);
+}
@@ -0,0 +1,38 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Fbm, Perlin, Simplex, Value };
+use crate::gen::combine::Add;
+
+#[test]
+fn test_boxed_layers() {
+    let simplex = Simplex::new();
+    let perlin = Perlin::new();
+    let layers: Vec<Box<dyn NoiseGen>> = vec![
+        Box::new(simplex.clone()),
+        Box::new(perlin.clone()),
+        Box::new(Fbm::new(Simplex::new(), 3)),
+        Box::new(Value::new())
+    ];
+
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random(), random());
+        for layer in layers.iter() {
+            let val = layer.noise2d(x, y);
+            assert!(val >= -1.0 && val <= 1.0);
+        }
+        assert_eq!(layers[0].noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(layers[1].noise2d(x, y), perlin.noise2d(x, y));
+    }
+}
+
+#[test]
+fn test_borrowed_in_combinator() {
+    let simplex = Simplex::new();
+    let boxed: Box<dyn NoiseGen> = Box::new(Perlin::new());
+    let sum = Add::new(&simplex, &boxed);
+
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random(), random());
+        assert_eq!(sum.noise2d(x, y), 0.5 * (simplex.noise2d(x, y) + boxed.noise2d(x, y)));
+    }
+}
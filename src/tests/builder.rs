@@ -0,0 +1,35 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, NoiseBuilder, Fbm, Simplex };
+use crate::gen::modifier::{ ScaleBias, Clamp };
+
+#[test]
+fn test_builder_pipeline() {
+    let simplex = Simplex::new();
+    let built = NoiseBuilder::new(simplex.clone())
+        .fbm(4)
+        .scale_bias(0.5, 0.5)
+        .clamp(0.2, 0.8)
+        .build();
+    let manual = Clamp::new(ScaleBias::new(Fbm::new(simplex, 4), 0.5, 0.5), 0.2, 0.8);
+
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+        let val = built.noise2d(x, y);
+        assert!(val >= 0.2 && val <= 0.8);
+        assert_eq!(val, manual.noise2d(x, y));
+        assert_eq!(built.noise3d(x, y, z), manual.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_builder_abs() {
+    // Abs folds onto [-1, 1] as 2|v| - 1, so halving and shifting down maps
+    // it onto [-1, 0].
+    let built = NoiseBuilder::new(Simplex::new()).abs().scale_bias(-0.5, -0.5).build();
+    for _ in 0..10000 {
+        let (x, y): (f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0);
+        let val = built.noise2d(x, y);
+        assert!(val >= -1.0 && val <= 0.0);
+    }
+}
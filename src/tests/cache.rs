@@ -0,0 +1,74 @@
+use std::cell::Cell;
+
+use crate::gen::{ NoiseGen, Cache, Simplex };
+
+/// Counts how often each generator method is called.
+struct Counting {
+    source: Simplex,
+    calls: Cell<usize>
+}
+
+impl Counting {
+    fn new() -> Counting {
+        Counting { source: Simplex::new(), calls: Cell::new(0) }
+    }
+
+    fn count(&self) {
+        self.calls.set(self.calls.get() + 1);
+    }
+}
+
+impl NoiseGen for Counting {
+    fn noise1d(&self, xin: f64) -> f64 {
+        self.count();
+        self.source.noise1d(xin)
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        self.count();
+        self.source.noise2d(xin, yin)
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        self.count();
+        self.source.noise3d(xin, yin, zin)
+    }
+}
+
+#[test]
+fn test_cache_hits() {
+    let cache = Cache::new(Counting::new());
+
+    let first = cache.noise2d(1.5, 2.5);
+    assert_eq!(cache.noise2d(1.5, 2.5), first);
+    assert_eq!(cache.noise2d(1.5, 2.5), first);
+    assert_eq!(cache.source().calls.get(), 1);
+    assert_eq!(first, cache.source().source.noise2d(1.5, 2.5));
+
+    cache.noise3d(1.0, 2.0, 3.0);
+    cache.noise3d(1.0, 2.0, 3.0);
+    assert_eq!(cache.source().calls.get(), 2);
+}
+
+#[test]
+fn test_cache_misses() {
+    let cache = Cache::new(Counting::new());
+
+    cache.noise2d(1.5, 2.5);
+    cache.noise2d(1.5, 2.75);
+    assert_eq!(cache.source().calls.get(), 2);
+
+    // The same leading coordinates in another dimension are a different
+    // sample.
+    cache.noise1d(1.5);
+    cache.noise2d(1.5, 0.0);
+    assert_eq!(cache.source().calls.get(), 4);
+
+    // Only the last sample is remembered.
+    cache.noise2d(1.5, 2.5);
+    assert_eq!(cache.source().calls.get(), 5);
+
+    cache.clear();
+    cache.noise2d(1.5, 2.5);
+    assert_eq!(cache.source().calls.get(), 6);
+}
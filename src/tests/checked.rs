@@ -0,0 +1,44 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Checked, Constant, Simplex };
+use crate::gen::modifier::ScaleBias;
+
+#[test]
+fn test_checked_passes_values_through() {
+    let simplex = Simplex::new();
+    let checked = Checked::new(simplex.clone());
+    for _ in 0..1000 {
+        let (x, y, z, w): (f64, f64, f64, f64) = (random(), random(), random(), random());
+        assert_eq!(checked.noise1d(x), simplex.noise1d(x));
+        assert_eq!(checked.noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(checked.noise3d(x, y, z), simplex.noise3d(x, y, z));
+        assert_eq!(checked.noise4d(x, y, z, w), simplex.noise4d(x, y, z, w));
+    }
+    assert_eq!(checked.bounds(), simplex.bounds());
+}
+
+/// Scaling zero by infinity yields NaN.
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "noise2d at [1.5, 2.5] returned NaN")]
+fn test_checked_catches_nan() {
+    let broken = Checked::new(ScaleBias::new(Constant::new(0.0), f64::INFINITY, 0.0));
+    broken.noise2d(1.5, 2.5);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "returned inf")]
+fn test_builder_pipeline_is_checked() {
+    use crate::gen::NoiseBuilder;
+
+    let broken = NoiseBuilder::new(Constant::new(1.0)).scale_bias(f64::INFINITY, 0.0).build();
+    broken.noise3d(0.0, 0.0, 0.0);
+}
+
+#[test]
+#[cfg(not(debug_assertions))]
+fn test_checked_unguarded_in_release() {
+    let broken = Checked::new(ScaleBias::new(Constant::new(0.0), f64::INFINITY, 0.0));
+    assert!(broken.noise2d(1.5, 2.5).is_nan());
+}
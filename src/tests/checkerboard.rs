@@ -1,6 +1,7 @@
-use std::rand::random;
+use core::f64::consts::FRAC_PI_4;
+use rand::random;
 
-use gen::{ NoiseGen, Checkerboard };
+use crate::gen::{ NoiseGen, Checkerboard };
 
 #[test]
 fn test_checkerboard_new() {
@@ -10,7 +11,7 @@ fn test_checkerboard_new() {
 #[test]
 fn test_checkerboard_noise1d() {
     let checkerboard = Checkerboard::new();
-    for _ in range(0us, 10000) {
+    for _ in 0..10000 {
         checkerboard.noise1d(random());
     }
 }
@@ -18,7 +19,7 @@ fn test_checkerboard_noise1d() {
 #[test]
 fn test_checkerboard_noise2d() {
     let checkerboard = Checkerboard::new();
-    for _ in range(0us, 10000) {
+    for _ in 0..10000 {
         checkerboard.noise2d(
             random(),
             random()
@@ -29,7 +30,7 @@ fn test_checkerboard_noise2d() {
 #[test]
 fn test_checkerboard_noise3d() {
     let checkerboard = Checkerboard::new();
-    for _ in range(0us, 10000) {
+    for _ in 0..10000 {
         checkerboard.noise3d(
             random(),
             random(),
@@ -37,3 +38,124 @@ fn test_checkerboard_noise3d() {
         );
     }
 }
+
+#[test]
+fn test_checkerboard_zero_rotation() {
+    let checkerboard = Checkerboard::new();
+    let rotated = Checkerboard::new().with_rotation(0.0);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        let (x, y, z) = (x * 20.0 - 10.0, y * 20.0 - 10.0, z * 20.0 - 10.0);
+        assert_eq!(rotated.noise2d(x, y), checkerboard.noise2d(x, y));
+        assert_eq!(rotated.noise3d(x, y, z), checkerboard.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_checkerboard_diagonal_rotation() {
+    let checkerboard = Checkerboard::new();
+    let rotated = Checkerboard::new().with_rotation(FRAC_PI_4);
+
+    assert_eq!(checkerboard.noise2d(1.0, 0.2), -1.0);
+    assert_eq!(rotated.noise2d(1.0, 0.2), 1.0);
+    assert_eq!(rotated.noise2d(0.1, 1.3), -1.0);
+    assert_eq!(rotated.noise2d(2.5, 0.25), 1.0);
+    assert_eq!(checkerboard.noise2d(0.4, 1.6), -1.0);
+    assert_eq!(rotated.noise2d(0.4, 1.6), 1.0);
+    assert_eq!(rotated.noise3d(0.4, 1.6, 0.5), 1.0);
+}
+
+#[test]
+fn test_checkerboard_seed_is_noop() {
+    use crate::gen::Seedable;
+
+    let mut checkerboard = Checkerboard::new();
+    checkerboard.set_seed(99);
+    assert_eq!(checkerboard.seed(), 0);
+    assert_eq!(checkerboard.noise2d(1.5, 0.5), Checkerboard::new().noise2d(1.5, 0.5));
+}
+
+#[test]
+fn test_checkerboard_size() {
+    let checkerboard = Checkerboard::new();
+    let doubled = Checkerboard::new().with_size(2.0);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        let (x, y, z) = (x * 20.0 - 10.0, y * 20.0 - 10.0, z * 20.0 - 10.0);
+        assert_eq!(doubled.noise1d(x * 2.0), checkerboard.noise1d(x));
+        assert_eq!(doubled.noise2d(x * 2.0, y * 2.0), checkerboard.noise2d(x, y));
+        assert_eq!(doubled.noise3d(x * 2.0, y * 2.0, z * 2.0), checkerboard.noise3d(x, y, z));
+    }
+
+    // Cells two units wide.
+    assert_eq!(doubled.noise1d(0.5), doubled.noise1d(1.5));
+    assert!(doubled.noise1d(1.5) != doubled.noise1d(2.5));
+    assert_eq!(doubled.noise2d(0.5, 0.5), doubled.noise2d(1.5, 1.5));
+    assert!(doubled.noise2d(1.5, 0.5) != doubled.noise2d(2.5, 0.5));
+    assert_eq!(doubled.noise3d(0.5, 0.5, 0.5), doubled.noise3d(1.5, 1.5, 1.5));
+    assert!(doubled.noise3d(0.5, 0.5, 1.5) != doubled.noise3d(0.5, 0.5, 2.5));
+}
+
+#[test]
+fn test_checkerboard_zero_soften_is_hard() {
+    let checkerboard = Checkerboard::new();
+    let softened = Checkerboard::new().with_soften(0.0);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        let (x, y, z) = (x * 20.0 - 10.0, y * 20.0 - 10.0, z * 20.0 - 10.0);
+        assert_eq!(softened.noise1d(x), checkerboard.noise1d(x));
+        assert_eq!(softened.noise2d(x, y), checkerboard.noise2d(x, y));
+        assert_eq!(softened.noise3d(x, y, z), checkerboard.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_checkerboard_soften_continuous_across_borders() {
+    let hard = Checkerboard::new();
+    let soft = Checkerboard::new().with_soften(0.25);
+    let eps: f64 = 1e-9;
+
+    for i in -5..6 {
+        let border: f64 = i as f64;
+        // The hard pattern jumps by 2 across every border.
+        assert_eq!((hard.noise1d(border + eps) - hard.noise1d(border - eps)).abs(), 2.0);
+        assert_eq!((hard.noise2d(border + eps, 0.5) - hard.noise2d(border - eps, 0.5)).abs(), 2.0);
+
+        assert!((soft.noise1d(border + eps) - soft.noise1d(border - eps)).abs() < 1e-6);
+        assert!((soft.noise2d(border + eps, 0.3) - soft.noise2d(border - eps, 0.3)).abs() < 1e-6);
+        assert!((soft.noise2d(0.3, border + eps) - soft.noise2d(0.3, border - eps)).abs() < 1e-6);
+        assert!((soft.noise3d(0.3, 0.6, border + eps) - soft.noise3d(0.3, 0.6, border - eps)).abs() < 1e-6);
+        assert_eq!(soft.noise1d(border), 0.0);
+    }
+
+    // Away from the borders the pattern is untouched.
+    for _ in 0..10000 {
+        let x: f64 = random::<f64>() * 0.7 + 0.15 - 3.0;
+        let y: f64 = random::<f64>() * 0.7 + 0.15 + 4.0;
+        assert_eq!(soft.noise1d(x), hard.noise1d(x));
+        assert_eq!(soft.noise2d(x, y), hard.noise2d(x, y));
+    }
+
+    // Between them the value stays in range.
+    for _ in 0..10000 {
+        let (x, y): (f64, f64) = (random(), random());
+        let val = soft.noise2d(x * 20.0 - 10.0, y * 20.0 - 10.0);
+        assert!(val >= -1.0 && val <= 1.0);
+    }
+
+    // Just inside the band, where the fade rounds above 1.
+    let val = soft.noise2d(5.12499921725748, 4.713576842214337);
+    assert!(val >= -1.0 && val <= 1.0);
+}
+
+#[test]
+#[should_panic(expected = "checkerboard size must be positive")]
+fn test_checkerboard_size_not_positive() {
+    Checkerboard::new().with_size(0.0);
+}
+
+#[test]
+#[should_panic]
+fn test_checkerboard_soften_out_of_range() {
+    Checkerboard::new().with_soften(1.5);
+}
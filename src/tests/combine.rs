@@ -0,0 +1,89 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Checkerboard, Constant, Simplex };
+use crate::gen::combine::{ Add, Multiply, Min, Max, Power };
+
+#[test]
+fn test_constant() {
+    let constant = Constant::new(0.25);
+    assert_eq!(constant.noise1d(3.0), 0.25);
+    assert_eq!(constant.noise2d(3.0, -1.0), 0.25);
+    assert_eq!(constant.noise3d(3.0, -1.0, 7.5), 0.25);
+    assert_eq!(constant.noise4d(3.0, -1.0, 7.5, 0.1), 0.25);
+}
+
+#[test]
+fn test_constant_sample_line2d() {
+    let constant = Constant::new(-0.5);
+    for _ in 0..100 {
+        let start: (f64, f64) = (random::<f64>() * 20.0 - 10.0, random::<f64>() * 20.0 - 10.0);
+        let end: (f64, f64) = (random::<f64>() * 20.0 - 10.0, random::<f64>() * 20.0 - 10.0);
+        let line = constant.sample_line2d(start, end, 17);
+        assert_eq!(line.len(), 17);
+        assert!(line.iter().all(|&v| v == -0.5));
+    }
+
+    assert!(constant.sample_line2d((0.0, 0.0), (1.0, 1.0), 0).is_empty());
+    assert_eq!(constant.sample_line2d((0.0, 0.0), (1.0, 1.0), 1), vec![-0.5]);
+}
+
+#[test]
+fn test_add() {
+    let add = Add::new(Checkerboard::new(), Constant::new(0.5));
+    assert_eq!(add.noise1d(0.5), 0.75);
+    assert_eq!(add.noise1d(1.5), -0.25);
+    assert_eq!(add.noise2d(0.5, 0.5), 0.75);
+    assert_eq!(add.noise2d(1.5, 0.5), -0.25);
+    assert_eq!(add.noise3d(1.5, 1.5, 0.5), 0.75);
+}
+
+#[test]
+fn test_multiply() {
+    let multiply = Multiply::new(Checkerboard::new(), Constant::new(-0.5));
+    assert_eq!(multiply.noise1d(0.5), -0.5);
+    assert_eq!(multiply.noise1d(1.5), 0.5);
+    assert_eq!(multiply.noise2d(1.5, 0.5), 0.5);
+    assert_eq!(multiply.noise3d(1.5, 1.5, 0.5), -0.5);
+}
+
+#[test]
+fn test_min_max() {
+    let min = Min::new(Checkerboard::new(), Constant::new(0.2));
+    let max = Max::new(Checkerboard::new(), Constant::new(0.2));
+    assert_eq!(min.noise2d(0.5, 0.5), 0.2);
+    assert_eq!(min.noise2d(1.5, 0.5), -1.0);
+    assert_eq!(max.noise2d(0.5, 0.5), 1.0);
+    assert_eq!(max.noise2d(1.5, 0.5), 0.2);
+    assert_eq!(min.noise3d(1.5, 0.5, 0.5), -1.0);
+    assert_eq!(max.noise3d(1.5, 0.5, 0.5), 0.2);
+}
+
+#[test]
+fn test_power_unit_exponent() {
+    let simplex = Simplex::new();
+    let power = Power::new(simplex.clone(), Constant::new(1.0));
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(power.noise1d(x), simplex.noise1d(x));
+        assert_eq!(power.noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(power.noise3d(x, y, z), simplex.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_power_keeps_sign() {
+    assert_eq!(Power::new(Constant::new(-0.5), Constant::new(2.0)).noise1d(0.0), -0.25);
+    assert_eq!(Power::new(Constant::new(0.25), Constant::new(-0.5)).noise1d(0.0), 0.5);
+    assert_eq!(Power::new(Constant::new(0.0), Constant::new(3.0)).noise1d(0.0), 0.0);
+    assert_eq!(Power::new(Constant::new(-4.0), Constant::new(1.0)).noise1d(0.0), -1.0);
+}
+
+#[test]
+fn test_combine_bounds() {
+    let half = Constant::new(0.5);
+    assert_eq!(half.bounds(), (0.5, 0.5));
+    assert_eq!(Add::new(Simplex::new(), half).bounds(), (-0.25, 0.75));
+    assert_eq!(Multiply::new(Simplex::new(), Constant::new(-0.5)).bounds(), (-0.5, 0.5));
+    assert_eq!(Min::new(Simplex::new(), half).bounds(), (-1.0, 0.5));
+    assert_eq!(Max::new(Simplex::new(), half).bounds(), (0.5, 1.0));
+}
@@ -0,0 +1,43 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::gen::{ Checkerboard, Constant, DomainWarp2, Fbm, NoiseBuilder, OpenSimplex, Perlin, Simplex, Value, Worley };
+use crate::gen::modifier::{ Clamp, ScaleBias };
+
+#[test]
+fn test_default_seeded_generators_equal() {
+    assert_eq!(Simplex::new_default(), Simplex::new_default());
+    assert_eq!(Perlin::from_seed(3), Perlin::from_seed(3));
+    assert_eq!(OpenSimplex::from_seed(3), OpenSimplex::from_seed(3));
+    assert_eq!(Checkerboard::new(), Checkerboard::new());
+    assert!(Checkerboard::new() != Checkerboard::new().with_size(2.0));
+
+    let value = |seed: u64| Value::from_rng(&mut StdRng::seed_from_u64(seed));
+    let worley = |seed: u64| Worley::from_rng(&mut StdRng::seed_from_u64(seed));
+    assert_eq!(value(3), value(3));
+    assert_eq!(worley(3), worley(3));
+    assert_eq!(DomainWarp2::new(Checkerboard::new(), 1, 0.5, 2, 0.5), DomainWarp2::new(Checkerboard::new(), 1, 0.5, 2, 0.5));
+    assert_eq!(Fbm::new(Simplex::new_default(), 4), Fbm::new(Simplex::new_default(), 4));
+}
+
+/// Debug output names the generator but leaves out the permutation table.
+#[test]
+fn test_debug_output_bounded() {
+    let outputs: Vec<String> = vec![
+        format!("{:?}", Simplex::new_default().with_channel(2)),
+        format!("{:?}", Perlin::from_seed(3)),
+        format!("{:?}", OpenSimplex::from_seed(3)),
+        format!("{:?}", Value::from_rng(&mut StdRng::seed_from_u64(3))),
+        format!("{:?}", Worley::from_rng(&mut StdRng::seed_from_u64(3))),
+        format!("{:?}", Checkerboard::new()),
+        format!("{:?}", Clamp::new(ScaleBias::new(Fbm::new(Perlin::from_seed(3), 6), 0.5, 0.5), 0.0, 1.0)),
+        format!("{:?}", NoiseBuilder::new(Constant::new(0.5)).fbm(2))
+    ];
+
+    for output in outputs.iter() {
+        assert!(!output.is_empty());
+        assert!(output.len() < 400, "debug output too long: {}", output);
+    }
+    assert!(outputs[0].starts_with("Simplex {") && outputs[0].contains("perm_len: 512"));
+    assert!(outputs[1].contains("seed: 3"));
+}
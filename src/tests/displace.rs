@@ -0,0 +1,46 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Checkerboard, Constant, Displace, Perlin, Simplex };
+
+#[test]
+fn test_displace_zero_sources() {
+    let perlin = Perlin::new();
+    let zero = Constant::new(0.0);
+    let displace = Displace::new(perlin.clone(), zero, zero, zero);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(displace.noise1d(x), perlin.noise1d(x));
+        assert_eq!(displace.noise2d(x, y), perlin.noise2d(x, y));
+        assert_eq!(displace.noise3d(x, y, z), perlin.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_displace_per_axis() {
+    let displace = Displace::new(
+        Checkerboard::new(),
+        Constant::new(1.0),
+        Constant::new(0.0),
+        Constant::new(-1.0)
+    );
+    // Moving one axis by a whole cell flips the checkerboard, moving two
+    // restores it.
+    assert_eq!(displace.noise1d(0.5), -1.0);
+    assert_eq!(displace.noise2d(0.5, 0.5), -1.0);
+    assert_eq!(displace.noise3d(0.5, 0.5, 0.5), 1.0);
+}
+
+#[test]
+fn test_displace_noise4d() {
+    let simplex = Simplex::new();
+    let displace = Displace::new(
+        simplex.clone(),
+        Constant::new(1.0),
+        Constant::new(0.0),
+        Constant::new(-1.0)
+    );
+    for _ in 0..10000 {
+        let (x, y, z, w): (f64, f64, f64, f64) = (random(), random(), random(), random());
+        assert_eq!(displace.noise4d(x, y, z, w), simplex.noise4d(x + 1.0, y, z - 1.0, w));
+    }
+}
@@ -0,0 +1,42 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, DomainWarp2, Simplex };
+
+#[test]
+fn test_domain_warp2_zero_strength() {
+    let simplex = Simplex::new();
+    let warped = DomainWarp2::new(simplex.clone(), 1, 0.0, 2, 0.0);
+    for _ in 0..10000 {
+        let (x, y, z, w): (f64, f64, f64, f64) = (random(), random(), random(), random());
+        assert_eq!(warped.noise1d(x), simplex.noise1d(x));
+        assert_eq!(warped.noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(warped.noise3d(x, y, z), simplex.noise3d(x, y, z));
+        assert_eq!(warped.noise4d(x, y, z, w), simplex.noise4d(x, y, z, w));
+    }
+}
+
+#[test]
+fn test_domain_warp2_reproducible() {
+    let simplex = Simplex::new();
+    let first = DomainWarp2::new(simplex.clone(), 1, 4.0, 2, 4.0);
+    let second = DomainWarp2::new(simplex.clone(), 1, 4.0, 2, 4.0);
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random(), random());
+        let val = first.noise2d(x, y);
+        assert_eq!(val, second.noise2d(x, y));
+        assert!(val >= -1.0 && val <= 1.0);
+    }
+}
+
+#[test]
+fn test_domain_warp2_noise4d_distorts() {
+    let simplex = Simplex::new();
+    let warped = DomainWarp2::new(simplex.clone(), 1, 4.0, 2, 4.0);
+    assert!(*warped.source() == simplex);
+
+    let differs = (0..100).any(|i| {
+        let t: f64 = i as f64 * 0.37;
+        warped.noise4d(t, t * 0.5, t * 0.25, t * 0.125) != simplex.noise4d(t, t * 0.5, t * 0.25, t * 0.125)
+    });
+    assert!(differs);
+}
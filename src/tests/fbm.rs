@@ -0,0 +1,137 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Fbm, Simplex };
+
+/// Variance of the differences between neighboring samples along a line,
+/// which grows with the amount of high-frequency detail.
+fn roughness<G: NoiseGen>(gen: &G) -> f64 {
+    roughness_along(gen, false)
+}
+
+/// `roughness` along a line parallel to x, or to y when `along_y` is set.
+fn roughness_along<G: NoiseGen>(gen: &G, along_y: bool) -> f64 {
+    let sample = |t: f64| if along_y { gen.noise2d(3.0, t) } else { gen.noise2d(t, 3.0) };
+    let diffs: Vec<f64> = (0..4096)
+        .map(|i| {
+            let t = 10.0 + i as f64 * 0.01;
+            sample(t + 0.01) - sample(t)
+        })
+        .collect();
+
+    let mean = diffs.iter().fold(0.0, |acc, d| acc + *d) / diffs.len() as f64;
+    diffs.iter().fold(0.0, |acc, d| acc + (*d - mean).powi(2)) / diffs.len() as f64
+}
+
+#[test]
+fn test_fbm_single_octave() {
+    let simplex = Simplex::new();
+    let fbm = Fbm::new(simplex.clone(), 1);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(fbm.noise1d(x), simplex.noise1d(x));
+        assert_eq!(fbm.noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(fbm.noise3d(x, y, z), simplex.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_fbm_range() {
+    let fbm = Fbm::new(Simplex::new(), 6).with_persistence(0.7).with_lacunarity(1.9);
+    for _ in 0..10000 {
+        let val = fbm.noise3d(random::<f64>() * 10.0, random::<f64>() * 10.0, random::<f64>() * 10.0);
+        assert!(val >= -1.0 && val <= 1.0);
+    }
+}
+
+#[test]
+fn test_fbm_adds_detail() {
+    // A fixed seed, as the margin is small enough for the odd random table
+    // to land below it.
+    let simplex = Simplex::from_seed(42);
+    let fbm = Fbm::new(simplex.clone(), 4);
+
+    assert!(roughness(&fbm) > roughness(&simplex));
+}
+
+#[test]
+fn test_fbm_axis_lacunarity() {
+    let simplex = Simplex::from_seed(42);
+    let scalar = Fbm::new(simplex.clone(), 4).with_lacunarity(2.5);
+    let uniform = Fbm::new(simplex.clone(), 4).with_axis_lacunarity((2.5, 2.5, 2.5));
+    assert!(scalar == uniform);
+    for _ in 0..1000 {
+        let (x, y, z, w): (f64, f64, f64, f64) = (random(), random(), random(), random());
+        assert_eq!(uniform.noise2d(x, y), scalar.noise2d(x, y));
+        assert_eq!(uniform.noise3d(x, y, z), scalar.noise3d(x, y, z));
+        assert_eq!(uniform.noise4d(x, y, z, w), scalar.noise4d(x, y, z, w));
+    }
+
+    // Growing the frequency faster along x packs the detail of the upper
+    // octaves along x only.
+    let stretched = Fbm::new(simplex.clone(), 4).with_axis_lacunarity((4.0, 2.0, 2.0));
+    let iso = Fbm::new(simplex.clone(), 4);
+    assert!(roughness_along(&stretched, false) > 1.5 * roughness_along(&iso, false));
+    assert!(roughness_along(&stretched, false) > 1.5 * roughness_along(&stretched, true));
+}
+
+#[test]
+#[should_panic]
+fn test_fbm_zero_octaves() {
+    Fbm::new(Simplex::new(), 0);
+}
+
+#[test]
+#[should_panic(expected = "fbm persistence must be finite and positive")]
+fn test_fbm_negative_persistence() {
+    // With 2 octaves, amplitudes 1 and -1 would sum to 0.
+    Fbm::new(Simplex::new(), 2).with_persistence(-1.0);
+}
+
+#[test]
+#[should_panic(expected = "fbm persistence must be finite and positive")]
+fn test_fbm_infinite_persistence() {
+    Fbm::new(Simplex::new(), 2).with_persistence(f64::INFINITY);
+}
+
+#[test]
+fn test_fbm_single_layer() {
+    let simplex = Simplex::new();
+    let fbm = Fbm::with_layers(simplex.clone(), vec![(1.0, 1.0)]);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(fbm.noise1d(x), simplex.noise1d(x));
+        assert_eq!(fbm.noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(fbm.noise3d(x, y, z), simplex.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_fbm_layer_amplitudes() {
+    let simplex = Simplex::new();
+
+    // A silent layer contributes nothing.
+    let silent = Fbm::with_layers(simplex.clone(), vec![(1.0, 2.0), (8.0, 0.0)]);
+    // Two equal layers are averaged.
+    let even = Fbm::with_layers(simplex.clone(), vec![(1.0, 0.5), (8.0, 0.5)]);
+    // Amplitudes are relative to their total.
+    let weighted = Fbm::with_layers(simplex.clone(), vec![(1.0, 3.0), (8.0, 1.0)]);
+
+    for _ in 0..10000 {
+        let (x, y): (f64, f64) = (random::<f64>() * 10.0, random::<f64>() * 10.0);
+        let (low, high) = (simplex.noise2d(x, y), simplex.noise2d(x * 8.0, y * 8.0));
+        assert_eq!(silent.noise2d(x, y), low);
+        assert!((even.noise2d(x, y) - 0.5 * (low + high)).abs() < 1e-12);
+        assert!((weighted.noise2d(x, y) - (0.75 * low + 0.25 * high)).abs() < 1e-12);
+    }
+
+    // Layers following the geometric progression match the octaves.
+    let octaves = Fbm::new(simplex.clone(), 3);
+    let layers = Fbm::with_layers(simplex, vec![(1.0, 1.0), (2.0, 0.5), (4.0, 0.25)]);
+    assert_eq!(layers.noise2d(1.25, 3.5), octaves.noise2d(1.25, 3.5));
+}
+
+#[test]
+#[should_panic]
+fn test_fbm_no_layers() {
+    Fbm::with_layers(Simplex::new(), Vec::new());
+}
@@ -0,0 +1,48 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Gradient };
+
+#[test]
+fn test_gradient_endpoints() {
+    let gradient = Gradient::new([1.0, 2.0, 3.0], [3.0, 2.0, 3.0]);
+    assert_eq!(gradient.noise3d(1.0, 2.0, 3.0), -1.0);
+    assert_eq!(gradient.noise3d(3.0, 2.0, 3.0), 1.0);
+    assert_eq!(gradient.noise3d(2.0, 2.0, 3.0), 0.0);
+    assert_eq!(gradient.noise3d(1.5, 2.0, 3.0), -0.5);
+
+    // Beyond the endpoints the output is clamped.
+    assert_eq!(gradient.noise3d(-10.0, 2.0, 3.0), -1.0);
+    assert_eq!(gradient.noise3d(10.0, 2.0, 3.0), 1.0);
+
+    // Lower dimensions sample with the missing coordinates at 0.
+    assert_eq!(gradient.noise1d(2.0), 0.0);
+    assert_eq!(gradient.noise2d(2.5, -4.0), 0.5);
+}
+
+#[test]
+fn test_gradient_projection() {
+    let (start, end) = ([-1.0, 0.5, 2.0], [2.0, -1.5, 4.0]);
+    let gradient = Gradient::new(start, end);
+    for _ in 0..10000 {
+        let t: f64 = random::<f64>() * 2.0 - 0.5;
+        let along: [f64; 3] = [
+            start[0] + (end[0] - start[0]) * t,
+            start[1] + (end[1] - start[1]) * t,
+            start[2] + (end[2] - start[2]) * t
+        ];
+        let expected: f64 = (2.0 * t - 1.0).clamp(-1.0, 1.0);
+        assert!((gradient.noise3d(along[0], along[1], along[2]) - expected).abs() < 1e-12);
+
+        // Moving perpendicular to the axis doesn't change the value: (2, 3, 0)
+        // is orthogonal to the axis (3, -2, 2).
+        let s: f64 = random::<f64>() * 10.0 - 5.0;
+        let side = gradient.noise3d(along[0] + 2.0 * s, along[1] + 3.0 * s, along[2]);
+        assert!((side - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_gradient_same_endpoints() {
+    Gradient::new([1.0, 1.0, 1.0], [1.0, 1.0, 1.0]);
+}
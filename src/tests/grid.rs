@@ -0,0 +1,52 @@
+use crate::gen::{ NoiseGen, Simplex, Grid2d };
+
+#[test]
+fn test_grid_index() {
+    let simplex = Simplex::new();
+    let grid = simplex.field2d(13, 7, (5.0, 9.0), (0.1, 0.2));
+    assert_eq!(grid.data.len(), 13 * 7);
+
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            assert_eq!(grid[(x, y)], grid.data[y * grid.width + x]);
+            assert_eq!(grid.get(x, y), Some(grid.data[y * grid.width + x]));
+        }
+    }
+}
+
+#[test]
+fn test_grid_rows() {
+    let grid = Grid2d::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3, 2);
+    let rows: Vec<&[f64]> = grid.rows().collect();
+    assert_eq!(rows, vec![[1.0, 2.0, 3.0].as_slice(), [4.0, 5.0, 6.0].as_slice()]);
+    assert_eq!(grid.iter().count(), 6);
+}
+
+#[test]
+fn test_grid_rows_empty() {
+    for &(width, height) in [(0, 0), (0, 4), (4, 0)].iter() {
+        let grid = Grid2d::new(Vec::new(), width, height);
+        assert_eq!(grid.rows().count(), 0);
+    }
+}
+
+#[test]
+fn test_grid_get_out_of_bounds() {
+    let grid = Grid2d::new(vec![0.0; 6], 3, 2);
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 2), None);
+}
+
+#[test]
+#[should_panic]
+fn test_grid_index_column_out_of_bounds() {
+    // (3, 0) would land on (0, 1) if the column were not checked.
+    let grid = Grid2d::new(vec![0.0; 6], 3, 2);
+    let _ = grid[(3, 0)];
+}
+
+#[test]
+#[should_panic]
+fn test_grid_new_wrong_length() {
+    Grid2d::new(vec![0.0; 5], 3, 2);
+}
@@ -0,0 +1,35 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, HybridMulti, Simplex };
+
+#[test]
+fn test_hybrid_range() {
+    for &offset in [0.7, 0.0, -0.5, 1.5].iter() {
+        let hybrid = HybridMulti::new(Simplex::new(), 6).with_offset(offset);
+        for _ in 0..10000 {
+            let (x, y, z): (f64, f64, f64) = (random::<f64>() * 50.0, random::<f64>() * 50.0, random::<f64>() * 50.0);
+            let val2 = hybrid.noise2d(x, y);
+            let val3 = hybrid.noise3d(x, y, z);
+            assert!(val2 >= -1.0 && val2 <= 1.0);
+            assert!(val3 >= -1.0 && val3 <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_hybrid_octaves_add_detail() {
+    let simplex = Simplex::from_seed(42);
+
+    // Sums the magnitude of the second differences along a line, which
+    // grows with the high-frequency content of the field.
+    let roughness = |octaves: u32| {
+        let hybrid = HybridMulti::new(simplex.clone(), octaves);
+        let step: f64 = 0.002;
+        let vals: Vec<f64> = (0..5000).map(|i| hybrid.noise2d(3.0 + i as f64 * step, 7.5)).collect();
+        vals.windows(3).map(|w| (w[0] - 2.0 * w[1] + w[2]).abs()).sum::<f64>()
+    };
+
+    let (one, three, six) = (roughness(1), roughness(3), roughness(6));
+    assert!(three > one);
+    assert!(six > three);
+}
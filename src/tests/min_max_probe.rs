@@ -0,0 +1,35 @@
+use crate::gen::{ NoiseGen, Checkerboard, MinMaxProbe, Simplex };
+
+#[test]
+fn test_min_max_probe_empty() {
+    let probe = MinMaxProbe::new(Simplex::new());
+    assert_eq!(probe.range(), None);
+}
+
+#[test]
+fn test_min_max_probe_known_sequence() {
+    let probe = MinMaxProbe::new(Checkerboard::new());
+
+    assert_eq!(probe.noise1d(0.5), 1.0);
+    assert_eq!(probe.range(), Some((1.0, 1.0)));
+
+    assert_eq!(probe.noise2d(1.5, 0.5), -1.0);
+    assert_eq!(probe.range(), Some((-1.0, 1.0)));
+
+    probe.reset();
+    assert_eq!(probe.range(), None);
+
+    probe.noise3d(1.5, 1.5, 1.5);
+    assert_eq!(probe.range(), Some((-1.0, -1.0)));
+}
+
+#[test]
+fn test_min_max_probe_grid() {
+    let simplex = Simplex::new();
+    let probe = MinMaxProbe::new(simplex.clone());
+    let grid = probe.fill_grid2d(32, 32, (0.0, 0.0), (0.1, 0.1));
+
+    let min = grid.iter().fold(grid[0], |acc, v| if *v < acc { *v } else { acc });
+    let max = grid.iter().fold(grid[0], |acc, v| if *v > acc { *v } else { acc });
+    assert_eq!(probe.range(), Some((min, max)));
+}
@@ -1,3 +1,68 @@
+// Range checks are written out as comparisons to read like the documented
+// intervals they assert.
+#![allow(clippy::manual_range_contains)]
+
+// Most tests seed their generators from entropy with `new`, which needs
+// `std`. The rest also run under `--no-default-features`.
+
+#[cfg(feature = "std")]
 mod simplex;
+#[cfg(feature = "std")]
 mod perlin;
+#[cfg(feature = "std")]
+mod open_simplex;
 mod checkerboard;
+#[cfg(feature = "std")]
+mod grid;
+#[cfg(feature = "std")]
+mod min_max_probe;
+#[cfg(feature = "std")]
+mod unit_range;
+#[cfg(feature = "std")]
+mod domain_warp;
+#[cfg(feature = "std")]
+mod fbm;
+#[cfg(feature = "std")]
+mod billow;
+#[cfg(feature = "std")]
+mod worley;
+#[cfg(feature = "std")]
+mod value;
+mod utils;
+#[cfg(feature = "std")]
+mod noise_map;
+#[cfg(feature = "std")]
+mod modifier;
+#[cfg(feature = "std")]
+mod tileable;
+#[cfg(feature = "std")]
+mod combine;
+#[cfg(feature = "std")]
+mod select;
+#[cfg(all(feature = "serde", feature = "std"))]
+mod serialize;
+#[cfg(feature = "std")]
+mod turbulence;
+#[cfg(feature = "std")]
+mod ridged;
+#[cfg(feature = "std")]
+mod hybrid;
+mod shapes;
+#[cfg(feature = "std")]
+mod displace;
+#[cfg(feature = "std")]
+mod blend;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod boxed;
+#[cfg(feature = "std")]
+mod gradient;
+#[cfg(feature = "std")]
+mod normal;
+#[cfg(feature = "std")]
+mod debug;
+#[cfg(feature = "std")]
+mod checked;
@@ -0,0 +1,394 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Checkerboard, Constant, Simplex };
+use crate::gen::modifier::{ ScaleBias, Abs, Clamp, Curve, RotatePoint, ScalePoint, TranslatePoint, Exponent, Terrace, Repeat, Gaussianize };
+
+#[test]
+fn test_scale_bias_constant_inputs() {
+    let unit = ScaleBias::new(Checkerboard::new(), 0.5, 0.5);
+    assert_eq!(unit.noise1d(0.5), 1.0);
+    assert_eq!(unit.noise1d(1.5), 0.0);
+
+    let meters = ScaleBias::new(Checkerboard::new(), 100.0, -20.0);
+    assert_eq!(meters.noise2d(0.5, 0.5), 80.0);
+    assert_eq!(meters.noise2d(1.5, 0.5), -120.0);
+    assert_eq!(meters.noise3d(1.5, 1.5, 0.5), 80.0);
+}
+
+#[test]
+fn test_scale_bias_forwards() {
+    let simplex = Simplex::new();
+    let scaled = ScaleBias::new(simplex.clone(), 2.0, 1.0);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(scaled.noise1d(x), simplex.noise1d(x) * 2.0 + 1.0);
+        assert_eq!(scaled.noise2d(x, y), simplex.noise2d(x, y) * 2.0 + 1.0);
+        assert_eq!(scaled.noise3d(x, y, z), simplex.noise3d(x, y, z) * 2.0 + 1.0);
+    }
+}
+
+#[test]
+fn test_abs_range() {
+    let abs = Abs::new(Simplex::new());
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 10.0, random::<f64>() * 10.0, random::<f64>() * 10.0);
+        for val in [abs.noise1d(x), abs.noise2d(x, y), abs.noise3d(x, y, z)].iter() {
+            assert!(*val >= -1.0 && *val <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_abs_folds_negative_values() {
+    let simplex = Simplex::new();
+    let abs = Abs::new(simplex.clone());
+    for _ in 0..10000 {
+        let (x, y): (f64, f64) = (random::<f64>() * 10.0, random::<f64>() * 10.0);
+        let val = simplex.noise2d(x, y);
+        assert_eq!(abs.noise2d(x, y), 2.0 * val.abs() - 1.0);
+        if val < 0.0 {
+            assert_eq!(abs.noise2d(x, y), 2.0 * -val - 1.0);
+        }
+    }
+
+    // Both checkerboard colors fold onto the same value.
+    let checkerboard = Abs::new(Checkerboard::new());
+    assert_eq!(checkerboard.noise1d(0.5), 1.0);
+    assert_eq!(checkerboard.noise1d(1.5), 1.0);
+}
+
+#[test]
+fn test_clamp_range() {
+    let clamp = Clamp::new(Simplex::new(), -0.5, 0.5);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 10.0, random::<f64>() * 10.0, random::<f64>() * 10.0);
+        for val in [clamp.noise1d(x), clamp.noise2d(x, y), clamp.noise3d(x, y, z)].iter() {
+            assert!(*val >= -0.5 && *val <= 0.5);
+        }
+    }
+}
+
+#[test]
+fn test_clamp_equal_bounds() {
+    let clamp = Clamp::new(Simplex::new(), 0.25, 0.25);
+    for _ in 0..1000 {
+        assert_eq!(clamp.noise2d(random::<f64>() * 10.0, random::<f64>() * 10.0), 0.25);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_clamp_inverted_bounds() {
+    Clamp::new(Simplex::new(), 0.5, -0.5);
+}
+
+#[test]
+fn test_curve_identity() {
+    // The curve extends past [-1, 1] so every segment in use has a neighbour
+    // on both sides.
+    let points: Vec<(f64, f64)> = (0..13).map(|i| {
+        let v: f64 = i as f64 * 0.25 - 1.5;
+        (v, v)
+    }).collect();
+
+    for i in 0..201 {
+        let v: f64 = i as f64 * 0.01 - 1.0;
+        let curve = Curve::new(Constant::new(v), points.clone());
+        assert!((curve.noise2d(0.0, 0.0) - v).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_curve_terraces() {
+    let points: Vec<(f64, f64)> = vec![(0.1, 0.5), (-1.0, -0.5), (1.0, 0.5), (-0.1, -0.5)];
+
+    for i in 0..91 {
+        let low: f64 = -1.0 + i as f64 * 0.01;
+        let high: f64 = 0.1 + i as f64 * 0.01;
+        assert!((Curve::new(Constant::new(low), points.clone()).noise1d(0.0) + 0.5).abs() < 0.1);
+        assert!((Curve::new(Constant::new(high), points.clone()).noise1d(0.0) - 0.5).abs() < 0.1);
+    }
+
+    // The control points are hit exactly and the ends are clamped.
+    assert_eq!(Curve::new(Constant::new(-0.1), points.clone()).noise1d(0.0), -0.5);
+    assert_eq!(Curve::new(Constant::new(-2.0), points.clone()).noise1d(0.0), -0.5);
+    assert_eq!(Curve::new(Constant::new(2.0), points.clone()).noise1d(0.0), 0.5);
+    assert_eq!(Curve::new(Constant::new(0.0), points.clone()).noise1d(0.0), 0.0);
+}
+
+#[test]
+fn test_curve_bounds() {
+    // The steep middle segments overshoot the control point outputs.
+    let points: Vec<(f64, f64)> = vec![(-1.0, 0.0), (-0.5, 0.0), (-0.1, -0.8), (0.1, 0.8), (0.5, 0.0), (1.0, 0.0)];
+    let (lower, upper) = Curve::new(Simplex::new(), points.clone()).bounds();
+    assert!(lower < -0.8 && upper > 0.8);
+
+    let (mut min, mut max): (f64, f64) = (f64::INFINITY, f64::NEG_INFINITY);
+    for i in 0..40001 {
+        let v: f64 = i as f64 * 0.0001 - 2.0;
+        let val = Curve::new(Constant::new(v), points.clone()).noise1d(0.0);
+        assert!(val >= lower - 1e-12 && val <= upper + 1e-12);
+        min = min.min(val);
+        max = max.max(val);
+    }
+
+    // The bounds are tight.
+    assert!((min - lower).abs() < 1e-6);
+    assert!((max - upper).abs() < 1e-6);
+}
+
+#[test]
+#[should_panic]
+fn test_curve_too_few_points() {
+    Curve::new(Simplex::new(), vec![(-1.0, -1.0), (0.0, 0.0), (1.0, 1.0)]);
+}
+
+#[test]
+fn test_terrace_quantizes() {
+    let points: Vec<f64> = vec![0.5, -1.0, 0.0, 1.0, -0.5];
+    let terrace = |val: f64| Terrace::new(Constant::new(val), points.clone());
+
+    // Control points map to themselves and the ends are clamped.
+    for &p in [-1.0, -0.5, 0.0, 0.5, 1.0].iter() {
+        assert_eq!(terrace(p).noise1d(0.0), p);
+    }
+    assert_eq!(terrace(-3.0).noise2d(0.0, 0.0), -1.0);
+    assert_eq!(terrace(3.0).noise3d(0.0, 0.0, 0.0), 1.0);
+
+    // A quarter of the way up a step rises a sixteenth of it.
+    for i in 0..4 {
+        let low: f64 = -1.0 + i as f64 * 0.5;
+        assert!((terrace(low + 0.125).noise1d(0.0) - (low + 0.03125)).abs() < 1e-12);
+        assert!((terrace(low + 0.375).noise1d(0.0) - (low + 0.28125)).abs() < 1e-12);
+
+        let inverted = Terrace::new(Constant::new(low + 0.375), points.clone()).with_invert(true);
+        assert!((inverted.noise1d(0.0) - (low + 0.5 - 0.03125)).abs() < 1e-12);
+
+        let smooth = Terrace::new(Constant::new(low + 0.25), points.clone()).with_smoothing(true);
+        assert!((smooth.noise1d(0.0) - (low + 0.25)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_terrace_monotonic_and_bounded() {
+    let simplex = Simplex::new();
+    let terrace = Terrace::new(simplex.clone(), vec![-0.8, -0.1, 0.2, 0.7]);
+    assert_eq!(terrace.bounds(), (-0.8, 0.7));
+
+    let mut samples: Vec<(f64, f64)> = (0..10000).map(|_| {
+        let (x, y): (f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0);
+        (simplex.noise2d(x, y), terrace.noise2d(x, y))
+    }).collect();
+    samples.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    for pair in samples.windows(2) {
+        assert!(pair[0].1 <= pair[1].1);
+        assert!(pair[1].1 >= -0.8 && pair[1].1 <= 0.7);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_terrace_too_few_points() {
+    Terrace::new(Simplex::new(), vec![0.0]);
+}
+
+#[test]
+fn test_repeat_makes_checkerboard_periodic() {
+    // A rotated pattern never repeats on the axes by itself.
+    let pattern = Checkerboard::new().with_rotation(0.3);
+    let tile = Repeat::new(pattern, [5, 3, 2]);
+    assert!(pattern.noise2d(0.5, 0.5) != pattern.noise2d(5.5, 0.5));
+
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        let (x, y, z) = (x * 5.0, y * 3.0, z * 2.0);
+        assert_eq!(tile.noise2d(x, y), pattern.noise2d(x, y));
+        for &(i, j, k) in [(1.0, 0.0, 0.0), (-2.0, 3.0, 1.0), (7.0, -4.0, -5.0)].iter() {
+            let (sx, sy, sz) = (x + i * 5.0, y + j * 3.0, z + k * 2.0);
+            assert_eq!(tile.noise1d(sx), tile.noise1d(x));
+            assert_eq!(tile.noise2d(sx, sy), tile.noise2d(x, y));
+            assert_eq!(tile.noise3d(sx, sy, sz), tile.noise3d(x, y, z));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_repeat_zero_period() {
+    Repeat::new(Simplex::new(), [4, 0, 4]);
+}
+
+/// Sample skewness and kurtosis of `vals`.
+fn moments(vals: &[f64]) -> (f64, f64) {
+    let n: f64 = vals.len() as f64;
+    let mean: f64 = vals.iter().sum::<f64>() / n;
+    let central = |k: i32| vals.iter().map(|v| (v - mean).powi(k)).sum::<f64>() / n;
+    let var: f64 = central(2);
+
+    (central(3) / var.powf(1.5), central(4) / (var * var))
+}
+
+#[test]
+fn test_gaussianize_moments() {
+    // Bending the output makes it strongly skewed.
+    let skewed = Exponent::new(Simplex::from_seed(8), 3.0);
+    let normal = Gaussianize::new(skewed.clone());
+
+    let (mut raw, mut out): (Vec<f64>, Vec<f64>) = (Vec::new(), Vec::new());
+    for i in 0..100000 {
+        let (x, y): (f64, f64) = ((i % 400) as f64 * 0.37 + 1000.0, (i / 400) as f64 * 0.41 - 500.0);
+        let val = normal.noise2d(x, y);
+        assert!(val >= -1.0 && val <= 1.0);
+        raw.push(skewed.noise2d(x, y));
+        out.push(val);
+    }
+
+    let (raw_skew, _) = moments(raw.as_slice());
+    let (out_skew, out_kurtosis) = moments(out.as_slice());
+    assert!(raw_skew > 0.5);
+    assert!(out_skew.abs() < 0.1 && out_skew.abs() < raw_skew.abs());
+    // A normal distribution has a kurtosis of 3.
+    assert!((out_kurtosis - 3.0).abs() < 0.3);
+}
+
+#[test]
+fn test_gaussianize_keeps_order() {
+    let simplex = Simplex::new();
+    let normal = Gaussianize::new(simplex.clone());
+    assert_eq!(normal.bounds(), (-1.0, 1.0));
+
+    let mut samples: Vec<(f64, f64)> = (0..10000).map(|_| {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 50.0, random::<f64>() * 50.0, random::<f64>() * 50.0);
+        (simplex.noise3d(x, y, z), normal.noise3d(x, y, z))
+    }).collect();
+    samples.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    for pair in samples.windows(2) {
+        assert!(pair[0].1 <= pair[1].1);
+    }
+
+    // A flat source sits in the middle of the distribution.
+    assert_eq!(Gaussianize::new(Constant::new(0.3)).noise2d(1.0, 2.0), 0.0);
+}
+
+/// Returns one of the coordinates it is sampled at, to observe how modifiers
+/// transform their input.
+#[derive(Copy, Clone)]
+struct Axis(usize);
+
+impl NoiseGen for Axis {
+    fn noise1d(&self, xin: f64) -> f64 {
+        [xin][self.0]
+    }
+
+    fn noise2d(&self, xin: f64, yin: f64) -> f64 {
+        [xin, yin][self.0]
+    }
+
+    fn noise3d(&self, xin: f64, yin: f64, zin: f64) -> f64 {
+        [xin, yin, zin][self.0]
+    }
+}
+
+#[test]
+fn test_rotate_point_identity() {
+    let simplex = Simplex::new();
+    let rotated = RotatePoint::new(simplex.clone(), 0.0, 0.0, 0.0);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(rotated.noise1d(x), simplex.noise1d(x));
+        assert_eq!(rotated.noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(rotated.noise3d(x, y, z), simplex.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_rotate_point_quarter_turn_about_z() {
+    use std::f64::consts::FRAC_PI_2;
+
+    let axes: Vec<RotatePoint<Axis>> = (0..3)
+        .map(|axis| RotatePoint::new(Axis(axis), 0.0, 0.0, FRAC_PI_2))
+        .collect();
+
+    // Rotating (x, y) counterclockwise by 90 degrees gives (-y, x).
+    assert!((axes[0].noise2d(2.0, 3.0) + 3.0).abs() < 1e-12);
+    assert!((axes[1].noise2d(2.0, 3.0) - 2.0).abs() < 1e-12);
+    assert!((axes[0].noise3d(2.0, 3.0, 5.0) + 3.0).abs() < 1e-12);
+    assert!((axes[1].noise3d(2.0, 3.0, 5.0) - 2.0).abs() < 1e-12);
+    assert!((axes[2].noise3d(2.0, 3.0, 5.0) - 5.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_scale_translate_point_identity() {
+    let simplex = Simplex::new();
+    let scaled = ScalePoint::new(simplex.clone(), 1.0, 1.0, 1.0);
+    let translated = TranslatePoint::new(simplex.clone(), 0.0, 0.0, 0.0);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(scaled.noise1d(x), simplex.noise1d(x));
+        assert_eq!(scaled.noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(scaled.noise3d(x, y, z), simplex.noise3d(x, y, z));
+        assert_eq!(translated.noise1d(x), simplex.noise1d(x));
+        assert_eq!(translated.noise2d(x, y), simplex.noise2d(x, y));
+        assert_eq!(translated.noise3d(x, y, z), simplex.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_scale_translate_point_per_axis() {
+    let scaled = ScalePoint::new(Axis(1), 1.0, 3.0, -2.0);
+    let translated = TranslatePoint::new(Axis(2), 1.0, 3.0, -2.0);
+    assert_eq!(scaled.noise2d(5.0, 7.0), 21.0);
+    assert_eq!(scaled.noise3d(5.0, 7.0, 11.0), 21.0);
+    assert_eq!(translated.noise3d(5.0, 7.0, 11.0), 9.0);
+}
+
+#[test]
+fn test_exponent_identity() {
+    let simplex = Simplex::new();
+    let exponent = Exponent::new(simplex.clone(), 1.0);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert!((exponent.noise1d(x) - simplex.noise1d(x)).abs() < 1e-12);
+        assert!((exponent.noise2d(x, y) - simplex.noise2d(x, y)).abs() < 1e-12);
+        assert!((exponent.noise3d(x, y, z) - simplex.noise3d(x, y, z)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_exponent_lowers_median() {
+    let simplex = Simplex::new();
+    let exponent = Exponent::new(simplex.clone(), 2.0);
+
+    let median = |gen: &dyn NoiseGen| -> f64 {
+        let mut vals: Vec<f64> = (0..4096)
+            .map(|i| gen.noise2d((i % 64) as f64 * 0.13, (i / 64) as f64 * 0.13))
+            .collect();
+        vals.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        vals[vals.len() / 2]
+    };
+
+    assert!(median(&exponent) < median(&simplex));
+    assert_eq!(Exponent::new(Constant::new(1.0), 2.0).noise1d(0.0), 1.0);
+    assert_eq!(Exponent::new(Constant::new(-1.0), 2.0).noise1d(0.0), -1.0);
+    assert_eq!(Exponent::new(Constant::new(0.0), 2.0).noise1d(0.0), -0.5);
+}
+
+#[test]
+fn test_modifier_bounds() {
+    assert_eq!(Simplex::new().bounds(), (-1.0, 1.0));
+    assert_eq!(ScaleBias::new(Simplex::new(), 2.0, 1.0).bounds(), (-1.0, 3.0));
+    assert_eq!(ScaleBias::new(Simplex::new(), -0.5, 0.5).bounds(), (0.0, 1.0));
+    assert_eq!(Clamp::new(Simplex::new(), -0.5, 2.0).bounds(), (-0.5, 1.0));
+    assert_eq!(Abs::new(Simplex::new()).bounds(), (-1.0, 1.0));
+    assert_eq!(Abs::new(ScaleBias::new(Simplex::new(), 0.25, -0.5)).bounds(), (-0.5, 0.5));
+    assert_eq!(Exponent::new(ScaleBias::new(Simplex::new(), 0.5, 0.5), 2.0).bounds(), (-0.5, 1.0));
+
+    let pipeline = Clamp::new(ScaleBias::new(Abs::new(Simplex::new()), 3.0, 0.5), -2.0, 2.0);
+    let (lower, upper) = pipeline.bounds();
+    assert_eq!((lower, upper), (-2.0, 2.0));
+    for _ in 0..10000 {
+        let (x, y): (f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0);
+        let val = pipeline.noise2d(x, y);
+        assert!(val >= lower && val <= upper);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::gen::{ NoiseGen, Checkerboard, Constant, Fbm, Simplex };
+use crate::map::NoiseMap;
+
+#[test]
+fn test_noise_map_checkerboard() {
+    let map = NoiseMap::generate2d(&Checkerboard::new(), 16, 16, (0.5, 0.5), (1.0, 1.0));
+    assert_eq!(map.width(), 16);
+    assert_eq!(map.height(), 16);
+    assert_eq!(map.as_slice().len(), 256);
+
+    for y in 0..16 {
+        for x in 0..16 {
+            let expected = if (x ^ y) & 1 == 1 { -1.0 } else { 1.0 };
+            assert_eq!(map.get(x, y), expected);
+        }
+    }
+    assert_eq!(map.min(), -1.0);
+    assert_eq!(map.max(), 1.0);
+}
+
+#[test]
+fn test_noise_map_matches_noise2d() {
+    let simplex = Simplex::new();
+    let map = NoiseMap::generate2d(&simplex, 8, 5, (3.0, -1.0), (0.25, 0.5));
+    for y in 0..5 {
+        for x in 0..8 {
+            assert_eq!(map.get(x, y), simplex.noise2d(3.0 + x as f64 * 0.25, -1.0 + y as f64 * 0.5));
+        }
+    }
+}
+
+#[test]
+fn test_noise_map_normalize() {
+    let simplex = Fbm::new(Simplex::from_seed(9), 5);
+    let original = NoiseMap::generate2d(&simplex, 32, 24, (0.0, 0.0), (0.05, 0.05));
+    let (min, max) = (original.min(), original.max());
+
+    let mut map = original.clone();
+    map.normalize();
+    assert_eq!(map.min(), 0.0);
+    assert_eq!(map.max(), 1.0);
+    for (val, orig) in map.as_slice().iter().zip(original.as_slice().iter()) {
+        assert!((val - (orig - min) / (max - min)).abs() < 1e-12);
+    }
+
+    // The checkerboard already spans [-1, 1].
+    let mut checks = NoiseMap::generate2d(&Checkerboard::new(), 4, 4, (0.5, 0.5), (1.0, 1.0));
+    checks.normalize();
+    assert_eq!(checks.get(0, 0), 1.0);
+    assert_eq!(checks.get(1, 0), 0.0);
+
+    // A flat map has no range to stretch.
+    let mut flat = NoiseMap::generate2d(&Constant::new(-0.3), 5, 5, (0.0, 0.0), (1.0, 1.0));
+    flat.normalize();
+    assert!(flat.as_slice().iter().all(|&val| val == 0.5));
+
+    let mut empty = NoiseMap::generate2d(&simplex, 0, 0, (0.0, 0.0), (1.0, 1.0));
+    empty.normalize();
+    assert!(empty.as_slice().is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_noise_map_get_out_of_bounds() {
+    let map = NoiseMap::generate2d(&Checkerboard::new(), 4, 4, (0.0, 0.0), (1.0, 1.0));
+    map.get(4, 0);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_noise_map_gray_image() {
+    let map = NoiseMap::generate2d(&Checkerboard::new(), 4, 4, (0.5, 0.5), (1.0, 1.0));
+    let image = map.to_gray_image();
+    assert_eq!(image.dimensions(), (4, 4));
+
+    for y in 0..4 {
+        for x in 0..4 {
+            let expected: u8 = if (x ^ y) & 1 == 1 { 0 } else { 255 };
+            assert_eq!(image.get_pixel(x, y).0, [expected]);
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_noise_map_parallel() {
+    let simplex = Simplex::new();
+    let serial = NoiseMap::generate2d(&simplex, 123, 77, (-4.5, 2.25), (0.03, 0.07));
+    let parallel = NoiseMap::generate2d_parallel(&simplex, 123, 77, (-4.5, 2.25), (0.03, 0.07));
+    assert!(serial == parallel);
+
+    let empty = NoiseMap::generate2d_parallel(&simplex, 0, 5, (0.0, 0.0), (1.0, 1.0));
+    assert_eq!(empty.as_slice().len(), 0);
+}
@@ -0,0 +1,48 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Constant, Simplex };
+
+#[test]
+fn test_normal2d_flat_field() {
+    let flat = Constant::new(0.3);
+    for _ in 0..100 {
+        let (x, y): (f64, f64) = (random(), random());
+        assert_eq!(flat.normal2d(x * 10.0, y * 10.0, 5.0), [0.0, 0.0, 1.0]);
+    }
+}
+
+#[test]
+fn test_normal2d_matches_finite_differences() {
+    let simplex = Simplex::from_seed(7);
+    let h: f64 = 1e-6;
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random(), random());
+        let (x, y) = (x * 20.0 - 10.0, y * 20.0 - 10.0);
+        let strength: f64 = 0.75;
+
+        let dx = (simplex.noise2d(x + h, y) - simplex.noise2d(x - h, y)) / (2.0 * h);
+        let dy = (simplex.noise2d(x, y + h) - simplex.noise2d(x, y - h)) / (2.0 * h);
+        let len = (strength * strength * (dx * dx + dy * dy) + 1.0).sqrt();
+
+        let normal = simplex.normal2d(x, y, strength);
+        assert!((normal[0] + strength * dx / len).abs() < 1e-5);
+        assert!((normal[1] + strength * dy / len).abs() < 1e-5);
+        assert!((normal[2] - 1.0 / len).abs() < 1e-5);
+
+        // Boxing the generator keeps its analytic gradient.
+        let boxed: Box<dyn NoiseGen> = Box::new(simplex.clone());
+        let (bx, by) = boxed.gradient2d(x, y);
+        assert!((bx - dx).abs() < 1e-4 && (by - dy).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_normal2d_unit_length() {
+    let simplex = Simplex::from_seed(7);
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random(), random());
+        let [nx, ny, nz] = simplex.normal2d(x * 20.0, y * 20.0, 3.0);
+        assert!((nx * nx + ny * ny + nz * nz - 1.0).abs() < 1e-12);
+        assert!(nz > 0.0);
+    }
+}
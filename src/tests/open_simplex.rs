@@ -0,0 +1,133 @@
+use rand::{ thread_rng, random, SeedableRng };
+use rand::RngCore;
+use rand::rngs::{ OsRng, SmallRng, StdRng, ThreadRng };
+use rand_xorshift::XorShiftRng;
+
+use crate::gen::{ NoiseGen, OpenSimplex };
+
+macro_rules! test_open_simplex_from_rng(
+    ($t: ty) => ({
+        let mut rng: $t = SeedableRng::from_entropy();
+
+        OpenSimplex::from_rng(&mut rng);
+    });
+);
+
+#[test]
+fn test_open_simplex_new() {
+    OpenSimplex::new();
+}
+
+#[test]
+fn test_open_simplex_from_osrng() {
+    OpenSimplex::from_rng(&mut OsRng);
+}
+
+#[test]
+fn test_open_simplex_from_stdrng() {
+    test_open_simplex_from_rng!(StdRng);
+}
+
+#[test]
+fn test_open_simplex_from_xorshiftrng() {
+    test_open_simplex_from_rng!(XorShiftRng);
+}
+
+#[test]
+fn test_open_simplex_from_smallrng() {
+    test_open_simplex_from_rng!(SmallRng);
+}
+
+#[test]
+fn test_open_simplex_from_dyn_rng() {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(1234);
+    let dyn_rng: &mut dyn RngCore = &mut rng;
+
+    OpenSimplex::from_rng(dyn_rng);
+}
+
+#[test]
+fn test_open_simplex_from_threadrng() {
+    let mut thread_rng: ThreadRng = thread_rng();
+
+    OpenSimplex::from_rng(&mut thread_rng);
+}
+
+#[test]
+fn test_open_simplex_noise1d() {
+    let open_simplex = OpenSimplex::new();
+    for _ in 0..10000 {
+        let val = open_simplex.noise1d(random::<f64>() * 256.0);
+        assert!(val >= -1.0 && val <= 1.0);
+    }
+}
+
+#[test]
+fn test_open_simplex_noise2d() {
+    let open_simplex = OpenSimplex::new();
+    for _ in 0..10000 {
+        let val = open_simplex.noise2d(
+            random::<f64>() * 256.0,
+            random::<f64>() * 256.0
+        );
+        assert!(val >= -1.0 && val <= 1.0);
+    }
+}
+
+#[test]
+fn test_open_simplex_noise3d() {
+    let open_simplex = OpenSimplex::new();
+    for _ in 0..10000 {
+        let val = open_simplex.noise3d(
+            random::<f64>() * 256.0,
+            random::<f64>() * 256.0,
+            random::<f64>() * 256.0
+        );
+        assert!(val >= -1.0 && val <= 1.0);
+    }
+}
+
+#[test]
+fn test_open_simplex_continuous() {
+
+    let open_simplex = OpenSimplex::new();
+    let eps: f64 = 1e-7;
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+        assert!((open_simplex.noise2d(x + eps, y) - open_simplex.noise2d(x, y)).abs() < 1e-5);
+        assert!((open_simplex.noise3d(x, y, z + eps) - open_simplex.noise3d(x, y, z)).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_open_simplex_from_seed() {
+    let first = OpenSimplex::from_seed(42);
+    let second = OpenSimplex::from_seed(42);
+    assert!(first == second);
+    assert!(first != OpenSimplex::from_seed(43));
+
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(first.noise1d(x), second.noise1d(x));
+        assert_eq!(first.noise2d(x, y), second.noise2d(x, y));
+        assert_eq!(first.noise3d(x, y, z), second.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_open_simplex_from_perm_round_trip() {
+    let open_simplex = OpenSimplex::new();
+    let restored = OpenSimplex::from_perm(open_simplex.perm().to_vec()).unwrap();
+    assert!(open_simplex == restored);
+    assert!(OpenSimplex::from_perm(Vec::new()).is_err());
+}
+
+#[test]
+fn test_open_simplex_set_seed() {
+    use crate::gen::Seedable;
+
+    let mut open_simplex = OpenSimplex::new();
+    open_simplex.set_seed(99);
+    assert_eq!(open_simplex.seed(), 99);
+    assert!(open_simplex == OpenSimplex::from_seed(99));
+}
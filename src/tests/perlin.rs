@@ -1,25 +1,13 @@
-use std::rand::{ thread_rng, random };
-use std::rand::{ Rng, OsRng, StdRng, ThreadRng, IsaacRng, Isaac64Rng, XorShiftRng };
+use rand::{ thread_rng, random, Rng, SeedableRng };
+use rand::RngCore;
+use rand::rngs::{ OsRng, SmallRng, StdRng, ThreadRng };
+use rand_xorshift::XorShiftRng;
 
-use gen::{NoiseGen, Perlin};
+use crate::gen::{NoiseGen, Perlin};
 
 macro_rules! test_perlin_from_rng(
-    ($t: ident) => ({
-        let mut rng = match $t::new() {
-            Ok(r) => r,
-            Err(e) => panic!("Failed to create RNG: {}", e)
-        };
-
-        Perlin::from_rng(&mut rng);
-    });
-);
-
-macro_rules! test_perlin_from_rand_rng(
     ($t: ty) => ({
-        let mut rng: $t = match OsRng::new() {
-            Ok(mut r) => r.gen(),
-            Err(e) => panic!("Failed to create seeded RNG: {}", e)
-        };
+        let mut rng: $t = SeedableRng::from_entropy();
 
         Perlin::from_rng(&mut rng);
     });
@@ -32,7 +20,7 @@ fn test_perlin_new() {
 
 #[test]
 fn test_perlin_from_osrng() {
-    test_perlin_from_rng!(OsRng);
+    Perlin::from_rng(&mut OsRng);
 }
 
 #[test]
@@ -41,18 +29,21 @@ fn test_perlin_from_stdrng() {
 }
 
 #[test]
-fn test_perlin_from_isaacrng() {
-    test_perlin_from_rand_rng!(IsaacRng);
+fn test_perlin_from_xorshiftrng() {
+    test_perlin_from_rng!(XorShiftRng);
 }
 
 #[test]
-fn test_perlin_from_isaac64rng() {
-    test_perlin_from_rand_rng!(Isaac64Rng);
+fn test_perlin_from_smallrng() {
+    test_perlin_from_rng!(SmallRng);
 }
 
 #[test]
-fn test_perlin_from_xorshiftrng() {
-    test_perlin_from_rand_rng!(XorShiftRng);
+fn test_perlin_from_dyn_rng() {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(1234);
+    let dyn_rng: &mut dyn RngCore = &mut rng;
+
+    Perlin::from_rng(dyn_rng);
 }
 
 #[test]
@@ -65,7 +56,7 @@ fn test_perlin_from_threadrng() {
 #[test]
 fn test_perlin_noise1d() {
     let perlin = Perlin::new();
-    for _ in range(0us, 10000) {
+    for _ in 0..10000 {
         perlin.noise1d(random());
     }
 }
@@ -73,7 +64,7 @@ fn test_perlin_noise1d() {
 #[test]
 fn test_perlin_noise2d() {
     let perlin = Perlin::new();
-    for _ in range(0us, 10000) {
+    for _ in 0..10000 {
         perlin.noise2d(
             random(),
             random()
@@ -84,7 +75,7 @@ fn test_perlin_noise2d() {
 #[test]
 fn test_perlin_noise3d() {
     let perlin = Perlin::new();
-    for _ in range(0us, 10000) {
+    for _ in 0..10000 {
         perlin.noise3d(
             random(),
             random(),
@@ -92,3 +83,331 @@ fn test_perlin_noise3d() {
         );
     }
 }
+
+#[test]
+fn test_perlin_noise2d_split_near_origin() {
+    let perlin = Perlin::new();
+    for _ in 0..10000 {
+        let (fx, fy): (f64, f64) = (random(), random());
+        let (cx, cy): (i64, i64) = (thread_rng().gen_range(-100..100), thread_rng().gen_range(-100..100));
+
+        // noise2d rounds the cell and offset into one f64 and takes the
+        // offset back out by subtraction, which drops its low bits.
+        let val = perlin.noise2d_split((cx, cy), (fx, fy));
+        assert!((val - perlin.noise2d(cx as f64 + fx, cy as f64 + fy)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_perlin_noise2d_split_large_cells() {
+    let perlin = Perlin::new();
+    let far: i64 = 1 << 50;
+    for _ in 0..10000 {
+        let (fx, fy): (f64, f64) = (random(), random());
+
+        // The lattice repeats every 256 cells, so a cell 2^50 away must
+        // produce exactly the same value as its counterpart at the origin.
+        // Combining 2^50 and fx into one f64 would leave only 2 bits for fx.
+        assert_eq!(
+            perlin.noise2d_split((far + 3, -far + 7), (fx, fy)),
+            perlin.noise2d_split((3, 7), (fx, fy))
+        );
+    }
+}
+
+#[test]
+fn test_perlin_ridge2d() {
+
+    let perlin = Perlin::new();
+    for _ in 0..10000 {
+        let (x, y): (f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0);
+        let val = perlin.ridge2d(x, y);
+        assert_eq!(val, (1.0 - 2.0 * perlin.noise2d(x, y).abs()).clamp(-1.0, 1.0));
+        assert!(val >= -1.0 && val <= 1.0);
+    }
+}
+
+#[test]
+fn test_perlin_from_seed() {
+    let first = Perlin::from_seed(42);
+    let second = Perlin::from_seed(42);
+    assert!(first == second);
+    assert!(first != Perlin::from_seed(43));
+
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(first.noise1d(x), second.noise1d(x));
+        assert_eq!(first.noise2d(x, y), second.noise2d(x, y));
+        assert_eq!(first.noise3d(x, y, z), second.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_perlin_from_perm_round_trip() {
+    let perlin = Perlin::new();
+    assert_eq!(perlin.perm().len(), 512);
+
+    let restored = Perlin::from_perm(perlin.perm().to_vec()).unwrap();
+    assert!(perlin == restored);
+
+    for _ in 0..1000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(perlin.noise3d(x, y, z), restored.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_perlin_from_perm_invalid() {
+    assert!(Perlin::from_perm(Vec::new()).is_err());
+
+    let mut perm: Vec<u8> = Perlin::new().perm().to_vec();
+    perm[511] = perm[255].wrapping_add(1);
+    assert!(Perlin::from_perm(perm).is_err());
+}
+
+#[test]
+fn test_perlin_fill2d() {
+    let perlin = Perlin::new();
+    let (width, origin, step) = (37usize, (-3.25, 8.5), (0.07, 0.11));
+
+    // A trailing partial row is left untouched.
+    let mut out: Vec<f64> = vec![2.0; width * 23 + 5];
+    perlin.fill2d(out.as_mut_slice(), width, origin, step);
+    for y in 0..23 {
+        for x in 0..width {
+            let expected = perlin.noise2d(origin.0 + x as f64 * step.0, origin.1 + y as f64 * step.1);
+            assert_eq!(out[y * width + x], expected);
+        }
+    }
+    assert!(out[width * 23..].iter().all(|&v| v == 2.0));
+}
+
+#[test]
+fn test_perlin_fill3d() {
+    let perlin = Perlin::new();
+    let (dims, origin, step) = ((4usize, 4usize, 4usize), [-1.5, 0.25, 7.0], [0.3, 0.45, 0.6]);
+
+    // Elements past the volume are left untouched.
+    let mut out: Vec<f64> = vec![2.0; 4 * 4 * 4 + 3];
+    perlin.fill3d(out.as_mut_slice(), dims, origin, step);
+    for z in 0..4 {
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = perlin.noise3d(
+                    origin[0] + x as f64 * step[0],
+                    origin[1] + y as f64 * step[1],
+                    origin[2] + z as f64 * step[2]
+                );
+                assert_eq!(out[(z * 4 + y) * 4 + x], expected);
+            }
+        }
+    }
+    assert!(out[4 * 4 * 4..].iter().all(|&v| v == 2.0));
+}
+
+#[test]
+#[should_panic]
+fn test_perlin_fill3d_buffer_too_small() {
+    let mut out: Vec<f64> = vec![0.0; 63];
+    Perlin::new().fill3d(out.as_mut_slice(), (4, 4, 4), [0.0; 3], [0.1; 3]);
+}
+
+#[test]
+fn test_perlin_set_seed() {
+    use crate::gen::Seedable;
+
+    let mut first = Perlin::new();
+    let mut second = Perlin::new();
+    first.set_seed(99);
+    second.set_seed(99);
+    assert!(first == second);
+    assert!(first == Perlin::from_seed(99));
+    assert_eq!(second.seed(), 99);
+}
+
+#[test]
+fn test_perlin_noise_point() {
+    let perlin = Perlin::new();
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(perlin.noise_point2([x, y]), perlin.noise2d(x, y));
+        assert_eq!(perlin.noise_point3([x, y, z]), perlin.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_perlin_fade_kind() {
+    use crate::utils::FadeKind;
+
+    let perlin = Perlin::new();
+    let quintic = perlin.clone().with_fade(FadeKind::Quintic);
+    let cubic = perlin.clone().with_fade(FadeKind::Cubic);
+    assert!(perlin == quintic);
+    assert!(perlin != cubic);
+
+    let mut differs = false;
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+        assert_eq!(quintic.noise1d(x), perlin.noise1d(x));
+        assert_eq!(quintic.noise2d(x, y), perlin.noise2d(x, y));
+        assert_eq!(quintic.noise3d(x, y, z), perlin.noise3d(x, y, z));
+        differs |= cubic.noise2d(x, y) != perlin.noise2d(x, y);
+
+        // Still continuous: a tiny step, even across a cell border, only
+        // moves the value a little.
+        let eps: f64 = 1e-6;
+        assert!((cubic.noise2d(x + eps, y) - cubic.noise2d(x, y)).abs() < 1e-4);
+        assert!((cubic.noise3d(x, y, z + eps) - cubic.noise3d(x, y, z)).abs() < 1e-4);
+        let border: f64 = x.floor();
+        assert!((cubic.noise2d(border - eps, y) - cubic.noise2d(border + eps, y)).abs() < 1e-4);
+    }
+    assert!(differs);
+}
+
+#[test]
+fn test_perlin_offset() {
+
+    let perlin = Perlin::new();
+    let offset = perlin.clone().with_offset(97);
+    assert!(perlin == perlin.clone().with_offset(0));
+    assert!(perlin != offset);
+
+    let mut differs = false;
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+        let val2 = offset.noise2d(x, y);
+        let val3 = offset.noise3d(x, y, z);
+        assert!(val2 >= -1.0 && val2 <= 1.0);
+        assert!(val3 >= -1.0 && val3 <= 1.0);
+        assert_eq!(offset.noise1d(x), perlin.noise1d(x));
+        differs |= val2 != perlin.noise2d(x, y);
+
+        // The offset shifts the field along y and z.
+        assert!((val2 - perlin.noise2d(x, y + 97.0)).abs() < 1e-9);
+        assert!((val3 - perlin.noise3d(x, y + 97.0, z + 97.0)).abs() < 1e-9);
+    }
+    assert!(differs);
+}
+
+#[test]
+fn test_perlin_offset_saturated_cell() {
+    // Huge coordinates saturate the lattice cell at i64::MAX, where adding
+    // the offset must wrap instead of overflowing.
+    let offset = Perlin::new().with_offset(255);
+    let coord: f64 = (1u64 << 63) as f64;
+    let val2 = offset.noise2d(coord, coord);
+    let val3 = offset.noise3d(coord, coord, coord);
+    assert!(val2 >= -1.0 && val2 <= 1.0);
+    assert!(val3 >= -1.0 && val3 <= 1.0);
+}
+
+#[test]
+fn test_perlin_with_frequencies() {
+    let perlin = Perlin::new();
+    let stretched = perlin.clone().with_frequencies((2.0, 0.5, 3.0));
+    assert!(perlin == perlin.clone().with_frequencies((1.0, 1.0, 1.0)));
+    assert!(perlin != stretched);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+
+        assert_eq!(stretched.noise1d(x), perlin.noise1d(x * 2.0));
+        assert_eq!(stretched.noise2d(x, y), perlin.noise2d(x * 2.0, y * 0.5));
+        assert_eq!(stretched.noise3d(x, y, z), perlin.noise3d(x * 2.0, y * 0.5, z * 3.0));
+    }
+
+    let mut grid = vec![0.0; 16 * 16];
+    stretched.fill2d(grid.as_mut_slice(), 16, (-1.5, 2.0), (0.3, 0.2));
+    for (idx, &val) in grid.iter().enumerate() {
+        let (x, y) = ((idx % 16) as f64, (idx / 16) as f64);
+        assert!((val - stretched.noise2d(-1.5 + x * 0.3, 2.0 + y * 0.2)).abs() < 1e-12);
+    }
+}
+
+/// Counts the sign changes of `perlin` along 16 lines parallel to x, or to y
+/// when `along_y` is set, each sampled across one 256-unit period.
+fn perlin_crossings(perlin: &Perlin, along_y: bool) -> usize {
+    let mut crossings: usize = 0;
+    for line in 0..16 {
+        let across: f64 = line as f64 * 7.3 + 0.4;
+        let sample = |t: f64| if along_y { perlin.noise2d(across, t) } else { perlin.noise2d(t, across) };
+        let mut prev: f64 = sample(0.0);
+        for step in 1..5120 {
+            let val: f64 = sample(step as f64 * 0.05);
+            if (val < 0.0) != (prev < 0.0) {
+                crossings += 1;
+            }
+            prev = val;
+        }
+    }
+
+    crossings
+}
+
+#[test]
+fn test_perlin_frequency_anisotropy() {
+    let perlin = Perlin::from_seed(7);
+    let stretched = perlin.clone().with_frequency([2.0, 1.0, 1.0]);
+    assert!(stretched == perlin.clone().with_frequencies((2.0, 1.0, 1.0)));
+
+    // Twice the features along x, the same along y.
+    let ratio_x: f64 = perlin_crossings(&stretched, false) as f64 / perlin_crossings(&perlin, false) as f64;
+    assert!(ratio_x > 1.9 && ratio_x < 2.1, "x crossings ratio {}", ratio_x);
+    let ratio_y: f64 = perlin_crossings(&stretched, true) as f64 / perlin_crossings(&perlin, true) as f64;
+    assert!(ratio_y > 0.9 && ratio_y < 1.1, "y crossings ratio {}", ratio_y);
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn test_perlin_fill_grid2d_f32_simd() {
+    use crate::utils::FadeKind;
+
+    for perlin in [Perlin::new(), Perlin::new().with_fade(FadeKind::Cubic).with_offset(97).with_frequencies((1.5, 0.75, 1.0))].iter() {
+        // 19 columns exercises both the four-lane body and the scalar tail.
+        let mut scalar: Vec<f32> = vec![0.0; 19 * 11];
+        let mut lanes: Vec<f32> = vec![0.0; 19 * 11];
+        perlin.fill_grid2d_f32(scalar.as_mut_slice(), 19, 11, (-4.0, 7.5), (0.13, 0.24));
+        perlin.fill_grid2d_f32_simd(lanes.as_mut_slice(), 19, 11, (-4.0, 7.5), (0.13, 0.24));
+
+        for (a, b) in lanes.iter().zip(scalar.iter()) {
+            assert!((*a - *b).abs() <= f32::EPSILON);
+        }
+    }
+}
+
+#[test]
+fn test_perlin_noise2d_tiled() {
+    let perlin = Perlin::new();
+    for &(px, py) in [(16i64, 16i64), (8, 32), (5, 7), (256, 256), (300, 3)].iter() {
+        for _ in 0..1000 {
+            let (x, y): (f64, f64) = (random::<f64>() * px as f64, random::<f64>() * py as f64);
+            let val = perlin.noise2d_tiled(x, y, px, py);
+            assert!(val >= -1.0 && val <= 1.0);
+
+            assert_eq!(perlin.noise2d_tiled(0.0, y, px, py), perlin.noise2d_tiled(px as f64, y, px, py));
+            assert_eq!(perlin.noise2d_tiled(x, 0.0, px, py), perlin.noise2d_tiled(x, py as f64, px, py));
+        }
+    }
+
+    // Away from the tile borders, the full 256 period matches noise2d.
+    assert_eq!(perlin.noise2d_tiled(3.25, 7.5, 256, 256), perlin.noise2d(3.25, 7.5));
+}
+
+#[test]
+#[should_panic]
+fn test_perlin_noise2d_tiled_zero_period() {
+    Perlin::new().noise2d_tiled(1.0, 1.0, 0, 16);
+}
+
+#[test]
+fn test_perlin_pinned_output() {
+    // Known values of the improved algorithm and its 0.188, 0.507 and 0.936
+    // scaling constants, so any change to the output is deliberate.
+    let perlin = Perlin::from_seed(42);
+
+    assert_eq!(perlin.noise1d(0.37), 0.60990583046652);
+    assert_eq!(perlin.noise1d(-12.81), -0.08180032553690404);
+    assert_eq!(perlin.noise2d(0.37, 1.91), -0.46614067853550617);
+    assert_eq!(perlin.noise2d(-12.81, 40.07), 0.11305392686875576);
+    assert_eq!(perlin.noise3d(0.37, 1.91, 2.53), 0.20326865504864136);
+    assert_eq!(perlin.noise3d(-12.81, 40.07, -7.77), 0.19055666257206855);
+}
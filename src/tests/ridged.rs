@@ -0,0 +1,61 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, RidgedMulti, Simplex };
+
+#[test]
+fn test_ridged_range() {
+    let ridged = RidgedMulti::new(Simplex::new(), 6);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 50.0, random::<f64>() * 50.0, random::<f64>() * 50.0);
+        let val2 = ridged.noise2d(x, y);
+        let val3 = ridged.noise3d(x, y, z);
+        assert!(val2 >= -1.0 && val2 <= 1.0);
+        assert!(val3 >= -1.0 && val3 <= 1.0);
+    }
+}
+
+#[test]
+fn test_ridged_range_small_offset() {
+    for &offset in [0.05, 0.2, 0.45].iter() {
+        let ridged = RidgedMulti::new(Simplex::new(), 6).with_offset(offset);
+        for _ in 0..10000 {
+            let (x, y, z): (f64, f64, f64) = (random::<f64>() * 50.0, random::<f64>() * 50.0, random::<f64>() * 50.0);
+            let val2 = ridged.noise2d(x, y);
+            let val3 = ridged.noise3d(x, y, z);
+            assert!(val2 >= -1.0 && val2 <= 1.0);
+            assert!(val3 >= -1.0 && val3 <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_ridged_crests_at_zero_crossings() {
+    let simplex = Simplex::new();
+    let ridged = RidgedMulti::new(simplex.clone(), 1);
+    let step: f64 = 0.001;
+    let sample = |i: usize| 10.0 + i as f64 * step;
+
+    let mut crossings: usize = 0;
+    for i in 5usize..20000 - 5 {
+        let (here, next) = (simplex.noise2d(sample(i), 3.0), simplex.noise2d(sample(i + 1), 3.0));
+        if here.signum() == next.signum() {
+            continue;
+        }
+        crossings += 1;
+
+        // The sample closest to the crossing sits on a crest of the ridged
+        // field, above its neighbours on either side.
+        let crest: usize = if here.abs() < next.abs() { i } else { i + 1 };
+        let val = ridged.noise2d(sample(crest), 3.0);
+        assert!(val > 0.98);
+        assert!(val >= ridged.noise2d(sample(crest - 5), 3.0));
+        assert!(val >= ridged.noise2d(sample(crest + 5), 3.0));
+    }
+    assert!(crossings > 0);
+}
+
+#[test]
+#[should_panic(expected = "ridged multifractal offset must be positive")]
+fn test_ridged_offset_not_positive() {
+    RidgedMulti::new(Simplex::new(), 6).with_offset(0.0);
+}
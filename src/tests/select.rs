@@ -0,0 +1,45 @@
+use crate::gen::{ NoiseGen, Checkerboard, Constant, Select };
+use crate::gen::combine::Add;
+
+#[test]
+fn test_select_hard() {
+    let select = Select::new(Constant::new(-0.5), Constant::new(0.5), Checkerboard::new(), 0.0, 1.0);
+    assert_eq!(select.noise1d(0.5), 0.5);
+    assert_eq!(select.noise1d(1.5), -0.5);
+    assert_eq!(select.noise2d(0.5, 0.5), 0.5);
+    assert_eq!(select.noise2d(1.5, 0.5), -0.5);
+    assert_eq!(select.noise3d(1.5, 1.5, 0.5), 0.5);
+}
+
+#[test]
+fn test_select_soft() {
+    // Averaging a checkerboard with a constant gives control values of
+    // 0.5 * (±1 + c), which can be placed anywhere around the bounds.
+    let control = |c: f64| Add::new(Checkerboard::new(), Constant::new(c));
+    let select = |c: f64| {
+        Select::new(Constant::new(-1.0), Constant::new(1.0), control(c), -0.5, 2.0)
+            .with_edge_falloff(0.25)
+    };
+
+    // Control -1.0 is outside of the falloff band: first source.
+    assert_eq!(select(-1.0).noise2d(1.5, 0.5), -1.0);
+    // Control 0.0 is past the band: second source.
+    assert_eq!(select(1.0).noise2d(1.5, 0.5), 1.0);
+    // Control -0.5 sits on the lower bound: an even blend.
+    assert!(select(0.0).noise2d(1.5, 0.5).abs() < 1e-12);
+    // Control -0.625 is a quarter into the band, control -0.375 three quarters.
+    let low = select(-0.25).noise2d(1.5, 0.5);
+    let high = select(0.25).noise2d(1.5, 0.5);
+    assert!(low > -1.0 && low < 0.0);
+    assert!(high > 0.0 && high < 1.0);
+    assert!((low + high).abs() < 1e-12);
+}
+
+#[test]
+fn test_select_falloff_limited_to_range() {
+    // A falloff wider than half the range never selects the first source
+    // at the center of the range.
+    let select = Select::new(Constant::new(-1.0), Constant::new(1.0), Constant::new(0.5), 0.0, 1.0)
+        .with_edge_falloff(10.0);
+    assert_eq!(select.noise2d(0.0, 0.0), 1.0);
+}
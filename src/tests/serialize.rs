@@ -0,0 +1,73 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Simplex, Perlin, Checkerboard };
+
+#[test]
+fn test_simplex_serde_round_trip() {
+    let simplex = Simplex::new()
+        .with_frequencies((2.0, 0.5, 1.0))
+        .with_gradient_seed(7);
+    let json = serde_json::to_string(&simplex).unwrap();
+    let restored: Simplex = serde_json::from_str(json.as_str()).unwrap();
+    assert!(simplex == restored);
+
+    for _ in 0..1000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(simplex.noise2d(x, y), restored.noise2d(x, y));
+        assert_eq!(simplex.noise3d(x, y, z), restored.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_perlin_serde_round_trip() {
+    let perlin = Perlin::new();
+    let json = serde_json::to_string(&perlin).unwrap();
+    let restored: Perlin = serde_json::from_str(json.as_str()).unwrap();
+    assert!(perlin == restored);
+}
+
+#[test]
+fn test_perm_serialized_as_base() {
+    let perlin = Perlin::new();
+    let value = serde_json::to_value(&perlin).unwrap();
+    assert_eq!(value["perm"].as_array().unwrap().len(), 256);
+}
+
+#[test]
+fn test_perm_rejects_wrong_length() {
+    let json = format!("{{\"perm\":{:?}}}", vec![0u8; 512]);
+    assert!(serde_json::from_str::<Perlin>(json.as_str()).is_err());
+}
+
+#[test]
+fn test_simplex_rejects_short_grad_map() {
+    let simplex = Simplex::new().with_gradient_seed(7);
+    let mut value = serde_json::to_value(&simplex).unwrap();
+    value["grad_map"] = serde_json::json!(vec![0u8; 255]);
+    assert!(serde_json::from_value::<Simplex>(value).is_err());
+
+    // Saved state without a gradient map still loads.
+    let mut value = serde_json::to_value(&simplex).unwrap();
+    value.as_object_mut().unwrap().remove("grad_map");
+    let restored: Simplex = serde_json::from_value(value).unwrap();
+    assert_eq!(restored.noise2d(1.5, 2.5), Simplex::from_perm(simplex.perm().to_vec()).unwrap().noise2d(1.5, 2.5));
+}
+
+#[test]
+fn test_simplex_rejects_bad_gradients() {
+    let simplex = Simplex::new().with_gradients(Simplex::default_gradients());
+    let json = serde_json::to_string(&simplex).unwrap();
+    assert!(simplex == serde_json::from_str::<Simplex>(json.as_str()).unwrap());
+
+    let mut value = serde_json::to_value(&simplex).unwrap();
+    value["gradients"] = serde_json::json!([]);
+    assert!(serde_json::from_value::<Simplex>(value).is_err());
+}
+
+#[test]
+fn test_checkerboard_serde_round_trip() {
+    let checkerboard = Checkerboard::new().with_rotation(0.5);
+    let json = serde_json::to_string(&checkerboard).unwrap();
+    let restored: Checkerboard = serde_json::from_str(json.as_str()).unwrap();
+    assert_eq!(checkerboard.noise2d(1.3, 2.7), restored.noise2d(1.3, 2.7));
+}
@@ -0,0 +1,39 @@
+use core::f64::consts::FRAC_1_SQRT_2;
+use rand::random;
+
+use crate::gen::NoiseGen;
+use crate::gen::shapes::{ Cylinders, Spheres };
+
+#[test]
+fn test_cylinders_periodic_in_radius() {
+    let cylinders = Cylinders::new(2.0);
+    let period: f64 = 0.5;
+    for _ in 0..10000 {
+        let r: f64 = random::<f64>() * 10.0;
+        let angle: f64 = random::<f64>() * 6.0;
+        let y: f64 = random::<f64>() * 10.0 - 5.0;
+        let (x, z) = (angle.cos(), angle.sin());
+
+        let val = cylinders.noise3d(r * x, y, r * z);
+        assert!(val >= -1.0 && val <= 1.0);
+        assert!((val - cylinders.noise3d((r + period) * x, -y, (r + period) * z)).abs() < 1e-9);
+        assert!((val + cylinders.noise3d((r + period * 0.5) * x, y, (r + period * 0.5) * z)).abs() < 1e-9);
+    }
+
+    assert_eq!(cylinders.noise3d(0.0, 3.0, 0.0), 1.0);
+    assert!((cylinders.noise2d(-0.25, 7.0) + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_spheres_periodic_in_radius() {
+    let spheres = Spheres::new(0.25);
+    let period: f64 = 4.0;
+    for _ in 0..10000 {
+        let r: f64 = random::<f64>() * 20.0;
+        let val = spheres.noise2d(r * FRAC_1_SQRT_2, r * FRAC_1_SQRT_2);
+        assert!(val >= -1.0 && val <= 1.0);
+        assert!((val - spheres.noise3d(0.0, r + period, 0.0)).abs() < 1e-9);
+        assert!((val - spheres.noise1d(-(r + 2.0 * period))).abs() < 1e-9);
+        assert!((val + spheres.noise4d(0.0, 0.0, 0.0, r + period * 0.5)).abs() < 1e-9);
+    }
+}
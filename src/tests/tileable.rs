@@ -0,0 +1,47 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Simplex, Tileable2d, Worley };
+
+#[test]
+fn test_tileable2d_wraps_edges() {
+    let period: (f64, f64) = (16.0, 8.0);
+    let tile = Tileable2d::new(Simplex::new(), period);
+    for _ in 0..10000 {
+        let (x, y): (f64, f64) = (random::<f64>() * period.0, random::<f64>() * period.1);
+
+        assert!((tile.noise2d(0.0, y) - tile.noise2d(period.0, y)).abs() < 1e-9);
+        assert!((tile.noise2d(x, 0.0) - tile.noise2d(x, period.1)).abs() < 1e-9);
+        assert!((tile.noise2d(x, y) - tile.noise2d(x + 3.0 * period.0, y - period.1)).abs() < 1e-9);
+        assert!((tile.noise1d(x) - tile.noise1d(x + period.0)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_tileable2d_range() {
+    let tile = Tileable2d::new(Simplex::new(), (32.0, 32.0));
+    for _ in 0..10000 {
+        let val = tile.noise2d(random::<f64>() * 32.0, random::<f64>() * 32.0);
+        assert!(val >= -1.0 && val <= 1.0);
+    }
+}
+
+#[test]
+fn test_tileable2d_worley() {
+    let period: (f64, f64) = (16.0, 8.0);
+    let tile = Tileable2d::new(Worley::new(), period);
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random::<f64>() * period.0, random::<f64>() * period.1);
+
+        let val = tile.noise2d(x, y);
+        assert!(val >= -1.0 && val <= 1.0);
+        assert!((val - tile.noise2d(x + period.0, y - period.1)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_tileable2d_noise3d() {
+    let tile = Tileable2d::new(Simplex::new(), (32.0, 32.0));
+    for &z in [-7.5, 0.0, 3.0, 1000.0].iter() {
+        assert_eq!(tile.noise3d(1.0, 2.0, z), tile.noise2d(1.0, 2.0));
+    }
+}
@@ -0,0 +1,46 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Perlin, Simplex, Turbulence };
+
+#[test]
+fn test_turbulence_zero_power() {
+    let perlin = Perlin::new();
+    let turbulence = Turbulence::new(perlin.clone(), Simplex::new()).with_power(0.0);
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(turbulence.noise1d(x), perlin.noise1d(x));
+        assert_eq!(turbulence.noise2d(x, y), perlin.noise2d(x, y));
+        assert_eq!(turbulence.noise3d(x, y, z), perlin.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_turbulence_distorts() {
+    let perlin = Perlin::new();
+    let turbulence = Turbulence::new(perlin.clone(), Simplex::new())
+        .with_power(0.5)
+        .with_frequency(2.0);
+    assert_eq!(turbulence.power(), 0.5);
+    assert_eq!(turbulence.frequency(), 2.0);
+
+    let differs = (0..100).any(|i| {
+        let (x, y): (f64, f64) = (i as f64 * 0.37, i as f64 * 0.21);
+        turbulence.noise2d(x, y) != perlin.noise2d(x, y)
+    });
+    assert!(differs);
+}
+
+#[test]
+fn test_turbulence_noise4d() {
+    let source = Simplex::from_seed(7);
+    let still = Turbulence::new(source.clone(), Simplex::new()).with_power(0.0);
+    let turbulence = Turbulence::new(source.clone(), Simplex::new()).with_power(0.5);
+
+    let mut differs: bool = false;
+    for _ in 0..10000 {
+        let (x, y, z, w): (f64, f64, f64, f64) = (random(), random(), random(), random());
+        assert_eq!(still.noise4d(x, y, z, w), source.noise4d(x, y, z, w));
+        differs |= turbulence.noise4d(x, y, z, w) != source.noise4d(x, y, z, w);
+    }
+    assert!(differs);
+}
@@ -0,0 +1,47 @@
+use rand::random;
+
+use crate::gen::{ NoiseGen, Simplex, UnitRange };
+
+#[test]
+fn test_unit_range_matches_remap() {
+    let simplex = Simplex::new();
+    let unit = UnitRange::new(simplex.clone());
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        assert_eq!(unit.noise1d(x), (simplex.noise1d(x) + 1.0) / 2.0);
+        assert_eq!(unit.noise2d(x, y), (simplex.noise2d(x, y) + 1.0) / 2.0);
+        assert_eq!(unit.noise3d(x, y, z), (simplex.noise3d(x, y, z) + 1.0) / 2.0);
+    }
+}
+
+#[test]
+fn test_unit_range_bounds() {
+    let unit = UnitRange::new(Simplex::new());
+    for _ in 0..10000 {
+        let val = unit.noise3d(random::<f64>() * 100.0, random::<f64>() * 100.0, random::<f64>() * 100.0);
+        assert!(val >= 0.0 && val <= 1.0);
+    }
+}
+
+#[test]
+fn test_unit_range_endpoints_and_overshoot() {
+    use crate::gen::Constant;
+    use crate::gen::modifier::ScaleBias;
+
+    assert_eq!(UnitRange::new(Constant::new(-1.0)).noise2d(0.5, 0.5), 0.0);
+    assert_eq!(UnitRange::new(Constant::new(0.0)).noise2d(0.5, 0.5), 0.5);
+    assert_eq!(UnitRange::new(Constant::new(1.0)).noise2d(0.5, 0.5), 1.0);
+
+    // A composed source overshooting [-1, 1] is clamped.
+    let loud = UnitRange::new(ScaleBias::new(Simplex::from_seed(3), 1.5, 0.0));
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random(), random(), random());
+        let (x, y, z) = (x * 50.0, y * 50.0, z * 50.0);
+        for &val in [loud.noise1d(x), loud.noise2d(x, y), loud.noise3d(x, y, z), loud.noise4d(x, y, z, x)].iter() {
+            assert!(val >= 0.0 && val <= 1.0);
+        }
+    }
+    assert_eq!(UnitRange::new(Constant::new(1.2)).noise1d(0.0), 1.0);
+    assert_eq!(UnitRange::new(Constant::new(-1.2)).noise3d(0.0, 0.0, 0.0), 0.0);
+    assert_eq!(loud.bounds(), (0.0, 1.0));
+}
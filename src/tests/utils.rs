@@ -0,0 +1,267 @@
+use alloc::vec::Vec;
+use rand::random;
+
+use crate::utils::fast_floor;
+
+#[test]
+fn test_fast_floor_integers() {
+    for i in -100..101 {
+        assert_eq!(fast_floor(i as f64), i);
+    }
+    assert_eq!(fast_floor(0.0), 0);
+    assert_eq!(fast_floor(-0.0), 0);
+}
+
+#[test]
+fn test_fast_floor_just_below_integers() {
+    assert_eq!(fast_floor(2.9999999), 2);
+    assert_eq!(fast_floor(-2.9999999), -3);
+    assert_eq!(fast_floor(0.9999999999999999), 0);
+    assert_eq!(fast_floor(-0.0000001), -1);
+}
+
+#[test]
+fn test_fast_floor_large_magnitudes() {
+    assert_eq!(fast_floor(1e15 + 0.5), 1000000000000000);
+    assert_eq!(fast_floor(-1e15 - 0.5), -1000000000000001);
+    assert_eq!(fast_floor(4503599627370496.0), 4503599627370496);
+    assert_eq!(fast_floor(-4503599627370496.0), -4503599627370496);
+    assert_eq!(fast_floor(9.0e18), 9000000000000000000);
+    assert_eq!(fast_floor(-9.0e18), -9000000000000000000);
+}
+
+#[test]
+fn test_fast_floor_saturates() {
+    for &x in [9.3e18, 1e19, 1e300, f64::INFINITY].iter() {
+        assert_eq!(fast_floor(x), i64::MAX);
+    }
+    for &x in [-9.3e18, -1e19, -1e300, f64::NEG_INFINITY].iter() {
+        assert_eq!(fast_floor(x), i64::MIN);
+    }
+}
+
+#[test]
+fn test_noise_saturated_cells() {
+    use crate::gen::{ NoiseGen, Perlin, Simplex, Worley };
+    use crate::utils::seeded_rng;
+
+    let perlin = Perlin::from_seed(0);
+    let simplex = Simplex::from_seed(0);
+    let worley = Worley::from_rng(&mut seeded_rng(0));
+
+    // Coordinates past the i64 range floor to i64::MIN or i64::MAX, where
+    // neighbouring cells and skew sums must wrap instead of overflowing.
+    let edge: f64 = (1u64 << 63) as f64;
+    for &x in [edge, -edge, 1e19, -1e19, 1e300, -1e300, f64::INFINITY, f64::NEG_INFINITY].iter() {
+        let _ = perlin.noise1d(x);
+        let _ = perlin.noise2d_tiled(x, x, 16, 16);
+        let _ = simplex.noise1d(x);
+        let _ = simplex.noise2d(x, x);
+        let _ = simplex.noise3d(x, x, x);
+        let _ = simplex.noise4d(x, x, x, x);
+        let _ = worley.noise1d(x);
+        let _ = worley.noise2d(x, x);
+        let _ = worley.noise3d(x, x, x);
+        let _ = worley.noise4d(x, x, x, x);
+    }
+}
+
+#[test]
+fn test_fast_floor_matches_floor() {
+    for _ in 0..10000 {
+        let x: f64 = (random::<f64>() - 0.5) * 2e6;
+        assert_eq!(fast_floor(x), x.floor() as i64);
+    }
+}
+
+#[test]
+fn test_perm_at() {
+    use crate::utils::perm_at;
+
+    let perm: Vec<u8> = (0..512).map(|i: usize| ((i * 7) & 255) as u8).collect();
+    for i in 0..512 {
+        assert_eq!(perm_at(perm.as_slice(), i), perm[i]);
+    }
+}
+
+/// Outputs of generators built from a fixed table, hashed bit for bit. The
+/// same value must come out with and without the `unsafe_perm` feature.
+#[test]
+fn test_perm_lookup_outputs_pinned() {
+
+    use crate::gen::{ NoiseGen, Perlin, Simplex };
+
+    let perm: Vec<u8> = (0..512).map(|i: usize| ((i & 255) * 167 + 13) as u8).collect();
+    let simplex = Simplex::from_perm(perm.clone()).unwrap();
+    let perlin = Perlin::from_perm(perm).unwrap();
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for i in 0..100 {
+        let x: f64 = i as f64 * 0.37 - 18.5;
+        let y: f64 = i as f64 * 0.23 - 7.25;
+        let z: f64 = 11.0 - i as f64 * 0.41;
+        let vals = [
+            simplex.noise2d(x, y),
+            simplex.noise3d(x, y, z),
+            perlin.noise2d(x, y),
+            perlin.noise3d(x, y, z)
+        ];
+        for &val in vals.iter() {
+            let bits: u64 = val.to_bits();
+            hash = (hash ^ bits).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    assert_eq!(hash, 0x43d91518d1564a19);
+}
+
+#[test]
+fn test_remap_identity() {
+    use crate::utils::remap;
+
+    for _ in 0..10000 {
+        let val: f64 = random::<f64>();
+        assert_eq!(remap(val, 0.0, 1.0, 0.0, 1.0), val);
+        assert!((remap(val * 2.0 - 1.0, -1.0, 1.0, -1.0, 1.0) - (val * 2.0 - 1.0)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_remap_ranges() {
+    use crate::utils::remap;
+
+    assert_eq!(remap(0.5, -1.0, 1.0, 0.0, 100.0), 75.0);
+    // Reversed target range.
+    assert_eq!(remap(-1.0, -1.0, 1.0, 1.0, 0.0), 1.0);
+    assert_eq!(remap(0.5, -1.0, 1.0, 1.0, 0.0), 0.25);
+    // Reversed source range.
+    assert_eq!(remap(0.5, 1.0, -1.0, 0.0, 1.0), 0.25);
+    // Out of range inputs are clamped.
+    assert_eq!(remap(3.0, -1.0, 1.0, 0.0, 10.0), 10.0);
+    assert_eq!(remap(3.0, -1.0, 1.0, 10.0, 0.0), 0.0);
+    assert_eq!(remap(-3.0, -1.0, 1.0, 10.0, 0.0), 10.0);
+}
+
+#[test]
+fn test_to_unit() {
+    use crate::utils::to_unit;
+
+    assert_eq!(to_unit(-1.0), 0.0);
+    assert_eq!(to_unit(0.0), 0.5);
+    assert_eq!(to_unit(1.0), 1.0);
+    assert_eq!(to_unit(1.5), 1.0);
+    assert_eq!(to_unit(-2.0), 0.0);
+}
+
+/// The gradient functions take the hash byte straight from the permutation
+/// table and only look at its low bits, so every byte must give the same
+/// gradient as its low nibble (low 5 bits for 4D).
+#[test]
+fn test_grad_hash_bytes() {
+    use crate::utils::grad::{ grad1, grad2, grad3, grad4 };
+
+    let (x, y, z, w): (f64, f64, f64, f64) = (0.3, -0.7, 0.11, 0.5);
+    for hash in 0..256 {
+        let byte: u8 = hash as u8;
+        assert_eq!(grad1(byte, x), grad1(byte & 15, x));
+        assert_eq!(grad2(byte, x, y), grad2(byte & 7, x, y));
+        assert_eq!(grad3(byte, x, y, z), grad3(byte & 15, x, y, z));
+        assert_eq!(grad4(byte, x, y, z, w), grad4(byte & 31, x, y, z, w));
+    }
+
+    // A few values of the gradient tables, spelled out.
+    assert_eq!(grad1(0, x), x);
+    assert_eq!(grad1(7, x), 8.0 * x);
+    assert_eq!(grad1(15, x), -8.0 * x);
+    assert_eq!(grad2(0, x, y), x + 2.0 * y);
+    assert_eq!(grad2(7, x, y), -y - 2.0 * x);
+    assert_eq!(grad3(0, x, y, z), x + y);
+    assert_eq!(grad3(13, x, y, z), -y + z);
+    assert_eq!(grad3(14, x, y, z), y - x);
+}
+
+#[test]
+fn test_grad4_deterministic_and_bounded() {
+    use crate::utils::grad::grad4;
+
+    for _ in 0..1000 {
+        let (x, y, z, w): (f64, f64, f64, f64) = (
+            random::<f64>() * 2.0 - 1.0,
+            random::<f64>() * 2.0 - 1.0,
+            random::<f64>() * 2.0 - 1.0,
+            random::<f64>() * 2.0 - 1.0
+        );
+        let norm: f64 = (x * x + y * y + z * z + w * w).sqrt();
+        for hash in 0..256 {
+            let val = grad4(hash as u8, x, y, z, w);
+            assert_eq!(val, grad4(hash as u8, x, y, z, w));
+            // Every gradient has three components of +-1, so its dot product
+            // is at most sqrt(3) times the length of the residual.
+            assert!(val.abs() <= 3.0f64.sqrt() * norm + 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_grad4_directions() {
+    use crate::utils::grad::grad4;
+
+    // Recover each gradient by dotting it with the unit axes.
+    let mut directions: Vec<[i32; 4]> = (0..32).map(|hash: u8| {
+        [grad4(hash, 1.0, 0.0, 0.0, 0.0) as i32,
+         grad4(hash, 0.0, 1.0, 0.0, 0.0) as i32,
+         grad4(hash, 0.0, 0.0, 1.0, 0.0) as i32,
+         grad4(hash, 0.0, 0.0, 0.0, 1.0) as i32]
+    }).collect();
+
+    for dir in directions.iter() {
+        assert_eq!(dir.iter().filter(|&&c| c == 0).count(), 1);
+        assert!(dir.iter().all(|&c| c >= -1 && c <= 1));
+    }
+
+    directions.sort();
+    directions.dedup();
+    assert_eq!(directions.len(), 32);
+}
+
+#[test]
+fn test_float_shim_matches_std() {
+    use crate::utils::float::Float;
+
+    for _ in 0..10000 {
+        let x: f64 = (random::<f64>() - 0.5) * 200.0;
+        let y: f64 = random::<f64>() * 4.0;
+        assert_eq!(Float::floor(x), x.floor());
+        assert_eq!(Float::round(x), x.round());
+        assert_eq!(Float::rem_euclid(x, 7.5), x.rem_euclid(7.5));
+        assert!((Float::sqrt(x.abs()) - x.abs().sqrt()).abs() < 1e-12);
+        assert!((Float::sin(x) - x.sin()).abs() < 1e-12);
+        assert!((Float::cos(x) - x.cos()).abs() < 1e-12);
+        assert_eq!(Float::sin_cos(x), (Float::sin(x), Float::cos(x)));
+        assert!((Float::powf(y, x / 50.0) - y.powf(x / 50.0)).abs() < 1e-9);
+        assert!((Float::powi(y, 3) - y.powi(3)).abs() < 1e-9);
+        assert!((Float::ln(y + 0.01) - (y + 0.01).ln()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_interp_endpoints_and_monotonic() {
+    use crate::utils::{ interp, smoothstep, smootherstep, Interp };
+
+    for &kind in [Interp::Linear, Interp::Smooth, Interp::Smoother].iter() {
+        assert_eq!(interp(kind, 0.0, -2.0, 3.0), -2.0);
+        assert_eq!(interp(kind, 1.0, -2.0, 3.0), 3.0);
+        assert_eq!(interp(kind, 0.5, -2.0, 3.0), 0.5);
+
+        let mut last: f64 = interp(kind, 0.0, -2.0, 3.0);
+        for i in 1..1001 {
+            let val = interp(kind, i as f64 / 1000.0, -2.0, 3.0);
+            assert!(val >= last);
+            last = val;
+        }
+    }
+
+    assert_eq!(smoothstep(0.25), 0.15625);
+    assert_eq!(smootherstep(0.25), 0.103515625);
+    assert_eq!(Interp::default(), Interp::Linear);
+}
@@ -0,0 +1,58 @@
+use rand::{ random, SeedableRng };
+use rand::rngs::{ SmallRng, StdRng };
+use rand_xorshift::XorShiftRng;
+
+use crate::gen::{ NoiseGen, Value };
+
+#[test]
+fn test_value_new() {
+    Value::new();
+}
+
+#[test]
+fn test_value_from_stdrng() {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(1234);
+
+    Value::from_rng(&mut rng);
+}
+
+#[test]
+fn test_value_from_smallrng() {
+    let mut rng: SmallRng = SeedableRng::seed_from_u64(1234);
+
+    Value::from_rng(&mut rng);
+}
+
+#[test]
+fn test_value_from_xorshiftrng() {
+    let mut rng: XorShiftRng = SeedableRng::seed_from_u64(1234);
+
+    Value::from_rng(&mut rng);
+}
+
+#[test]
+fn test_value_range() {
+    let value = Value::new();
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 100.0, random::<f64>() * 100.0, random::<f64>() * 100.0);
+        for val in [value.noise1d(x), value.noise2d(x, y), value.noise3d(x, y, z)].iter() {
+            assert!(*val >= -1.0 && *val <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_value_continuous() {
+    let value = Value::new();
+    let h: f64 = 1e-6;
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 100.0, random::<f64>() * 100.0, random::<f64>() * 100.0);
+
+        // The fade curve has a slope of at most 1.875 per unit, and each
+        // lattice value step is at most 2, so a step of h moves the output
+        // by no more than 1.875 * 2 * h per axis.
+        assert!((value.noise1d(x + h) - value.noise1d(x)).abs() < 4.0 * h);
+        assert!((value.noise2d(x + h, y + h) - value.noise2d(x, y)).abs() < 8.0 * h);
+        assert!((value.noise3d(x + h, y + h, z + h) - value.noise3d(x, y, z)).abs() < 12.0 * h);
+    }
+}
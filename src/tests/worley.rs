@@ -0,0 +1,198 @@
+use rand::{ random, SeedableRng };
+use rand::rngs::{ SmallRng, StdRng };
+use rand_xorshift::XorShiftRng;
+
+use crate::gen::{ NoiseGen, Worley, DistanceKind, FeatureKind };
+
+#[test]
+fn test_worley_new() {
+    Worley::new();
+}
+
+#[test]
+fn test_worley_from_stdrng() {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(1234);
+
+    Worley::from_rng(&mut rng);
+}
+
+#[test]
+fn test_worley_from_smallrng() {
+    let mut rng: SmallRng = SeedableRng::seed_from_u64(1234);
+
+    Worley::from_rng(&mut rng);
+}
+
+#[test]
+fn test_worley_range() {
+    let distances = [DistanceKind::Euclidean, DistanceKind::EuclideanSquared, DistanceKind::Manhattan, DistanceKind::Chebyshev];
+    let features = [FeatureKind::F1, FeatureKind::F2, FeatureKind::F2MinusF1, FeatureKind::CellValue];
+    for &distance in distances.iter() {
+        for &feature in features.iter() {
+            let worley = Worley::new().with_distance(distance).with_feature(feature);
+            for _ in 0..1000 {
+                let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+                let w: f64 = random::<f64>() * 20.0;
+                for val in [worley.noise1d(x), worley.noise2d(x, y), worley.noise3d(x, y, z), worley.noise4d(x, y, z, w)].iter() {
+                    assert!(*val >= -1.0 && *val <= 1.0);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_worley_reproducible() {
+    let mut first_rng: XorShiftRng = SeedableRng::seed_from_u64(1234);
+    let mut second_rng: XorShiftRng = SeedableRng::seed_from_u64(1234);
+    let first = Worley::from_rng(&mut first_rng);
+    let second = Worley::from_rng(&mut second_rng);
+
+    assert!(first == second);
+    for _ in 0..1000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+        assert_eq!(first.noise2d(x, y), second.noise2d(x, y));
+        assert_eq!(first.noise3d(x, y, z), second.noise3d(x, y, z));
+    }
+}
+
+#[test]
+fn test_worley_cell_borders_are_ridges() {
+    let worley = Worley::new().with_feature(FeatureKind::F2MinusF1);
+    let vals: Vec<f64> = (0..20000).map(|i| worley.noise2d(i as f64 * 0.001, 0.37)).collect();
+
+    let min = vals.iter().fold(1.0, |acc, v| if *v < acc { *v } else { acc });
+    let max = vals.iter().fold(-1.0, |acc, v| if *v > acc { *v } else { acc });
+
+    // Crossing a cell border, the two nearest feature points are equally far
+    // away and F2 - F1 drops to 0, i.e. -1 after normalization.
+    assert!(min < -0.95);
+    assert!(max > -0.5);
+
+    // The borders show up as sharp local minima in the sweep.
+    let ridges = (1..vals.len() - 1)
+        .filter(|&i| vals[i] < vals[i - 1] && vals[i] < vals[i + 1] && vals[i] < -0.9)
+        .count();
+    assert!(ridges >= 5);
+}
+
+#[test]
+fn test_worley_cell_value() {
+    let worley = Worley::from_rng(&mut StdRng::seed_from_u64(5));
+    let cells = worley.clone().with_feature(FeatureKind::CellValue);
+    let gap = worley.with_feature(FeatureKind::F2MinusF1);
+
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0);
+
+        // Well inside a cell, where F2 - F1 exceeds 0.1 * sqrt(2), a step of
+        // 0.02 changes it by at most 0.04 and cannot reach another cell.
+        if gap.noise2d(x, y) > -0.8 {
+            let val = cells.noise2d(x, y);
+            for &(dx, dy) in [(0.02, 0.0), (-0.02, 0.0), (0.0, 0.02), (0.0, -0.02), (0.014, 0.014)].iter() {
+                assert_eq!(cells.noise2d(x + dx, y + dy), val);
+            }
+        }
+    }
+
+    // Along a sweep the value only changes on cell borders, where F2 - F1
+    // drops to 0.
+    let vals: Vec<f64> = (0..20000).map(|i| cells.noise2d(i as f64 * 0.001, 0.37)).collect();
+    let mut changes: usize = 0;
+    for i in 1..vals.len() {
+        if vals[i] != vals[i - 1] {
+            changes += 1;
+            assert!(gap.noise2d((i as f64 - 0.5) * 0.001, 0.37) < -0.99);
+        }
+    }
+    assert!(changes >= 5);
+}
+
+#[test]
+fn test_worley_euclidean_squared() {
+    let worley = Worley::from_rng(&mut StdRng::seed_from_u64(11));
+    let squared = worley.clone().with_distance(DistanceKind::EuclideanSquared);
+    let cells = worley.clone().with_feature(FeatureKind::CellValue);
+    let squared_cells = squared.clone().with_feature(FeatureKind::CellValue);
+
+    for _ in 0..10000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+
+        // Squaring keeps the order of distances, so the nearest cell is the
+        // same.
+        assert_eq!(squared_cells.noise2d(x, y), cells.noise2d(x, y));
+        assert_eq!(squared_cells.noise3d(x, y, z), cells.noise3d(x, y, z));
+
+        // F1 is the square of the Euclidean F1, on its own scale.
+        let f1 = (worley.noise3d(x, y, z) + 1.0) * 0.5 * 3.0f64.sqrt();
+        let f1_squared = (squared.noise3d(x, y, z) + 1.0) * 0.5 * 3.0;
+        assert!((f1 * f1 - f1_squared).abs() < 1e-9);
+    }
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_worley_cell_color2() {
+    let worley = Worley::from_rng(&mut StdRng::seed_from_u64(5));
+    let gap = worley.clone().with_feature(FeatureKind::F2MinusF1);
+
+    for _ in 0..1000 {
+        let (x, y): (f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0);
+
+        // See test_worley_cell_value: these steps stay within the cell.
+        if gap.noise2d(x, y) > -0.8 {
+            let color = worley.cell_color2(x, y);
+            for &(dx, dy) in [(0.02, 0.0), (-0.02, 0.0), (0.0, 0.02), (0.0, -0.02), (0.014, 0.014)].iter() {
+                assert_eq!(worley.cell_color2(x + dx, y + dy), color);
+            }
+        }
+    }
+
+    // Each cell border along a sweep shows up as a sharp minimum of F2 - F1,
+    // and the colors on either side of it differ.
+    let gaps: Vec<f64> = (0..20000).map(|i| gap.noise2d(i as f64 * 0.001, 0.37)).collect();
+    let mut borders: usize = 0;
+    for i in 1..gaps.len() - 1 {
+        if gaps[i] < gaps[i - 1] && gaps[i] < gaps[i + 1] && gaps[i] < -0.99 {
+            borders += 1;
+            let before = worley.cell_color2((i - 1) as f64 * 0.001, 0.37);
+            let after = worley.cell_color2((i + 1) as f64 * 0.001, 0.37);
+            assert!(before != after, "same color across the border at x = {}", i as f64 * 0.001);
+        }
+    }
+    assert!(borders >= 5);
+}
+
+#[test]
+fn test_worley_noise4d_integer_w() {
+    let worley = Worley::from_rng(&mut StdRng::seed_from_u64(17));
+    let gap = worley.clone().with_feature(FeatureKind::F2MinusF1);
+    let cells = worley.clone().with_feature(FeatureKind::CellValue);
+
+    for _ in 0..1000 {
+        let (x, y, z): (f64, f64, f64) = (random::<f64>() * 20.0, random::<f64>() * 20.0, random::<f64>() * 20.0);
+        let w: f64 = (random::<f64>() * 20.0).floor();
+
+        // F1 moves no faster than the coordinate, in w as in x, y and z;
+        // with the normalization by sqrt(4) / 2 the values do too.
+        let val = worley.noise4d(x, y, z, w);
+        assert!((worley.noise4d(x, y, z, w + 0.01) - val).abs() <= 0.01 + 1e-12);
+        assert!((worley.noise4d(x + 0.01, y, z, w) - val).abs() <= 0.01 + 1e-12);
+    }
+
+    // Held at an integer w, the 4D field is a 3D cellular field: F2 - F1
+    // drops to 0 on the cell borders and the cell value is constant in
+    // between.
+    let w: f64 = 3.0;
+    let gaps: Vec<f64> = (0..20000).map(|i| gap.noise4d(i as f64 * 0.001, 0.37, 1.5, w)).collect();
+    let vals: Vec<f64> = (0..20000).map(|i| cells.noise4d(i as f64 * 0.001, 0.37, 1.5, w)).collect();
+    assert!(gaps.iter().any(|&g| g < -0.95));
+    let mut changes: usize = 0;
+    for i in 1..vals.len() {
+        if vals[i] != vals[i - 1] {
+            changes += 1;
+            assert!(gap.noise4d((i as f64 - 0.5) * 0.001, 0.37, 1.5, w) < -0.99);
+        }
+    }
+    assert!(changes >= 5);
+}
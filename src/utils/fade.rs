@@ -1,5 +1,23 @@
-/// C(2) continuous interpolant
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+
+/// The curve used to ease interpolation between lattice points.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FadeKind {
+    /// Hermite smoothstep, `3t² - 2t³`. C(1) continuous and cheaper to
+    /// evaluate, but its second derivative jumps at cell borders.
+    Cubic,
+    /// Perlin's improved curve, `6t⁵ - 15t⁴ + 10t³`. C(2) continuous.
+    #[default]
+    Quintic
+}
+
+/// Eases `t` in [0, 1] along the curve `kind`.
 #[inline]
-pub fn fade(t: f64) -> f64 {
-    t * t * t * ( t * ( t * 6.0 - 15.0 ) + 10.0 )
+pub fn fade(kind: FadeKind, t: f64) -> f64 {
+    match kind {
+        FadeKind::Cubic => t * t * ( 3.0 - 2.0 * t ),
+        FadeKind::Quintic => t * t * t * ( t * ( t * 6.0 - 15.0 ) + 10.0 )
+    }
 }
@@ -1,9 +1,13 @@
 /// Fast f64 to int floor function.
+///
+/// Matches `f64::floor` for every finite input within the range of an `i64`,
+/// including negative numbers, exact integers and -0.0. Inputs beyond that
+/// range, including the infinities, saturate to `i64::MIN` or `i64::MAX`.
 #[inline]
 pub fn fast_floor(x: f64) -> i64 {
-    if x > 0.0 {
-        x as i64
-    } else {
-        (x as i64) - 1
-    }
+    // Truncation rounds towards zero, which is one too high for negative
+    // non-integers. The cast saturates, so below the i64 range the step
+    // down must saturate too.
+    let i: i64 = x as i64;
+    if x < i as f64 { i.saturating_sub(1) } else { i }
 }
@@ -0,0 +1,86 @@
+//! Floating point functions for `no_std` builds.
+//!
+//! Without `std`, `f64` lacks the methods that std implements on top of the
+//! platform's math library. Importing `Float` provides them from `libm`
+//! under the same names, so the generators read the same in both builds.
+//!
+//! When another crate links std anyway, as in tests and examples, std's
+//! methods take precedence and the imports go unused, so they allow
+//! `unused_imports`.
+
+/// The `f64` methods the generators need that `core` does not provide.
+pub trait Float {
+    /// Largest integer less than or equal to `self`.
+    fn floor(self) -> Self;
+    /// Nearest integer, rounding half-way cases away from zero.
+    fn round(self) -> Self;
+    /// Square root.
+    fn sqrt(self) -> Self;
+    /// Sine, in radians.
+    fn sin(self) -> Self;
+    /// Cosine, in radians.
+    fn cos(self) -> Self;
+    /// Sine and cosine, in radians.
+    fn sin_cos(self) -> (Self, Self) where Self: Sized;
+    /// `self` raised to a floating point power.
+    fn powf(self, n: Self) -> Self;
+    /// `self` raised to an integer power.
+    fn powi(self, n: i32) -> Self;
+    /// Least non-negative remainder of `self` divided by `rhs`.
+    fn rem_euclid(self, rhs: Self) -> Self;
+    /// Natural logarithm.
+    fn ln(self) -> Self;
+}
+
+impl Float for f64 {
+    #[inline]
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    #[inline]
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+
+    #[inline]
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    #[inline]
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    #[inline]
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    #[inline]
+    fn sin_cos(self) -> (f64, f64) {
+        libm::sincos(self)
+    }
+
+    #[inline]
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    #[inline]
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    #[inline]
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let r: f64 = self % rhs;
+        if r < 0.0 { r + rhs.abs() } else { r }
+    }
+
+    #[inline]
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+}
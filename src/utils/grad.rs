@@ -1,6 +1,6 @@
 //! Helper functions to compute gradients-dot-residual vectors (1D to 4D).
 
-use utils::if_else;
+use crate::utils::if_else;
 
 /// Compute 1D gradient-dot-residual vector.
 pub fn grad1(hash: u8, x: f64) -> f64 {
@@ -24,6 +24,15 @@ pub fn grad2(hash: u8, x: f64, y: f64) -> f64 {
     if_else(h & 1 != 0, -u, u) + if_else(h & 2 != 0, -2.0 * v, 2.0 * v)
 }
 
+/// Look up the 2D gradient vector that `grad2` dots with the residual.
+pub fn grad2_vec(hash: u8) -> (f64, f64) {
+    let h: u8 = hash & 7;
+    let su: f64 = if_else(h & 1 != 0, -1.0, 1.0);
+    let sv: f64 = if_else(h & 2 != 0, -2.0, 2.0);
+
+    if h < 4 { (su, sv) } else { (sv, su) }
+}
+
 /// Compute 3D gradient-dot-residual vector.
 pub fn grad3(hash: u8, x: f64, y: f64, z: f64) -> f64 {
     // Convert low 4 bits of hash code into 12 simple gradient directions,
@@ -35,3 +44,32 @@ pub fn grad3(hash: u8, x: f64, y: f64, z: f64) -> f64 {
 
     if_else(h & 1 != 0, -u, u) + if_else(h & 2 != 0, -v, v)
 }
+
+/// Look up the 3D gradient vector that `grad3` dots with the residual.
+pub fn grad3_vec(hash: u8) -> (f64, f64, f64) {
+    let h: u8 = hash & 15;
+    let su: f64 = if_else(h & 1 != 0, -1.0, 1.0);
+    let sv: f64 = if_else(h & 2 != 0, -1.0, 1.0);
+
+    if h < 4 {
+        (su, sv, 0.0)
+    } else if h < 8 {
+        (su, 0.0, sv)
+    } else if h == 12 || h == 14 {
+        (sv, su, 0.0)
+    } else {
+        (0.0, su, sv)
+    }
+}
+
+/// Compute 4D gradient-dot-residual vector.
+pub fn grad4(hash: u8, x: f64, y: f64, z: f64, w: f64) -> f64 {
+    // Convert low 5 bits of hash code into 32 simple gradient directions,
+    // and compute dot product.
+    let h: u8 = hash & 31;
+    let u: f64 = if_else(h < 24, x, y);
+    let v: f64 = if_else(h < 16, y, z);
+    let t: f64 = if_else(h < 8, z, w);
+
+    if_else(h & 1 != 0, -u, u) + if_else(h & 2 != 0, -v, v) + if_else(h & 4 != 0, -t, t)
+}
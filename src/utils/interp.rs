@@ -0,0 +1,57 @@
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+
+use crate::utils::{ fade, lerp, FadeKind };
+
+/// Hermite smoothstep, `3t² - 2t³`, for `t` in [0, 1].
+///
+/// Eases in and out with a zero slope at both ends. Cheaper than
+/// `smootherstep`, at the cost of a jump in the second derivative.
+#[inline]
+pub fn smoothstep(t: f64) -> f64 {
+    fade(FadeKind::Cubic, t)
+}
+
+/// Perlin's smootherstep, `6t⁵ - 15t⁴ + 10t³`, for `t` in [0, 1].
+///
+/// The same curve as `fade` with `FadeKind::Quintic`; both its first and
+/// second derivatives are zero at the ends.
+#[inline]
+pub fn smootherstep(t: f64) -> f64 {
+    fade(FadeKind::Quintic, t)
+}
+
+/// How `interp` eases between two values.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Interp {
+    /// Straight `lerp`, with a kink at both ends.
+    #[default]
+    Linear,
+    /// Eased with `smoothstep`.
+    Smooth,
+    /// Eased with `smootherstep`.
+    Smoother
+}
+
+/// Interpolates from `a` at `t = 0` to `b` at `t = 1`, easing `t` along
+/// the curve `kind`.
+///
+/// # Example
+///
+/// ```rust
+/// use noisy::utils::{interp, Interp};
+///
+/// assert_eq!(interp(Interp::Linear, 0.25, 0.0, 4.0), 1.0);
+/// assert_eq!(interp(Interp::Smooth, 0.5, 0.0, 4.0), 2.0);
+/// ```
+#[inline]
+pub fn interp(kind: Interp, t: f64, a: f64, b: f64) -> f64 {
+    let t: f64 = match kind {
+        Interp::Linear => t,
+        Interp::Smooth => smoothstep(t),
+        Interp::Smoother => smootherstep(t)
+    };
+
+    lerp(t, a, b)
+}
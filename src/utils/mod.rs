@@ -1,13 +1,30 @@
 //! Miscelaneous, helper functions.
 
-pub use utils::fast_floor::fast_floor;
-pub use utils::if_else::if_else;
-pub use utils::lerp::lerp;
-pub use utils::fade::fade;
+pub use crate::utils::fast_floor::fast_floor;
+pub use crate::utils::if_else::if_else;
+pub use crate::utils::lerp::lerp;
+pub use crate::utils::fade::{ fade, FadeKind };
+pub use crate::utils::interp::{ interp, smoothstep, smootherstep, Interp };
+pub use crate::utils::seeded_rng::seeded_rng;
+pub use crate::utils::portable_perm::portable_perm;
+pub use crate::utils::validate_perm::validate_perm;
+pub(crate) use crate::utils::perm_at::perm_at;
+pub use crate::utils::remap::{ remap, to_unit };
 
 pub mod grad;
+#[cfg(feature = "serde")]
+pub mod serde_perm;
+// Also built for tests, which check it against std.
+#[cfg(any(test, not(feature = "std")))]
+pub mod float;
 
 mod fast_floor;
 mod if_else;
 mod lerp;
 mod fade;
+mod interp;
+mod seeded_rng;
+mod portable_perm;
+mod validate_perm;
+mod perm_at;
+mod remap;
@@ -0,0 +1,27 @@
+/// Looks up entry `idx` of a permutation table.
+///
+/// The generators mask their lattice coordinates so every index they build
+/// stays within their 512-entry tables. With the `unsafe_perm` feature
+/// enabled that guarantee is relied upon and the bounds check is skipped,
+/// which speeds up the nested lookups in the hot noise loops.
+#[cfg(not(feature = "unsafe_perm"))]
+#[inline(always)]
+pub(crate) fn perm_at(perm: &[u8], idx: usize) -> u8 {
+    perm[idx]
+}
+
+/// Looks up entry `idx` of a permutation table.
+///
+/// The generators mask their lattice coordinates so every index they build
+/// stays within their 512-entry tables. With the `unsafe_perm` feature
+/// enabled that guarantee is relied upon and the bounds check is skipped,
+/// which speeds up the nested lookups in the hot noise loops.
+///
+/// This is crate-private: it is only sound because every caller masks its
+/// index into the table.
+#[cfg(feature = "unsafe_perm")]
+#[inline(always)]
+pub(crate) fn perm_at(perm: &[u8], idx: usize) -> u8 {
+    debug_assert!(idx < perm.len());
+    unsafe { *perm.get_unchecked(idx) }
+}
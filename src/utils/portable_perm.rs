@@ -0,0 +1,35 @@
+use alloc::vec::Vec;
+
+/// Advances a SplitMix64 state and returns its next output.
+///
+/// SplitMix64 is Sebastiano Vigna's 64-bit mixing generator; the constants
+/// below are the ones from his reference implementation.
+#[inline]
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z: u64 = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+    z ^ (z >> 31)
+}
+
+/// Builds a 512-entry permutation table from an integer seed, without going
+/// through `rand`.
+///
+/// The bytes 0 to 255 are shuffled with a Fisher–Yates shuffle, walking `i`
+/// down from 255 to 1 and swapping entry `i` with entry
+/// `splitmix64() % (i + 1)`, where the SplitMix64 state starts out as
+/// `seed`. The second half of the table mirrors the first. Only wrapping
+/// integer arithmetic is involved, so a seed gives the same table on every
+/// platform and every version of the crate and its dependencies.
+pub fn portable_perm(seed: u64) -> Vec<u8> {
+    let mut state: u64 = seed;
+    let mut p: Vec<u8> = (0..256).map(|idx: u32| idx as u8).collect();
+    for i in (1..256).rev() {
+        let j: usize = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+        p.swap(i, j);
+    }
+
+    (0..512).map(|idx: usize| p[idx & 255]).collect()
+}
@@ -0,0 +1,35 @@
+/// Linearly maps `value` from `[in_min, in_max]` to `[out_min, out_max]`,
+/// clamping the result to the target range.
+///
+/// Either range may be reversed, e.g. `remap(v, -1.0, 1.0, 1.0, 0.0)` maps
+/// -1 to 1 and 1 to 0. `in_min` and `in_max` must differ.
+///
+/// # Example
+///
+/// ```rust
+/// use noisy::utils::remap;
+///
+/// // Map noise to a height in meters.
+/// assert_eq!(remap(0.5, -1.0, 1.0, 0.0, 100.0), 75.0);
+/// ```
+#[inline]
+pub fn remap(value: f64, in_min: f64, in_max: f64, out_min: f64, out_max: f64) -> f64 {
+    let t: f64 = (value - in_min) / (in_max - in_min);
+    let out: f64 = out_min + t * (out_max - out_min);
+
+    out.max(out_min.min(out_max)).min(out_min.max(out_max))
+}
+
+/// Maps a noise value from [-1, 1] to [0, 1], clamping the result.
+///
+/// # Example
+///
+/// ```rust
+/// use noisy::utils::to_unit;
+///
+/// assert_eq!(to_unit(0.5), 0.75);
+/// ```
+#[inline]
+pub fn to_unit(value: f64) -> f64 {
+    ((value + 1.0) * 0.5).clamp(0.0, 1.0)
+}
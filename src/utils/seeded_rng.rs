@@ -0,0 +1,18 @@
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+/// Creates a XorShiftRng deterministically seeded from a single integer.
+///
+/// The 64 bits of `seed` are spread over the four words of XorShift state,
+/// two of them mixed with fixed constants so the state is never all zeros.
+pub fn seeded_rng(seed: u64) -> XorShiftRng {
+    let lo: u32 = (seed & 0xffffffff) as u32;
+    let hi: u32 = (seed >> 32) as u32;
+
+    let mut bytes: [u8; 16] = [0; 16];
+    for (chunk, word) in bytes.chunks_mut(4).zip([lo, hi, lo ^ 0x9e3779b9, hi ^ 0x7f4a7c15].iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+
+    XorShiftRng::from_seed(bytes)
+}
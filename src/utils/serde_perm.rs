@@ -0,0 +1,25 @@
+//! Serializes a mirrored 512-entry permutation table as its 256-byte base.
+//!
+//! Used through `#[serde(with = "serde_perm")]` on the `perm` fields of the
+//! generators, so saved state is half the size and cannot be inconsistent.
+
+use alloc::vec::Vec;
+use alloc::format;
+
+use serde::{ Serialize, Serializer, Deserialize, Deserializer };
+use serde::de::Error;
+
+/// Serializes the first 256 entries of `perm`.
+pub fn serialize<S: Serializer>(perm: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    perm[..256].serialize(serializer)
+}
+
+/// Deserializes a 256-byte base permutation and mirrors it to 512 entries.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let base: Vec<u8> = Deserialize::deserialize(deserializer)?;
+    if base.len() != 256 {
+        return Err(D::Error::custom(format!("permutation has {} entries, expected 256", base.len())));
+    }
+
+    Ok((0..512).map(|idx: usize| base[idx & 255]).collect())
+}
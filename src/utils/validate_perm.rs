@@ -0,0 +1,18 @@
+use alloc::format;
+use alloc::string::String;
+
+/// Checks that `perm` is a 512-entry permutation table whose second half
+/// mirrors the first, as built by the generators' constructors.
+pub fn validate_perm(perm: &[u8]) -> Result<(), String> {
+    if perm.len() != 512 {
+        return Err(format!("permutation table has {} entries, expected 512", perm.len()));
+    }
+
+    for i in 256..512 {
+        if perm[i] != perm[i & 255] {
+            return Err(format!("permutation table entry {} does not mirror entry {}", i, i & 255));
+        }
+    }
+
+    Ok(())
+}